@@ -1,32 +1,93 @@
-#![feature(test, crate_visibility_modifier)]
+#![feature(test)]
 extern crate peg;
 
 extern crate test;
 
 use test::Bencher;
 
-peg::parser!(grammar parser() for str {
-crate rule expr() = eq()
+// The same grammar with each `#[cache]` storage; `parser` picks it from the input length.
+macro_rules! expr_parser {
+    ($name:ident $(, $storage:ident)?) => {
+        peg::parser!(grammar $name() for str {
+        $(#![cache($storage)])?
 
-#[cache]
-rule eq() = additive() "=" eq() / additive()
-#[cache]
-rule additive() = multitive() "+" additive() / multitive()
-#[cache]
-rule multitive() = pow() "*" multitive() / pow()
-#[cache]
-rule pow() = atom() "^" pow() / atom()
+        pub rule expr() = eq()
 
-#[cache]
-rule atom() = ['0'..='9']+ / "(" expr() ")"
-});
+        #[cache]
+        rule eq() = additive() "=" eq() / additive()
+        #[cache]
+        rule additive() = multitive() "+" additive() / multitive()
+        #[cache]
+        rule multitive() = pow() "*" multitive() / pow()
+        #[cache]
+        rule pow() = atom() "^" pow() / atom()
 
-#[bench]
-fn expr(b: &mut Bencher) {
-    let bench_str = "1+2+3+4*5*6^7^8^(0^1*2+1)";
+        #[cache]
+        rule atom() = ['0'..='9']+ / "(" expr() ")"
+        });
+    };
+}
+
+expr_parser!(parser);
+expr_parser!(dense, dense);
+expr_parser!(chunked, chunked);
+expr_parser!(hash, hash);
 
-    b.bytes = bench_str.len() as u64;
+const SHORT: &str = "1+2+3+4*5*6^7^8^(0^1*2+1)";
+
+fn long() -> String {
+    let mut s = String::from(SHORT);
+    for _ in 0..5000 {
+        s.push_str("+(");
+        s.push_str(SHORT);
+        s.push(')');
+    }
+    s
+}
+
+fn bench(b: &mut Bencher, input: &str, parse: fn(&str) -> Result<(), peg::error::ParseError<peg::str::LineCol>>) {
+    b.bytes = input.len() as u64;
     b.iter(|| {
-        parser::expr(bench_str).unwrap();
+        parse(input).unwrap();
     });
 }
+
+#[bench]
+fn expr(b: &mut Bencher) {
+    bench(b, SHORT, parser::expr);
+}
+
+#[bench]
+fn expr_dense(b: &mut Bencher) {
+    bench(b, SHORT, dense::expr);
+}
+
+#[bench]
+fn expr_chunked(b: &mut Bencher) {
+    bench(b, SHORT, chunked::expr);
+}
+
+#[bench]
+fn expr_hash(b: &mut Bencher) {
+    bench(b, SHORT, hash::expr);
+}
+
+#[bench]
+fn expr_long(b: &mut Bencher) {
+    bench(b, &long(), parser::expr);
+}
+
+#[bench]
+fn expr_long_dense(b: &mut Bencher) {
+    bench(b, &long(), dense::expr);
+}
+
+#[bench]
+fn expr_long_chunked(b: &mut Bencher) {
+    bench(b, &long(), chunked::expr);
+}
+
+#[bench]
+fn expr_long_hash(b: &mut Bencher) {
+    bench(b, &long(), hash::expr);
+}
@@ -43,9 +43,61 @@ rule string()
     = "\"" (!"\"" [_])* "\""
 });
 
-#[bench]
-fn json(b: &mut Bencher) {
-    let bench_str = r#"
+// The same grammar with `#[cache]` on `value` and `string`, once with each storage for the
+// tables; `cached` picks it from the input length. `**` is spelled out because
+// `macro_rules!` splits it into two tokens.
+macro_rules! cached_json_parser {
+    ($name:ident $(, $storage:ident)?) => {
+        peg::parser!(grammar $name() for str {
+        $(#![cache($storage)])?
+
+        // JSON grammar (RFC 4627). Note that this only checks for valid JSON and does not build a syntax
+        // tree.
+
+        pub rule json() = _ (object() / array()) _
+
+        rule _() = [' ' | '\t' | '\r' | '\n']*
+        rule value_separator() = _ "," _
+
+        #[cache]
+        rule value()
+            = "false" / "true" / "null" / object() / array() / number() / string()
+
+        rule object()
+            = "{" _ (member() (value_separator() member())*)? _ "}"
+
+        rule member()
+            = string() _ ":" _ value()
+
+        rule array()
+            = "[" _ (value() (value_separator() value())*)? _ "]"
+
+        rule number()
+            = "-"? int() frac()? exp()? {}
+
+        rule int()
+            = ['0'] / ['1'..='9']['0'..='9']*
+
+        rule exp()
+            = ("e" / "E") ("-" / "+")? ['0'..='9']*<1,>
+
+        rule frac()
+            = "." ['0'..='9']*<1,>
+
+        // note: escaped chars not handled
+        #[cache]
+        rule string()
+            = "\"" (!"\"" [_])* "\""
+        });
+    };
+}
+
+cached_json_parser!(cached);
+cached_json_parser!(dense, dense);
+cached_json_parser!(chunked, chunked);
+cached_json_parser!(hash, hash);
+
+const JSON: &str = r#"
 {
 	"X": 0.6e2,
 	"Y": 5,
@@ -69,8 +121,76 @@ fn json(b: &mut Bencher) {
 }
 "#;
 
+#[bench]
+fn json(b: &mut Bencher) {
+    let bench_str = JSON;
+
     b.bytes = bench_str.len() as u64;
     b.iter(|| {
         parser::json(bench_str).unwrap();
     });
 }
+
+fn long() -> String {
+    let mut s = String::from("[");
+    for i in 0..2000 {
+        if i > 0 {
+            s.push(',');
+        }
+        s.push_str(JSON);
+    }
+    s.push(']');
+    s
+}
+
+fn bench(b: &mut Bencher, input: &str, parse: fn(&str) -> Result<(), peg::error::ParseError<peg::str::LineCol>>) {
+    b.bytes = input.len() as u64;
+    b.iter(|| {
+        parse(input).unwrap();
+    });
+}
+
+#[bench]
+fn json_cached(b: &mut Bencher) {
+    bench(b, JSON, cached::json);
+}
+
+#[bench]
+fn json_dense(b: &mut Bencher) {
+    bench(b, JSON, dense::json);
+}
+
+#[bench]
+fn json_chunked(b: &mut Bencher) {
+    bench(b, JSON, chunked::json);
+}
+
+#[bench]
+fn json_hash(b: &mut Bencher) {
+    bench(b, JSON, hash::json);
+}
+
+#[bench]
+fn json_long(b: &mut Bencher) {
+    bench(b, &long(), parser::json);
+}
+
+#[bench]
+fn json_long_cached(b: &mut Bencher) {
+    bench(b, &long(), cached::json);
+}
+
+#[bench]
+fn json_long_dense(b: &mut Bencher) {
+    bench(b, &long(), dense::json);
+}
+
+#[bench]
+fn json_long_chunked(b: &mut Bencher) {
+    bench(b, &long(), chunked::json);
+}
+
+#[bench]
+fn json_long_hash(b: &mut Bencher) {
+    bench(b, &long(), hash::json);
+}
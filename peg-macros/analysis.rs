@@ -1,12 +1,19 @@
-use proc_macro2::Span;
-use std::collections::HashMap;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use std::collections::{HashMap, HashSet};
 
 use crate::ast::*;
 
 pub struct GrammarAnalysis<'a> {
     pub rules: HashMap<String, &'a Rule>,
     pub left_recursion: Vec<LeftRecursionError>,
+    pub left_rec_groups: Vec<LeftRecGroup>,
+    pub left_rec_params: Vec<LeftRecParamsError>,
     pub loop_nullability: Vec<LoopNullabilityError>,
+    pub recovering_rules: HashSet<String>,
+    pub cached_recovery: Vec<CachedRecoveryError>,
+    pub incremental_borrow: Vec<IncrementalBorrowError>,
+    pub cache_keys: Vec<CacheKeyError>,
+    pub lexical_calls: Vec<LexicalCallError>,
 }
 
 pub fn check<'a>(grammar: &'a Grammar) -> GrammarAnalysis<'a> {
@@ -17,26 +24,44 @@ pub fn check<'a>(grammar: &'a Grammar) -> GrammarAnalysis<'a> {
         rules.entry(rule.name.to_string()).or_insert(rule);
     }
 
-    let (rule_nullability, left_recursion) = LeftRecursionVisitor::check(grammar, &rules);
+    let (rule_nullability, left_calls, left_recursion) = LeftRecursionVisitor::check(grammar, &rules);
+    let (left_rec_groups, left_rec_params) = LeftRecGroup::find(grammar, &rules, &left_calls);
     let loop_nullability = LoopNullabilityVisitor::check(grammar, &rule_nullability);
+    let (recovering_rules, cached_recovery) = RecoveryVisitor::check(grammar);
+    let incremental_borrow = check_incremental_borrow(grammar);
+    let cache_keys = check_cache_keys(grammar, &rules);
+    let lexical_calls = check_lexical_calls(grammar, &rules);
 
     GrammarAnalysis {
         rules,
         left_recursion,
+        left_rec_groups,
+        left_rec_params,
         loop_nullability,
+        recovering_rules,
+        cached_recovery,
+        incremental_borrow,
+        cache_keys,
+        lexical_calls,
     }
 }
 
 /// Check for infinite loops in the form of left recursion.
 ///
 /// If a PEG expression recurses without first consuming input, it will
-/// recurse until the stack overflows.
+/// recurse until the stack overflows, unless a rule in the loop is marked
+/// `#[cache_left_rec]`. Also collects the calls each rule makes without first
+/// consuming input, from which the cycles are found.
 struct LeftRecursionVisitor<'a> {
     stack: Vec<String>,
     rules: &'a HashMap<String, &'a Rule>,
+    left_calls: LeftCalls,
     errors: Vec<LeftRecursionError>,
 }
 
+/// Pairs of a rule and a rule it calls without first consuming input
+type LeftCalls = HashSet<(String, String)>;
+
 pub struct LeftRecursionError {
     pub span: Span,
     pub path: Vec<String>,
@@ -52,9 +77,10 @@ impl LeftRecursionError {
 }
 
 impl<'a> LeftRecursionVisitor<'a> {
-    fn check(grammar: &'a Grammar, rules: &HashMap<String, &'a Rule>) -> (HashMap<String, bool>, Vec<LeftRecursionError>) {
+    fn check(grammar: &'a Grammar, rules: &HashMap<String, &'a Rule>) -> (HashMap<String, bool>, LeftCalls, Vec<LeftRecursionError>) {
         let mut visitor = LeftRecursionVisitor {
             rules,
+            left_calls: HashSet::new(),
             errors: Vec::new(),
             stack: Vec::new(),
         };
@@ -67,7 +93,7 @@ impl<'a> LeftRecursionVisitor<'a> {
             rule_nullability.entry(rule.name.to_string()).or_insert(nullable);
         }
 
-        (rule_nullability, visitor.errors)
+        (rule_nullability, visitor.left_calls, visitor.errors)
     }
 
     fn walk_rule(&mut self, rule: &'a Rule) -> bool {
@@ -90,6 +116,10 @@ impl<'a> LeftRecursionVisitor<'a> {
                 let name = rule_ident.to_string();
 
                 if let Some(rule) = self.rules.get(&name) {
+                    if let Some(caller) = self.stack.last() {
+                        self.left_calls.insert((caller.clone(), name.clone()));
+                    }
+
                     if let Some(loop_start) = self
                         .stack
                         .iter()
@@ -97,14 +127,14 @@ impl<'a> LeftRecursionVisitor<'a> {
                     {
                         let mut recursive_loop = self.stack[loop_start..].to_vec();
                         recursive_loop.push(name.clone());
-                        match rule.cache {
-                            None | Some(Cache::Simple) =>
-                                self.errors.push(LeftRecursionError {
-                                    path: recursive_loop,
-                                    span: rule_ident.span(),
-                                }),
-                            _ => ()
-
+                        let handled = recursive_loop.iter().any(|name| {
+                            matches!(self.rules.get(name), Some(Rule { cache: Some(Cache { kind: CacheKind::Recursive, .. }), .. }))
+                        });
+                        if !handled {
+                            self.errors.push(LeftRecursionError {
+                                path: recursive_loop,
+                                span: rule_ident.span(),
+                            });
                         }
                         return false;
                     }
@@ -164,13 +194,150 @@ impl<'a> LeftRecursionVisitor<'a> {
                nullable
             }
 
-            LiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_) => false,
+            RecoverExpr(ref expr, ref sync) => {
+                let expr_nullable = self.walk_expr(expr);
+                let sync_nullable = self.walk_expr(sync);
+                expr_nullable || sync_nullable
+            }
 
-            PositionExpr => true,
+            LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_) => false,
+
+            PositionExpr | CutExpr | PosPredicateExpr(_) | NegPredicateExpr(_) | DynamicLiteralExpr(_) => true,
+
+            // `indent!()` only matches indentation wider than the current level, so it consumes a space or tab
+            IndentExpr(kind) => !matches!(kind, IndentKind::Indent),
         }
     }
 }
 
+/// A set of rules that are left recursive through each other, with at least one
+/// marked `#[cache_left_rec]`.
+///
+/// The first `#[cache_left_rec]` rule of the group called at a position becomes
+/// the head that grows the result there, and the other rules are re-evaluated
+/// on each iteration instead of using their caches. Groups of a single rule
+/// calling itself don't need this, and are not included.
+pub struct LeftRecGroup {
+    pub id: usize,
+    pub members: Vec<String>,
+}
+
+pub struct LeftRecParamsError {
+    pub span: Span,
+}
+
+impl LeftRecParamsError {
+    pub fn msg(&self) -> String {
+        "rules that are left recursive through other rules cannot take parameters".to_owned()
+    }
+}
+
+impl LeftRecGroup {
+    /// Find the strongly connected components of the left call graph (Tarjan's
+    /// algorithm), keeping the cycles containing a `#[cache_left_rec]` rule.
+    fn find(
+        grammar: &Grammar,
+        rules: &HashMap<String, &Rule>,
+        left_calls: &LeftCalls,
+    ) -> (Vec<LeftRecGroup>, Vec<LeftRecParamsError>) {
+        struct Tarjan<'a> {
+            edges: HashMap<&'a str, Vec<&'a str>>,
+            index: HashMap<&'a str, usize>,
+            lowlink: HashMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            components: Vec<Vec<&'a str>>,
+        }
+
+        impl<'a> Tarjan<'a> {
+            fn visit(&mut self, v: &'a str) {
+                let index = self.index.len();
+                self.index.insert(v, index);
+                self.lowlink.insert(v, index);
+                self.stack.push(v);
+
+                for w in self.edges.get(v).cloned().unwrap_or_default() {
+                    if !self.index.contains_key(w) {
+                        self.visit(w);
+                        let low = self.lowlink[v].min(self.lowlink[w]);
+                        self.lowlink.insert(v, low);
+                    } else if self.stack.contains(&w) {
+                        let low = self.lowlink[v].min(self.index[w]);
+                        self.lowlink.insert(v, low);
+                    }
+                }
+
+                if self.lowlink[v] == self.index[v] {
+                    let mut component = Vec::new();
+                    loop {
+                        let w = self.stack.pop().unwrap();
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    self.components.push(component);
+                }
+            }
+        }
+
+        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (caller, callee) in left_calls {
+            edges.entry(caller.as_str()).or_default().push(callee.as_str());
+        }
+        for callees in edges.values_mut() {
+            callees.sort();
+        }
+
+        let mut tarjan = Tarjan {
+            edges,
+            index: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+
+        // Visit in grammar order so that the group ids are deterministic
+        let names: Vec<String> = grammar.iter_rules().map(|rule| rule.name.to_string()).collect();
+        for name in &names {
+            if !tarjan.index.contains_key(name.as_str()) {
+                tarjan.visit(name);
+            }
+        }
+
+        let mut groups = Vec::new();
+        let mut errors = Vec::new();
+        for component in tarjan.components {
+            if component.len() < 2 {
+                continue;
+            }
+
+            let members: Vec<&Rule> = grammar
+                .iter_rules()
+                .filter(|rule| component.contains(&rule.name.to_string().as_str()))
+                .filter(|rule| std::ptr::eq(rules[&rule.name.to_string()], *rule))
+                .collect();
+
+            if !members.iter().any(|rule| matches!(rule.cache, Some(Cache { kind: CacheKind::Recursive, .. }))) {
+                // Reported as a LeftRecursionError
+                continue;
+            }
+
+            for rule in &members {
+                if !rule.params.is_empty() {
+                    errors.push(LeftRecParamsError { span: rule.name.span() });
+                }
+            }
+
+            groups.push(LeftRecGroup {
+                id: groups.len(),
+                members: members.iter().map(|rule| rule.name.to_string()).collect(),
+            });
+        }
+
+        (groups, errors)
+    }
+}
+
 /// Check for loops whose body can succeed without consuming any input, which
 /// will loop infinitely.
 struct LoopNullabilityVisitor<'a> {
@@ -272,8 +439,601 @@ impl<'a> LoopNullabilityVisitor<'a> {
                 nullable 
             }
 
-            LiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_) => false,
-            PositionExpr => true,
+            RecoverExpr(ref expr, ref sync) => {
+                let expr_nullable = self.walk_expr(expr);
+                let sync_nullable = self.walk_expr(sync);
+                expr_nullable || sync_nullable
+            }
+
+            LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_) => false,
+            PositionExpr | CutExpr | PosPredicateExpr(_) | NegPredicateExpr(_) | DynamicLiteralExpr(_) => true,
+            IndentExpr(kind) => !matches!(kind, IndentKind::Indent),
+        }
+    }
+}
+
+/// Find the rules that can record errors with `recover!()`, directly or
+/// through the rules they call.
+///
+/// The recorded errors are not part of the memoized result, so a cache hit
+/// would silently drop them. Caching these rules is reported as an error.
+struct RecoveryVisitor<'a> {
+    recovering_rules: &'a HashSet<String>,
+}
+
+pub struct CachedRecoveryError {
+    pub span: Span,
+}
+
+impl CachedRecoveryError {
+    pub fn msg(&self) -> String {
+        "rules using recover!() (directly or through the rules they call) cannot use #[cache] or #[cache_left_rec]".to_owned()
+    }
+}
+
+impl<'a> RecoveryVisitor<'a> {
+    fn check(grammar: &Grammar) -> (HashSet<String>, Vec<CachedRecoveryError>) {
+        let mut recovering_rules = HashSet::new();
+
+        loop {
+            let mut found = Vec::new();
+
+            {
+                let visitor = RecoveryVisitor { recovering_rules: &recovering_rules };
+                for rule in grammar.iter_rules() {
+                    let name = rule.name.to_string();
+                    if !visitor.recovering_rules.contains(&name) && visitor.walk_expr(&rule.expr) {
+                        found.push(name);
+                    }
+                }
+            }
+
+            if found.is_empty() {
+                break;
+            }
+
+            recovering_rules.extend(found);
+        }
+
+        let errors = grammar
+            .iter_rules()
+            .filter(|rule| rule.cache.is_some() && recovering_rules.contains(&rule.name.to_string()))
+            .map(|rule| CachedRecoveryError { span: rule.name.span() })
+            .collect();
+
+        (recovering_rules, errors)
+    }
+
+    /// Returns true if the expression contains `recover!()` or calls a rule
+    /// already known to recover.
+    fn walk_expr(&self, this_expr: &SpannedExpr) -> bool {
+        use self::Expr::*;
+        match this_expr.expr {
+            RuleExpr(ref rule_ident, ref args) => {
+                self.recovering_rules.contains(&rule_ident.to_string())
+                    || args.iter().any(|arg| match arg {
+                        RuleArg::Peg(e) => self.walk_expr(e),
+                        RuleArg::Rust(_) => false,
+                    })
+            }
+
+            ActionExpr(ref elems, ..) => elems.iter().any(|elem| self.walk_expr(&elem.expr)),
+
+            ChoiceExpr(ref choices) => choices.iter().any(|expr| self.walk_expr(expr)),
+
+            OptionalExpr(ref expr) | PosAssertExpr(ref expr) | NegAssertExpr(ref expr)
+            | MatchStrExpr(ref expr) | QuietExpr(ref expr) => self.walk_expr(expr),
+
+            Repeat { ref inner, ref sep, .. } => {
+                self.walk_expr(inner) || match sep {
+                    Some(sep) => self.walk_expr(sep),
+                    None => false,
+                }
+            }
+
+            PrecedenceExpr { ref levels } => levels.iter().any(|level| {
+                level.operators.iter().any(|operator| {
+                    operator.elements.iter().any(|element| self.walk_expr(&element.expr))
+                })
+            }),
+
+            RecoverExpr(..) => true,
+
+            LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_)
+            | PositionExpr | CutExpr | PosPredicateExpr(_) | NegPredicateExpr(_) | DynamicLiteralExpr(_) | IndentExpr(_) => false,
+        }
+    }
+}
+
+/// In an `#![incremental]` grammar, the caches outlive the input they were
+/// built from, so the cached values can't borrow from it.
+pub struct IncrementalBorrowError {
+    pub span: Span,
+}
+
+impl IncrementalBorrowError {
+    pub fn msg(&self) -> String {
+        "rules in an #![incremental] grammar cannot use #[cache] or #[cache_left_rec] if their return type borrows the input ('input)".to_owned()
+    }
+}
+
+fn check_incremental_borrow(grammar: &Grammar) -> Vec<IncrementalBorrowError> {
+    if !grammar.incremental {
+        return Vec::new();
+    }
+
+    grammar
+        .iter_rules()
+        .filter(|rule| rule.cache.is_some())
+        .filter(|rule| match &rule.ret_type {
+            Some(ty) => mentions_input_lifetime(ty.clone()),
+            None => false,
+        })
+        .map(|rule| IncrementalBorrowError { span: rule.name.span() })
+        .collect()
+}
+
+/// Whether a type contains the lifetime `'input`.
+pub fn mentions_input_lifetime(ts: TokenStream) -> bool {
+    mentions_name(ts, "input", true)
+}
+
+/// Whether a token stream contains the identifier `name`, or with `lifetime`, the
+/// lifetime `'name`.
+fn mentions_name(ts: TokenStream, name: &str, lifetime: bool) -> bool {
+    let mut after_quote = false;
+    for tt in ts {
+        match tt {
+            TokenTree::Ident(ref i) if after_quote == lifetime && i == name => return true,
+            TokenTree::Group(ref g) if mentions_name(g.stream(), name, lifetime) => return true,
+            _ => {}
+        }
+        after_quote = match tt {
+            TokenTree::Punct(ref p) => p.as_char() == '\'',
+            _ => false,
+        };
+    }
+    false
+}
+
+/// The cache of a rule with parameters is keyed by the argument values, and
+/// by the call site for rule closures. Check that the arguments can be keyed.
+///
+/// Rust-typed parameters are stored in the cache and compared, so their types
+/// can't be unsized, mutable borrows, or generic over the rule's own type
+/// parameters (`Eq + Clone` is checked by rustc). A rule closure is identified
+/// only by where it was written, so it can't depend on anything that varies
+/// between calls from that site: the enclosing rule's parameters and labels.
+pub struct CacheKeyError {
+    pub span: Span,
+    pub rule: String,
+    pub reason: String,
+}
+
+impl CacheKeyError {
+    pub fn msg(&self) -> String {
+        format!("cannot cache rule `{}` by its arguments: {}", self.rule, self.reason)
+    }
+}
+
+fn check_cache_keys(grammar: &Grammar, rules: &HashMap<String, &Rule>) -> Vec<CacheKeyError> {
+    let mut errors = Vec::new();
+
+    for rule in grammar.iter_rules() {
+        if rule.cache.is_some() {
+            check_cache_key_types(grammar, rule, &mut errors);
+        }
+
+        let mut visitor = CallsiteVisitor {
+            rules,
+            enclosing: rule,
+            labels: count_labels(&rule.expr),
+            errors: &mut errors,
+        };
+        visitor.walk_expr(&rule.expr);
+    }
+
+    errors
+}
+
+fn check_cache_key_types(grammar: &Grammar, rule: &Rule, errors: &mut Vec<CacheKeyError>) {
+    let mut error = |span: Span, reason: String| {
+        errors.push(CacheKeyError { span, rule: rule.name.to_string(), reason })
+    };
+
+    for ty_param in rule.ty_params.iter().flatten() {
+        let (name, lifetime) = match generic_param_name(ty_param.clone()) {
+            Some(param) => param,
+            None => continue,
+        };
+        let shown = if lifetime { format!("'{}", name) } else { name.clone() };
+
+        if let Some(ret_ty) = &rule.ret_type {
+            if mentions_name(ret_ty.clone(), &name, lifetime) {
+                error(rule.name.span(), format!("its return type uses the rule's generic parameter `{}`", shown));
+            }
+        }
+
+        for param in &rule.params {
+            if let RuleParamTy::Rust(ty) = &param.ty {
+                if mentions_name(ty.clone(), &name, lifetime) {
+                    error(param.name.span(), format!("the type of parameter `{}` uses the rule's generic parameter `{}`", param.name, shown));
+                }
+            }
+        }
+    }
+
+    for param in &rule.params {
+        if let RuleParamTy::Rust(ty) = &param.ty {
+            if let Some(problem) = unkeyable_type(ty.clone()) {
+                error(param.name.span(), format!("parameter `{}` {}", param.name, problem));
+            } else if grammar.incremental && mentions_input_lifetime(ty.clone()) {
+                error(param.name.span(), format!("parameter `{}` borrows the input ('input), which the caches of an #![incremental] grammar outlive", param.name));
+            }
+        }
+    }
+}
+
+/// The name of a generic parameter like `T: Clone` or `'a`, and whether it's a lifetime.
+fn generic_param_name(ts: TokenStream) -> Option<(String, bool)> {
+    let mut tokens = ts.into_iter();
+    match tokens.next()? {
+        TokenTree::Ident(i) => Some((i.to_string(), false)),
+        TokenTree::Punct(ref p) if p.as_char() == '\'' => match tokens.next()? {
+            TokenTree::Ident(i) => Some((i.to_string(), true)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Describes why values of a parameter type can't be stored and compared as a
+/// cache key, for the types where that is apparent from the syntax.
+fn unkeyable_type(ts: TokenStream) -> Option<&'static str> {
+    let tokens: Vec<TokenTree> = ts.into_iter().collect();
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Ident(id) if id == "impl" => return Some("is an `impl Trait` type, which can't be stored in the cache"),
+            TokenTree::Ident(id) if id == "dyn" => return Some("is a trait object, which can't be stored in the cache"),
+            TokenTree::Ident(id) if id == "fn" => return Some("is a function pointer, which can't be reliably compared"),
+            TokenTree::Ident(id) if id == "f32" || id == "f64" => return Some("is a floating point number, which doesn't implement `Eq`"),
+            TokenTree::Punct(p) if p.as_char() == '&' => match tokens.get(i + 1) {
+                Some(TokenTree::Ident(id)) if id == "mut" => return Some("is a mutable reference, which can't be stored in the cache"),
+                Some(TokenTree::Punct(q)) if q.as_char() == '\'' => {
+                    if matches!(tokens.get(i + 2), Some(TokenTree::Ident(id)) if id == "_") {
+                        return Some("is a reference without a named lifetime such as 'input, so it can't be stored in the cache");
+                    }
+                }
+                _ => return Some("is a reference without a named lifetime such as 'input, so it can't be stored in the cache"),
+            },
+            TokenTree::Group(g) => {
+                if let Some(problem) = unkeyable_type(g.stream()) {
+                    return Some(problem);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// With `#![skip]`, a `#[lexical]` rule and the skip rule match their elements next
+/// to each other, so the rules they call must not skip between theirs, or a token
+/// could have whitespace in the middle. Each rule reached that way which would call
+/// the skip rule is reported, once.
+pub struct LexicalCallError {
+    pub span: Span,
+    pub rule: String,
+    pub lexical: String,
+}
+
+impl LexicalCallError {
+    pub fn msg(&self) -> String {
+        format!(
+            "rule `{}` is used by lexical rule `{}`, but skips between its elements; mark it #[lexical]",
+            self.rule, self.lexical
+        )
+    }
+}
+
+fn check_lexical_calls(grammar: &Grammar, rules: &HashMap<String, &Rule>) -> Vec<LexicalCallError> {
+    let skip = match &grammar.skip {
+        Some(skip) => skip,
+        None => return Vec::new(),
+    };
+    let is_lexical = |rule: &Rule| rule.lexical || rule.name == *skip;
+
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack: Vec<(&Rule, String)> = grammar
+        .iter_rules()
+        .filter(|rule| is_lexical(rule))
+        .map(|rule| (rule, rule.name.to_string()))
+        .collect();
+    // Start from the first lexical rule in the grammar
+    stack.reverse();
+
+    while let Some((rule, lexical)) = stack.pop() {
+        let mut calls = HashSet::new();
+        collect_closure_uses(&rule.expr, &mut calls, &mut HashSet::new());
+        let mut calls: Vec<String> = calls.into_iter().collect();
+        calls.sort();
+
+        for name in calls {
+            let callee = match rules.get(&name) {
+                Some(callee) if !is_lexical(callee) && seen.insert(name.clone()) => callee,
+                _ => continue,
+            };
+            if skips(&callee.expr) {
+                errors.push(LexicalCallError { span: callee.name.span(), rule: name, lexical: lexical.clone() });
+            } else {
+                stack.push((callee, lexical.clone()));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Whether a rule with this expression calls the `#![skip]` rule, unless it's `#[lexical]`.
+fn skips(this_expr: &SpannedExpr) -> bool {
+    use self::Expr::*;
+    match this_expr.expr {
+        RuleExpr(_, ref args) => args.iter().any(|arg| matches!(arg, RuleArg::Peg(e) if skips(e))),
+        ActionExpr(ref elems, ..) => {
+            elems.iter().skip(1).any(|elem| !matches!(elem.expr.expr, PositionExpr))
+                || elems.iter().any(|elem| skips(&elem.expr))
+        }
+        ChoiceExpr(ref choices) => choices.iter().any(skips),
+        OptionalExpr(ref expr) | PosAssertExpr(ref expr) | NegAssertExpr(ref expr)
+        | MatchStrExpr(ref expr) | QuietExpr(ref expr) => skips(expr),
+        RecoverExpr(ref expr, ref sync) => skips(expr) || skips(sync),
+        Repeat { .. } | PrecedenceExpr { .. } => true,
+        LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_)
+        | PositionExpr | CutExpr | PosPredicateExpr(_) | NegPredicateExpr(_) | DynamicLiteralExpr(_) | IndentExpr(_) => false,
+    }
+}
+
+/// Finds rule closures passed to rules cached by call site that depend on the
+/// enclosing rule.
+struct CallsiteVisitor<'a, 'b> {
+    rules: &'a HashMap<String, &'a Rule>,
+    enclosing: &'a Rule,
+    labels: HashMap<String, usize>,
+    errors: &'b mut Vec<CacheKeyError>,
+}
+
+impl<'a, 'b> CallsiteVisitor<'a, 'b> {
+    fn walk_expr(&mut self, this_expr: &SpannedExpr) {
+        use self::Expr::*;
+        match this_expr.expr {
+            RuleExpr(ref rule_ident, ref args) => {
+                let by_callsite = match self.rules.get(&rule_ident.to_string()) {
+                    Some(rule) => rule.cache_by_callsite(),
+                    None => false,
+                };
+
+                for arg in args {
+                    if let RuleArg::Peg(e) = arg {
+                        if by_callsite {
+                            self.check_closure(rule_ident, e);
+                        }
+                        self.walk_expr(e);
+                    }
+                }
+            }
+
+            ActionExpr(ref elems, ..) => {
+                for elem in elems {
+                    self.walk_expr(&elem.expr);
+                }
+            }
+
+            ChoiceExpr(ref choices) => {
+                for expr in choices {
+                    self.walk_expr(expr);
+                }
+            }
+
+            OptionalExpr(ref expr) | PosAssertExpr(ref expr) | NegAssertExpr(ref expr)
+            | MatchStrExpr(ref expr) | QuietExpr(ref expr) => self.walk_expr(expr),
+
+            Repeat { ref inner, ref sep, .. } => {
+                self.walk_expr(inner);
+                if let Some(sep) = sep {
+                    self.walk_expr(sep);
+                }
+            }
+
+            PrecedenceExpr { ref levels } => {
+                for level in levels {
+                    for operator in &level.operators {
+                        for element in &operator.elements {
+                            self.walk_expr(&element.expr);
+                        }
+                    }
+                }
+            }
+
+            RecoverExpr(ref expr, ref sync) => {
+                self.walk_expr(expr);
+                self.walk_expr(sync);
+            }
+
+            LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_)
+            | PositionExpr | CutExpr | PosPredicateExpr(_) | NegPredicateExpr(_) | DynamicLiteralExpr(_) | IndentExpr(_) => {}
+        }
+    }
+
+    fn check_closure(&mut self, callee: &Ident, closure: &SpannedExpr) {
+        let mut calls = HashSet::new();
+        let mut rust_idents = HashSet::new();
+        collect_closure_uses(closure, &mut calls, &mut rust_idents);
+
+        let inner_labels = count_labels(closure);
+        let outer_label = |name: &String| {
+            self.labels.get(name).copied().unwrap_or(0) > inner_labels.get(name).copied().unwrap_or(0)
+        };
+
+        let mut used = None;
+        for param in &self.enclosing.params {
+            let name = param.name.to_string();
+            let found = match param.ty {
+                RuleParamTy::Rust(_) => rust_idents.contains(&name),
+                RuleParamTy::Rule(_) => calls.contains(&name),
+            };
+            if found {
+                used = Some(format!("parameter `{}`", name));
+                break;
+            }
+        }
+        if used.is_none() {
+            let mut names: Vec<&String> = rust_idents.iter().filter(|name| outer_label(name)).collect();
+            names.sort();
+            used = names.first().map(|name| format!("label `{}`", name));
+        }
+
+        if let Some(used) = used {
+            self.errors.push(CacheKeyError {
+                span: closure.span,
+                rule: callee.to_string(),
+                reason: format!(
+                    "this rule argument uses {} of `{}`, but arguments that are rules are cached by call site and can't depend on the enclosing rule",
+                    used, self.enclosing.name
+                ),
+            });
+        }
+    }
+}
+
+/// Count the labels bound in an expression, by name.
+fn count_labels(this_expr: &SpannedExpr) -> HashMap<String, usize> {
+    fn walk(this_expr: &SpannedExpr, counts: &mut HashMap<String, usize>) {
+        use self::Expr::*;
+        let tagged = |elems: &[TaggedExpr], counts: &mut HashMap<String, usize>| {
+            for elem in elems {
+                if let Some(name) = &elem.name {
+                    *counts.entry(name.to_string()).or_insert(0) += 1;
+                }
+                walk(&elem.expr, counts);
+            }
+        };
+
+        match this_expr.expr {
+            RuleExpr(_, ref args) => {
+                for arg in args {
+                    if let RuleArg::Peg(e) = arg {
+                        walk(e, counts);
+                    }
+                }
+            }
+            ActionExpr(ref elems, ..) => tagged(elems, counts),
+            ChoiceExpr(ref choices) => {
+                for expr in choices {
+                    walk(expr, counts);
+                }
+            }
+            OptionalExpr(ref expr) | PosAssertExpr(ref expr) | NegAssertExpr(ref expr)
+            | MatchStrExpr(ref expr) | QuietExpr(ref expr) => walk(expr, counts),
+            Repeat { ref inner, ref sep, .. } => {
+                walk(inner, counts);
+                if let Some(sep) = sep {
+                    walk(sep, counts);
+                }
+            }
+            PrecedenceExpr { ref levels } => {
+                for level in levels {
+                    for operator in &level.operators {
+                        tagged(&operator.elements, counts);
+                    }
+                }
+            }
+            RecoverExpr(ref expr, ref sync) => {
+                walk(expr, counts);
+                walk(sync, counts);
+            }
+            LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_)
+            | PositionExpr | CutExpr | PosPredicateExpr(_) | NegPredicateExpr(_) | DynamicLiteralExpr(_) | IndentExpr(_) => {}
+        }
+    }
+
+    let mut counts = HashMap::new();
+    walk(this_expr, &mut counts);
+    counts
+}
+
+/// Collect the names of the rules called in an expression, and the identifiers
+/// used in its embedded Rust code.
+fn collect_closure_uses(this_expr: &SpannedExpr, calls: &mut HashSet<String>, idents: &mut HashSet<String>) {
+    fn rust_idents(ts: TokenStream, idents: &mut HashSet<String>) {
+        for tt in ts {
+            match tt {
+                TokenTree::Ident(i) => {
+                    idents.insert(i.to_string());
+                }
+                TokenTree::Group(g) => rust_idents(g.stream(), idents),
+                _ => {}
+            }
+        }
+    }
+
+    use self::Expr::*;
+    match this_expr.expr {
+        RuleExpr(ref rule_ident, ref args) => {
+            calls.insert(rule_ident.to_string());
+            for arg in args {
+                match arg {
+                    RuleArg::Peg(e) => collect_closure_uses(e, calls, idents),
+                    RuleArg::Rust(ts) => rust_idents(ts.clone(), idents),
+                }
+            }
+        }
+        ActionExpr(ref elems, ref code) => {
+            for elem in elems {
+                collect_closure_uses(&elem.expr, calls, idents);
+            }
+            if let Some(code) = code {
+                rust_idents(code.stream(), idents);
+            }
+        }
+        ChoiceExpr(ref choices) => {
+            for expr in choices {
+                collect_closure_uses(expr, calls, idents);
+            }
+        }
+        OptionalExpr(ref expr) | PosAssertExpr(ref expr) | NegAssertExpr(ref expr)
+        | MatchStrExpr(ref expr) | QuietExpr(ref expr) => collect_closure_uses(expr, calls, idents),
+        Repeat { ref inner, ref bound, ref sep } => {
+            collect_closure_uses(inner, calls, idents);
+            if let Some(sep) = sep {
+                collect_closure_uses(sep, calls, idents);
+            }
+            match bound {
+                BoundedRepeat::Exact(ts) => rust_idents(ts.clone(), idents),
+                BoundedRepeat::Both(min, max) => {
+                    for ts in min.iter().chain(max.iter()) {
+                        rust_idents(ts.clone(), idents);
+                    }
+                }
+                BoundedRepeat::None | BoundedRepeat::Plus => {}
+            }
+        }
+        PrecedenceExpr { ref levels } => {
+            for level in levels {
+                for operator in &level.operators {
+                    for element in &operator.elements {
+                        collect_closure_uses(&element.expr, calls, idents);
+                    }
+                    rust_idents(operator.action.stream(), idents);
+                }
+            }
+        }
+        RecoverExpr(ref expr, ref sync) => {
+            collect_closure_uses(expr, calls, idents);
+            collect_closure_uses(sync, calls, idents);
         }
+        PatternExpr(ref group) | PosPredicateExpr(ref group) | NegPredicateExpr(ref group) => rust_idents(group.stream(), idents),
+        MethodExpr(_, ref args) | DynamicLiteralExpr(ref args) => rust_idents(args.clone(), idents),
+        LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | FailExpr(_) | MarkerExpr(_) | PositionExpr | CutExpr | IndentExpr(_) => {}
     }
 }
@@ -9,6 +9,11 @@ pub struct Grammar {
     pub args: Vec<(Ident, TokenStream)>,
     pub items: Vec<Item>,
     pub input_type: TokenStream,
+    pub incremental: bool,
+    pub cache_storage: Option<CacheStorage>,
+    pub generator: bool,
+    pub skip: Option<Ident>,
+    pub max_depth: Option<Literal>,
 }
 
 impl Grammar {
@@ -27,11 +32,25 @@ pub enum Item {
 }
 
 #[derive(Debug)]
-pub enum Cache {
+pub struct Cache {
+    pub kind: CacheKind,
+    pub shared: bool,
+    pub storage: Option<CacheStorage>,
+}
+
+#[derive(Debug)]
+pub enum CacheKind {
     Simple,
     Recursive
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum CacheStorage {
+    Dense,
+    Chunked,
+    Hash,
+}
+
 #[derive(Debug)]
 pub struct Rule {
     pub span: Span,
@@ -44,6 +63,22 @@ pub struct Rule {
     pub visibility: Option<TokenStream>,
     pub cache: Option<Cache>,
     pub no_eof: bool,
+    pub recover: bool,
+    pub context: Option<Literal>,
+    pub lexical: bool,
+}
+
+impl Rule {
+    /// Whether the rule's cache is keyed by its arguments as well as the position.
+    pub fn cache_keyed(&self) -> bool {
+        self.cache.is_some() && !self.params.is_empty()
+    }
+
+    /// Whether the cache key includes the call site, which stands in for the
+    /// rule closures passed at that site.
+    pub fn cache_by_callsite(&self) -> bool {
+        self.cache.is_some() && self.params.iter().any(|param| matches!(param.ty, RuleParamTy::Rule(_)))
+    }
 }
 
 #[derive(Debug)]
@@ -72,6 +107,9 @@ pub struct SpannedExpr {
 #[derive(Debug, Clone)]
 pub enum Expr {
     LiteralExpr(Literal),
+    CaseInsensitiveLiteralExpr(Literal),
+    ByteLiteralExpr(Literal),
+    DynamicLiteralExpr(TokenStream),
     PatternExpr(Group),
     RuleExpr(Ident, Vec<RuleArg>),
     MethodExpr(Ident, TokenStream),
@@ -80,6 +118,8 @@ pub enum Expr {
     Repeat { inner: Box<SpannedExpr>, bound: BoundedRepeat, sep: Option<Box<SpannedExpr>> },
     PosAssertExpr(Box<SpannedExpr>),
     NegAssertExpr(Box<SpannedExpr>),
+    PosPredicateExpr(Group),
+    NegPredicateExpr(Group),
     ActionExpr(Vec<TaggedExpr>, Option<Group>),
     MatchStrExpr(Box<SpannedExpr>),
     PositionExpr,
@@ -89,6 +129,9 @@ pub enum Expr {
         levels: Vec<PrecedenceLevel>,
     },
     MarkerExpr(bool),
+    CutExpr,
+    IndentExpr(IndentKind),
+    RecoverExpr(Box<SpannedExpr>, Box<SpannedExpr>),
 }
 
 impl Expr {
@@ -97,6 +140,13 @@ impl Expr {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum IndentKind {
+    Indent,
+    Dedent,
+    Same,
+}
+
 #[derive(Debug, Clone)]
 pub enum RuleArg {
     Rust(TokenStream),
@@ -0,0 +1,575 @@
+//! The `generator` module emitted for a grammar with `#![generator]`, with a function
+//! for each rule producing random input that it should match.
+//!
+//! Each expression needs some depth of rule calls to produce any input at all. That is
+//! worked out here, so that once the depth asked for is used up, the generated code can
+//! take the alternatives and repetition counts that finish soonest. Expressions that
+//! can't be produced, like predicates, need `UNREACHABLE` depth, and so are only taken
+//! when a rule has nothing else.
+
+use proc_macro2::{Group, Ident, Literal, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote_spanned};
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::translate::{group_check_prefix, report_error};
+
+const UNREACHABLE: usize = usize::MAX;
+
+struct Context<'a> {
+    rules: &'a HashMap<String, &'a Rule>,
+
+    /// The depth each rule needs, besides the call to it
+    needed: HashMap<String, usize>,
+
+    /// The `#![skip]` rule, whose input goes between the elements of sequences
+    skip: Option<&'a Ident>,
+}
+
+pub(crate) fn compile_generator(grammar: &Grammar, rules: &HashMap<String, &Rule>) -> TokenStream {
+    let span = Span::mixed_site();
+
+    if grammar.input_type.to_string() != "str" {
+        return report_error(
+            grammar.name.span(),
+            "#![generator] is only supported for grammars `for str`".to_owned(),
+        );
+    }
+
+    let mut context = Context { rules, needed: HashMap::new(), skip: grammar.skip.as_ref() };
+    for rule in grammar.iter_rules() {
+        context.needed.insert(rule.name.to_string(), UNREACHABLE);
+    }
+
+    // The depths only ever decrease, so this settles once each rule has found its
+    // shallowest way to finish.
+    loop {
+        let mut changed = false;
+        for rule in grammar.iter_rules() {
+            if !generated(rule) {
+                continue;
+            }
+            let needed = context.expr_needed(&rule.expr);
+            let entry = context.needed.get_mut(&rule.name.to_string()).unwrap();
+            if needed < *entry {
+                *entry = needed;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let unsatisfiable: Vec<String> = grammar
+        .iter_rules()
+        .map(|rule| rule.name.to_string())
+        .filter(|name| context.needed[name] == UNREACHABLE)
+        .collect();
+
+    let mut functions = Vec::new();
+    for rule in grammar.iter_rules() {
+        if !generated(rule) {
+            continue;
+        }
+
+        let name = &rule.name;
+        let gen_name = format_ident!("__gen_{}", rule.name, span = span);
+        let body = context.compile_expr(&rule.expr, &rule.name.to_string());
+
+        // `_` can't name a function, but the other rules can still call its generator
+        if rule.name != "_" {
+            functions.push(quote_spanned! { span =>
+                /// Random input for the rule, with `depth` levels of rules nested beyond
+                /// the fewest it takes.
+                pub fn #name(rng: &mut ::peg::generate::Rng, depth: usize) -> ::peg::__core::result::Result<::peg::__alloc::string::String, ::peg::generate::Unsatisfiable> {
+                    let mut out = ::peg::__alloc::string::String::new();
+                    #gen_name(rng, depth, &mut out)?;
+                    ::peg::__core::result::Result::Ok(out)
+                }
+            });
+        }
+
+        functions.push(quote_spanned! { span =>
+            fn #gen_name(__rng: &mut ::peg::generate::Rng, __depth: usize, __out: &mut ::peg::__alloc::string::String) -> ::peg::__core::result::Result<(), ::peg::generate::Unsatisfiable> {
+                #body
+                ::peg::__core::result::Result::Ok(())
+            }
+        });
+    }
+
+    quote_spanned! { span =>
+        /// Random input for the rules of the grammar, generated with `#![generator]`.
+        #[allow(dead_code)]
+        pub mod generator {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// The rules that can't be generated, because they take arguments or
+            /// every way to finish them goes through something the generator can't
+            /// produce, like a predicate.
+            pub const UNSATISFIABLE: &[&str] = &[#(#unsatisfiable),*];
+
+            #(#functions)*
+        }
+    }
+}
+
+/// Whether the rule gets a generator function. Rules taking arguments don't.
+fn generated(rule: &Rule) -> bool {
+    rule.params.is_empty() && rule.ty_params.is_none()
+}
+
+/// Whether an action is a `{? }` block.
+fn is_conditional(code: &Option<Group>) -> bool {
+    matches!(code, Some(code) if group_check_prefix(code, '?').is_some())
+}
+
+fn depth_tokens(depth: usize) -> TokenStream {
+    if depth == UNREACHABLE {
+        quote_spanned!(Span::mixed_site() => ::peg::generate::UNREACHABLE)
+    } else {
+        quote_spanned!(Span::mixed_site() => #depth)
+    }
+}
+
+/// A literal repetition bound.
+fn repeat_bound(bound: &TokenStream) -> Option<usize> {
+    bound.to_string().parse().ok()
+}
+
+/// The bounds of a repetition, if they are literals.
+fn repeat_bounds(bound: &BoundedRepeat) -> Option<(usize, Option<usize>)> {
+    match bound {
+        BoundedRepeat::None => Some((0, None)),
+        BoundedRepeat::Plus => Some((1, None)),
+        BoundedRepeat::Exact(n) => repeat_bound(n).map(|n| (n, Some(n))),
+        BoundedRepeat::Both(min, max) => {
+            let min = match min {
+                Some(min) => repeat_bound(min)?,
+                None => 0,
+            };
+            let max = match max {
+                Some(max) => Some(repeat_bound(max)?),
+                None => None,
+            };
+            Some((min, max))
+        }
+    }
+}
+
+/// The kind of operator in a `precedence!{}` level, as `translate` tells them apart.
+enum Operator {
+    SpanCapture,
+    Infix,
+    Postfix,
+    Prefix,
+    Atom,
+}
+
+fn operator_kind(op: &PrecedenceOperator) -> Operator {
+    let elements = &op.elements;
+    let len = elements.len();
+    if len == 0 {
+        return Operator::Atom;
+    }
+    match (&elements[0].expr.expr, &elements[len - 1].expr.expr) {
+        (Expr::PositionExpr, Expr::PositionExpr) if len == 3 => Operator::SpanCapture,
+        (Expr::MarkerExpr(..), Expr::MarkerExpr(..)) if len >= 3 => Operator::Infix,
+        (Expr::MarkerExpr(..), _) if len >= 2 => Operator::Postfix,
+        (_, Expr::MarkerExpr(..)) if len >= 2 => Operator::Prefix,
+        _ => Operator::Atom,
+    }
+}
+
+impl<'a> Context<'a> {
+    fn rule_needed(&self, name: &str, args: &[RuleArg]) -> usize {
+        match self.rules.get(name) {
+            Some(rule) if args.is_empty() && generated(rule) => self.needed[name].saturating_add(1),
+            _ => UNREACHABLE,
+        }
+    }
+
+    fn seq_needed<'e>(&self, exprs: impl IntoIterator<Item = &'e SpannedExpr>) -> usize {
+        exprs.into_iter().map(|e| self.expr_needed(e)).max().unwrap_or(0)
+    }
+
+    /// The depth of rules an expression needs to produce any input.
+    fn expr_needed(&self, e: &SpannedExpr) -> usize {
+        match &e.expr {
+            Expr::LiteralExpr(..) | Expr::CaseInsensitiveLiteralExpr(..) | Expr::PatternExpr(..) | Expr::PositionExpr | Expr::CutExpr => 0,
+            Expr::RuleExpr(name, args) => self.rule_needed(&name.to_string(), args),
+            Expr::ChoiceExpr(exprs) => exprs.iter().map(|e| self.expr_needed(e)).min().unwrap_or(UNREACHABLE),
+            Expr::OptionalExpr(..) => 0,
+            Expr::Repeat { inner, bound, sep } => match repeat_bounds(bound) {
+                Some((0, _)) => 0,
+                Some((min, _)) => {
+                    let sep = sep.as_ref().filter(|_| min > 1);
+                    self.seq_needed(Some(&**inner).into_iter().chain(sep.map(|s| &**s)))
+                }
+                None => UNREACHABLE,
+            },
+            Expr::ActionExpr(exprs, code) => {
+                if is_conditional(code) {
+                    UNREACHABLE
+                } else {
+                    self.seq_needed(exprs.iter().map(|e| &e.expr))
+                }
+            }
+            Expr::MatchStrExpr(e) | Expr::QuietExpr(e) | Expr::RecoverExpr(e, _) => self.expr_needed(e),
+            Expr::PrecedenceExpr { levels } => self.precedence_atoms_needed(levels),
+            Expr::ByteLiteralExpr(..)
+            | Expr::DynamicLiteralExpr(..)
+            | Expr::IndentExpr(..)
+            | Expr::MethodExpr(..)
+            | Expr::PosAssertExpr(..)
+            | Expr::NegAssertExpr(..)
+            | Expr::PosPredicateExpr(..)
+            | Expr::NegPredicateExpr(..)
+            | Expr::FailExpr(..)
+            | Expr::MarkerExpr(..) => UNREACHABLE,
+        }
+    }
+
+    /// The shallowest atom of a `precedence!{}`, which is also the shallowest way to
+    /// finish any of its operands.
+    fn precedence_atoms_needed(&self, levels: &[PrecedenceLevel]) -> usize {
+        levels
+            .iter()
+            .flat_map(|level| &level.operators)
+            .filter(|op| matches!(operator_kind(op), Operator::Atom))
+            .map(|op| self.seq_needed(op.elements.iter().map(|e| &e.expr)))
+            .min()
+            .unwrap_or(UNREACHABLE)
+    }
+
+    fn compile_unsatisfiable(&self, rule_name: &str, reason: &str) -> TokenStream {
+        quote_spanned! { Span::mixed_site() =>
+            ::peg::__core::result::Result::Err(::peg::generate::Unsatisfiable { rule: #rule_name, reason: #reason })?;
+        }
+    }
+
+    fn compile_seq<'e>(&self, exprs: impl IntoIterator<Item = &'e SpannedExpr>, rule_name: &str) -> TokenStream {
+        let skip = self.compile_skip(rule_name);
+        exprs
+            .into_iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let code = self.compile_expr(e, rule_name);
+                if i > 0 && !matches!(e.expr, Expr::PositionExpr) {
+                    quote_spanned! { Span::mixed_site() => #skip #code }
+                } else {
+                    code
+                }
+            })
+            .collect()
+    }
+
+    /// Statements appending input for the `#![skip]` rule, where the parser calls it in
+    /// the rule: anywhere but a `#[lexical]` rule and the skip rule itself. Its depth
+    /// isn't counted in what the sequences need, as a skip rule finishes right away.
+    fn compile_skip(&self, rule_name: &str) -> TokenStream {
+        match self.skip {
+            Some(skip) if skip != rule_name && matches!(self.rules.get(rule_name), Some(rule) if !rule.lexical) => {
+                let call = SpannedExpr { span: skip.span(), expr: Expr::RuleExpr(skip.clone(), Vec::new()) };
+                self.compile_expr(&call, rule_name)
+            }
+            _ => TokenStream::new(),
+        }
+    }
+
+    /// Statements appending input for `e` to `__out`.
+    fn compile_expr(&self, e: &SpannedExpr, rule_name: &str) -> TokenStream {
+        let span = e.span.resolved_at(Span::mixed_site());
+
+        match &e.expr {
+            Expr::LiteralExpr(s) | Expr::CaseInsensitiveLiteralExpr(s) => {
+                quote_spanned! { span => __out.push_str(#s); }
+            }
+
+            Expr::PatternExpr(group) => self.compile_pattern(group, rule_name),
+
+            Expr::RuleExpr(name, args) => match self.rules.get(&name.to_string()) {
+                Some(rule) if args.is_empty() && generated(rule) => {
+                    let gen_name = format_ident!("__gen_{}", name, span = span);
+                    quote_spanned! { span => #gen_name(__rng, __depth.saturating_sub(1), __out)?; }
+                }
+                Some(_) => self.compile_unsatisfiable(rule_name, "rule with arguments"),
+                // Reported by `translate`
+                None => TokenStream::new(),
+            },
+
+            Expr::ChoiceExpr(exprs) => {
+                let needed = exprs.iter().map(|e| depth_tokens(self.expr_needed(e)));
+                let arms = exprs.iter().enumerate().map(|(i, e)| {
+                    let code = self.compile_expr(e, rule_name);
+                    if i + 1 < exprs.len() {
+                        quote_spanned! { span => #i => { #code } }
+                    } else {
+                        quote_spanned! { span => _ => { #code } }
+                    }
+                });
+                quote_spanned! { span =>
+                    match ::peg::generate::choose(__rng, __depth, &[#(#needed),*]) {
+                        #(#arms)*
+                    }
+                }
+            }
+
+            Expr::OptionalExpr(e) => {
+                let needed = depth_tokens(self.expr_needed(e));
+                let code = self.compile_expr(e, rule_name);
+                quote_spanned! { span =>
+                    if ::peg::generate::optional(__rng, __depth, #needed) { #code }
+                }
+            }
+
+            Expr::Repeat { inner, bound, sep } => {
+                let (min, max) = match repeat_bounds(bound) {
+                    Some(bounds) => bounds,
+                    None => return self.compile_unsatisfiable(rule_name, "repetition count that isn't a literal"),
+                };
+                let max = match max {
+                    Some(max) => quote_spanned! { span => ::peg::__core::option::Option::Some(#max) },
+                    None => quote_spanned! { span => ::peg::__core::option::Option::None },
+                };
+                let needed = depth_tokens(self.seq_needed(Some(&**inner).into_iter().chain(sep.as_deref())));
+                let inner = self.compile_expr(inner, rule_name);
+                let count = quote_spanned! { span => ::peg::generate::repeat_count(__rng, __depth, #needed, #min, #max) };
+                let skip = self.compile_skip(rule_name);
+                match sep {
+                    Some(sep) => {
+                        let sep = self.compile_expr(sep, rule_name);
+                        quote_spanned! { span =>
+                            for __i in 0..#count {
+                                if __i > 0 { #skip #sep #skip }
+                                #inner
+                            }
+                        }
+                    }
+                    None if !skip.is_empty() => quote_spanned! { span =>
+                        for __i in 0..#count {
+                            if __i > 0 { #skip }
+                            #inner
+                        }
+                    },
+                    None => quote_spanned! { span =>
+                        for _ in 0..#count { #inner }
+                    },
+                }
+            }
+
+            Expr::ActionExpr(exprs, code) => {
+                if is_conditional(code) {
+                    self.compile_unsatisfiable(rule_name, "`{? }` action")
+                } else {
+                    self.compile_seq(exprs.iter().map(|e| &e.expr), rule_name)
+                }
+            }
+
+            Expr::MatchStrExpr(e) | Expr::QuietExpr(e) | Expr::RecoverExpr(e, _) => self.compile_expr(e, rule_name),
+
+            Expr::PositionExpr | Expr::CutExpr => TokenStream::new(),
+
+            Expr::PrecedenceExpr { levels } => self.compile_precedence(span, levels, rule_name),
+
+            Expr::ByteLiteralExpr(..) => self.compile_unsatisfiable(rule_name, "byte literal"),
+            Expr::DynamicLiteralExpr(..) => self.compile_unsatisfiable(rule_name, "`literal!()`"),
+            Expr::IndentExpr(..) => self.compile_unsatisfiable(rule_name, "indentation"),
+            Expr::MethodExpr(..) => self.compile_unsatisfiable(rule_name, "`##method()`"),
+            Expr::PosAssertExpr(..) => self.compile_unsatisfiable(rule_name, "`&` predicate"),
+            Expr::NegAssertExpr(..) => self.compile_unsatisfiable(rule_name, "`!` predicate"),
+            Expr::PosPredicateExpr(..) => self.compile_unsatisfiable(rule_name, "`&{ }` predicate"),
+            Expr::NegPredicateExpr(..) => self.compile_unsatisfiable(rule_name, "`!{ }` predicate"),
+            Expr::FailExpr(..) => self.compile_unsatisfiable(rule_name, "`expected!()`"),
+            Expr::MarkerExpr(..) => self.compile_unsatisfiable(rule_name, "`@` outside `precedence!{}`"),
+        }
+    }
+
+    /// Draw a character matching the pattern. The characters and ranges spelled out in
+    /// it are tried first; for a pattern that can accept anything else, printable
+    /// ASCII is tried as well.
+    fn compile_pattern(&self, group: &Group, rule_name: &str) -> TokenStream {
+        let span = group.span().resolved_at(Span::mixed_site());
+
+        let (pattern, negated) = match group_check_prefix(group, '^') {
+            Some(pattern) => (pattern, true),
+            None => (group.stream(), false),
+        };
+        let (ranges, simple) = pattern_ranges(&pattern);
+        let ascii = negated || !simple;
+        let ranges = if negated { Vec::new() } else { ranges };
+        let ranges = ranges.iter().map(|&(lo, hi)| quote_spanned! { span => (#lo, #hi) });
+        let (in_set, not_in_set) = if negated { (false, true) } else { (true, false) };
+
+        quote_spanned! { span =>
+            match ::peg::generate::pattern_char(__rng, &[#(#ranges),*], #ascii, |__ch| match __ch { #pattern => #in_set, _ => #not_in_set }) {
+                ::peg::__core::option::Option::Some(__ch) => __out.push(__ch),
+                ::peg::__core::option::Option::None => {
+                    ::peg::__core::result::Result::Err(::peg::generate::Unsatisfiable { rule: #rule_name, reason: "no character found for a pattern" })?;
+                }
+            }
+        }
+    }
+
+    /// A local function producing an operand at a precedence level, picking from the
+    /// atoms and prefix operators, and the other operators at that level or above.
+    /// Operators take one level of depth, as a rule call does.
+    fn compile_precedence(&self, span: Span, levels: &[PrecedenceLevel], rule_name: &str) -> TokenStream {
+        let atoms_needed = self.precedence_atoms_needed(levels);
+
+        let mut needed = Vec::new();
+        let mut arms = Vec::new();
+        for (level, prec_level) in levels.iter().enumerate() {
+            for op in &prec_level.operators {
+                let kind = operator_kind(op);
+                let elements_needed = self.seq_needed(
+                    op.elements.iter().map(|e| &e.expr).filter(|e| !matches!(e.expr, Expr::MarkerExpr(..))),
+                );
+                needed.push(match kind {
+                    Operator::SpanCapture => depth_tokens(UNREACHABLE),
+                    Operator::Atom => depth_tokens(elements_needed),
+                    Operator::Prefix => depth_tokens(elements_needed.max(atoms_needed).saturating_add(1)),
+                    Operator::Infix | Operator::Postfix => {
+                        let op_needed = depth_tokens(elements_needed.max(atoms_needed).saturating_add(1));
+                        quote_spanned! { span => if __level <= #level { #op_needed } else { ::peg::generate::UNREACHABLE } }
+                    }
+                });
+
+                // The parser skips between the elements of an operator, operands included
+                let skip = if matches!(kind, Operator::SpanCapture) { TokenStream::new() } else { self.compile_skip(rule_name) };
+                let code = op.elements.iter().enumerate().map(|(i, element)| {
+                    let code = match element.expr.expr {
+                        Expr::MarkerExpr(same_level) => {
+                            let operand_level = if same_level || (i == 0 && matches!(kind, Operator::Postfix)) {
+                                level
+                            } else {
+                                level + 1
+                            };
+                            quote_spanned! { span => __precedence(__rng, __depth.saturating_sub(1), __out, #operand_level)?; }
+                        }
+                        _ => self.compile_expr(&element.expr, rule_name),
+                    };
+                    if i > 0 && !matches!(element.expr.expr, Expr::PositionExpr) {
+                        quote_spanned! { span => #skip #code }
+                    } else {
+                        code
+                    }
+                });
+                let arm = needed.len() - 1;
+                arms.push(quote_spanned! { span => #arm => { #(#code)* } });
+            }
+        }
+
+        quote_spanned! { span => {
+            fn __precedence(__rng: &mut ::peg::generate::Rng, __depth: usize, __out: &mut ::peg::__alloc::string::String, __level: usize) -> ::peg::__core::result::Result<(), ::peg::generate::Unsatisfiable> {
+                match ::peg::generate::choose(__rng, __depth, &[#(#needed),*]) {
+                    #(#arms)*
+                    _ => {}
+                }
+                ::peg::__core::result::Result::Ok(())
+            }
+            __precedence(__rng, __depth, __out, 0)?;
+        }}
+    }
+}
+
+/// The characters and ranges of characters written out in a pattern, and whether the
+/// pattern is only made of them.
+fn pattern_ranges(pattern: &TokenStream) -> (Vec<(char, char)>, bool) {
+    let tokens: Vec<TokenTree> = pattern.clone().into_iter().collect();
+    let mut ranges = Vec::new();
+    let mut simple = true;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let lo = match &tokens[i] {
+            TokenTree::Literal(lit) => char_literal(lit),
+            _ => None,
+        };
+        let lo = match lo {
+            Some(lo) => lo,
+            None => {
+                simple = false;
+                i += 1;
+                continue;
+            }
+        };
+        i += 1;
+
+        let range = match (tokens.get(i), tokens.get(i + 1), tokens.get(i + 2)) {
+            (Some(TokenTree::Punct(a)), Some(TokenTree::Punct(b)), Some(TokenTree::Punct(c)))
+                if a.as_char() == '.' && b.as_char() == '.' && c.as_char() == '=' =>
+            {
+                Some((3, true))
+            }
+            (Some(TokenTree::Punct(a)), Some(TokenTree::Punct(b)), _) if a.as_char() == '.' && b.as_char() == '.' => {
+                Some((2, false))
+            }
+            _ => None,
+        };
+
+        match range {
+            Some((len, inclusive)) => {
+                let hi = match tokens.get(i + len) {
+                    Some(TokenTree::Literal(lit)) => char_literal(lit),
+                    _ => None,
+                };
+                // An exclusive range ending at '\0' is empty, which rustc reports
+                let hi = match hi {
+                    Some(hi) if !inclusive => (hi as u32).checked_sub(1).map(std::char::from_u32).ok_or(()),
+                    hi => Ok(hi),
+                };
+                match hi {
+                    Ok(Some(hi)) if hi >= lo => ranges.push((lo, hi)),
+                    Err(()) => {}
+                    _ => simple = false,
+                }
+                i += len + 1;
+            }
+            None => ranges.push((lo, lo)),
+        }
+
+        match tokens.get(i) {
+            Some(TokenTree::Punct(p)) if p.as_char() == '|' => i += 1,
+            None => {}
+            Some(_) => simple = false,
+        }
+    }
+
+    (ranges, simple)
+}
+
+/// The value of a character literal token.
+fn char_literal(lit: &Literal) -> Option<char> {
+    let s = lit.to_string();
+    let inner = s.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut chars = inner.chars();
+    let c = match chars.next()? {
+        '\\' => match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            '\\' => '\\',
+            '\'' => '\'',
+            '"' => '"',
+            'x' => {
+                let code = u32::from_str_radix(chars.as_str(), 16).ok()?;
+                chars = "".chars();
+                std::char::from_u32(code)?
+            }
+            'u' => {
+                let hex = chars.as_str().strip_prefix('{')?.strip_suffix('}')?.replace('_', "");
+                chars = "".chars();
+                std::char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+            }
+            _ => return None,
+        },
+        c => c,
+    };
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(c)
+}
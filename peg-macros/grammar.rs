@@ -6,14 +6,14 @@ pub mod peg {
     type PositionRepr = <Input as ::peg::Parse>::PositionRepr;
     #[allow(unused_parens)]
     struct ParseState<'input> {
-        _phantom: ::std::marker::PhantomData<(&'input ())>,
-        primary_cache: ::std::collections::HashMap<usize, ::peg::RuleResult<SpannedExpr>>,
+        _phantom: ::peg::__core::marker::PhantomData<(&'input ())>,
+        primary_cache: ::peg::memo::MemoTable<::peg::RuleResult<SpannedExpr>>,
     }
     impl<'input> ParseState<'input> {
-        fn new() -> ParseState<'input> {
+        fn new(__input_len: Option<usize>) -> ParseState<'input> {
             ParseState {
-                _phantom: ::std::marker::PhantomData,
-                primary_cache: ::std::collections::HashMap::new(),
+                _phantom: ::peg::__core::marker::PhantomData,
+                primary_cache: ::peg::memo::MemoTable::new(::peg::memo::Storage::Auto, __input_len),
             }
         }
     }
@@ -23,40 +23,65 @@ pub mod peg {
     use proc_macro2::{Delimiter, Group, Ident, Literal, Span, TokenStream};
     pub fn peg_grammar<'input>(
         __input: &'input Input,
-    ) -> ::std::result::Result<Grammar, ::peg::error::ParseError<PositionRepr>> {
+    ) -> ::peg::__core::result::Result<Grammar, ::peg::error::ParseError<PositionRepr>> {
+        __export_peg_grammar(__input, &::peg::Limits::default(), None, None)
+    }
+    #[doc = " Like [`peg_grammar`], but with `limits` on the parse."]
+    #[allow(dead_code)]
+    pub fn peg_grammar_with_limits<'input>(
+        __input: &'input Input,
+        __limits: &::peg::Limits,
+    ) -> ::peg::__core::result::Result<Grammar, ::peg::error::ParseError<PositionRepr>> {
+        __export_peg_grammar(__input, __limits, None, None)
+    }
+    fn __export_peg_grammar<'input>(
+        __input: &'input Input,
+        __limits: &::peg::Limits,
+        __tracer: ::peg::__core::option::Option<&mut dyn::peg::trace::Tracer>,
+        __stats: ::peg::__core::option::Option<&mut ::peg::stats::ParseStats>,
+    ) -> ::peg::__core::result::Result<Grammar, ::peg::error::ParseError<PositionRepr>> {
         #![allow(non_snake_case, unused)]
         let mut __err_state = ::peg::error::ErrorState::new(::peg::Parse::start(__input));
-        let mut __state = ParseState::new();
-        match __parse_peg_grammar(
+        __err_state.set_limits(__limits, ::peg::__core::option::Option::None);
+        let mut __state = ParseState::new(::peg::Parse::input_len(__input));
+        let __result = __parse_peg_grammar(
             __input,
             &mut __state,
             &mut __err_state,
             ::peg::Parse::start(__input),
-        ) {
+        );
+        if __err_state.stopped.is_some() {
+            return Err(__err_state.into_parse_error(__input));
+        }
+        match __result {
             ::peg::RuleResult::Matched(__pos, __value) => {
                 if ::peg::Parse::is_eof(__input, __pos) {
                     return Ok(__value);
                 } else {
-                    __err_state.mark_failure(__pos, "EOF");
+                    __err_state.mark_failure(__pos, ::peg::error::Expected::Eof);
                 }
             }
             _ => (),
         }
-        __state = ParseState::new();
+        __state = ParseState::new(::peg::Parse::input_len(__input));
         __err_state.reparse_for_error();
-        match __parse_peg_grammar(
+        let __result = __parse_peg_grammar(
             __input,
             &mut __state,
             &mut __err_state,
             ::peg::Parse::start(__input),
-        ) {
+        );
+        if __err_state.stopped.is_some() {
+            return Err(__err_state.into_parse_error(__input));
+        }
+        match __result {
             ::peg::RuleResult::Matched(__pos, __value) => {
                 if ::peg::Parse::is_eof(__input, __pos) {
                     panic!(
                         "Parser is nondeterministic: succeeded when reparsing for error position"
                     );
                 } else {
-                    __err_state.mark_failure(__pos, "EOF");
+                    __err_state.mark_failure(__pos, ::peg::error::Expected::Eof);
                 }
             }
             _ => (),
@@ -66,710 +91,1736 @@ pub mod peg {
     fn __parse_peg_grammar<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Grammar> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = __parse_rust_doc_comment(__input, __state, __err_state, __pos);
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, doc) => {
-                    let __seq_res = __parse_rust_visibility(__input, __state, __err_state, __pos);
+        if __err_state.enter_rule("peg_grammar", __pos) {
+            let __rule_result: ::peg::RuleResult<Grammar> = {
+                {
+                    let __seq_res = __parse_rust_doc_comment(__input, __state, __err_state, __pos);
                     match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, visibility) => {
-                            match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, "grammar",
-                            ) {
-                                ::peg::RuleResult::Matched(__pos, __val) => {
-                                    let __seq_res =
-                                        __parse_IDENT(__input, __state, __err_state, __pos);
-                                    match __seq_res {
-                                        ::peg::RuleResult::Matched(__pos, name) => {
-                                            let __seq_res = match __parse_rust_lifetime_params(
-                                                __input,
-                                                __state,
-                                                __err_state,
-                                                __pos,
-                                            ) {
-                                                ::peg::RuleResult::Matched(__newpos, __value) => {
-                                                    ::peg::RuleResult::Matched(
-                                                        __newpos,
-                                                        Some(__value),
-                                                    )
-                                                }
-                                                ::peg::RuleResult::Failed => {
-                                                    ::peg::RuleResult::Matched(__pos, None)
-                                                }
-                                            };
+                        ::peg::RuleResult::Matched(__pos, doc) => {
+                            let __seq_res =
+                                __parse_rust_visibility(__input, __state, __err_state, __pos);
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, visibility) => {
+                                    match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, "grammar",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            let __seq_res =
+                                                __parse_IDENT(__input, __state, __err_state, __pos);
                                             match __seq_res {
-                                                ::peg::RuleResult::Matched(
-                                                    __pos,
-                                                    lifetime_params,
-                                                ) => {
-                                                    let __seq_res = __parse_grammar_args(
-                                                        __input,
-                                                        __state,
-                                                        __err_state,
-                                                        __pos,
-                                                    );
-                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "for") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let str_start = __pos ; match match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , :: peg :: ParseSlice :: parse_slice (__input , str_start , __newpos)) } , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , input_type) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "{") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = vec ! () ; loop { let __pos = __repeat_pos ; let __step_res = __parse_item (__input , __state , __err_state , __pos) ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } :: peg :: RuleResult :: Matched (__repeat_pos , __repeat_value) } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , items) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Grammar { doc , visibility , name , lifetime_params , args , input_type , items } }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"}\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"{\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"for\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                ::peg::RuleResult::Matched(__pos, name) => {
+                                                    let __seq_res =
+                                                        match __parse_rust_lifetime_params(
+                                                            __input,
+                                                            __state,
+                                                            __err_state,
+                                                            __pos,
+                                                        ) {
+                                                            ::peg::RuleResult::Matched(
+                                                                __newpos,
+                                                                __value,
+                                                            ) => ::peg::RuleResult::Matched(
+                                                                __newpos,
+                                                                Some(__value),
+                                                            ),
+                                                            ::peg::RuleResult::Failed => {
+                                                                ::peg::RuleResult::Matched(
+                                                                    __pos, None,
+                                                                )
+                                                            }
+                                                        };
+                                                    match __seq_res {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            lifetime_params,
+                                                        ) => {
+                                                            let __seq_res = __parse_grammar_args(
+                                                                __input,
+                                                                __state,
+                                                                __err_state,
+                                                                __pos,
+                                                            );
+                                                            match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "for") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let str_start = __pos ; match match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , :: peg :: ParseSlice :: parse_slice (__input , str_start , __newpos)) } , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , input_type) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "{") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_incremental_flag (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , incremental) => { { let __seq_res = __parse_grammar_cache_storage (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , cache_storage) => { { let __seq_res = __parse_generator_flag (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , generator) => { { let __seq_res = __parse_grammar_skip (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , skip) => { { let __seq_res = __parse_grammar_max_depth (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , max_depth) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = :: peg :: __alloc :: vec :: Vec :: new () ; loop { let __pos = __repeat_pos ; let __step_res = __parse_item (__input , __state , __err_state , __pos) ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } :: peg :: RuleResult :: Matched (__repeat_pos , __repeat_value) } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , items) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Grammar { doc , visibility , name , lifetime_params , args , input_type , incremental , cache_storage , generator , skip , max_depth , items } }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"}\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"{\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"for\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
                                                 }
                                                 ::peg::RuleResult::Failed => {
                                                     ::peg::RuleResult::Failed
                                                 }
                                             }
                                         }
-                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"grammar\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
                                     }
                                 }
-                                ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\"grammar\"");
-                                    ::peg::RuleResult::Failed
-                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                             }
                         }
                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_rust_lifetime_params<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Vec<TokenStream>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<") {
-            ::peg::RuleResult::Matched(__pos, __val) => {
-                let __seq_res = {
-                    let mut __repeat_pos = __pos;
-                    let mut __repeat_value = vec![];
-                    loop {
-                        let __pos = __repeat_pos;
-                        let __pos = if __repeat_value.is_empty() {
-                            __pos
-                        } else {
-                            let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, ",",
-                            ) {
-                                ::peg::RuleResult::Matched(__pos, __val) => {
-                                    ::peg::RuleResult::Matched(__pos, __val)
-                                }
-                                ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\",\"");
-                                    ::peg::RuleResult::Failed
+        if __err_state.enter_rule("rust_lifetime_params", __pos) {
+            let __rule_result: ::peg::RuleResult<Vec<TokenStream>> = {
+                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<") {
+                    ::peg::RuleResult::Matched(__pos, __val) => {
+                        let __seq_res = {
+                            let mut __repeat_pos = __pos;
+                            let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                            loop {
+                                let __pos = __repeat_pos;
+                                let __pos = if __repeat_value.is_empty() {
+                                    __pos
+                                } else {
+                                    let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, ",",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            ::peg::RuleResult::Matched(__pos, __val)
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\",\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    };
+                                    match __sep_res {
+                                        ::peg::RuleResult::Matched(__newpos, _) => __newpos,
+                                        ::peg::RuleResult::Failed => break,
+                                    }
+                                };
+                                let __step_res = {
+                                    let str_start = __pos;
+                                    match match __parse_LIFETIME(
+                                        __input,
+                                        __state,
+                                        __err_state,
+                                        __pos,
+                                    ) {
+                                        ::peg::RuleResult::Matched(pos, _) => {
+                                            ::peg::RuleResult::Matched(pos, ())
+                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    } {
+                                        ::peg::RuleResult::Matched(__newpos, _) => {
+                                            ::peg::RuleResult::Matched(
+                                                __newpos,
+                                                ::peg::ParseSlice::parse_slice(
+                                                    __input, str_start, __newpos,
+                                                ),
+                                            )
+                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    }
+                                };
+                                match __step_res {
+                                    ::peg::RuleResult::Matched(__newpos, __value) => {
+                                        __repeat_pos = __newpos;
+                                        __repeat_value.push(__value);
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        break;
+                                    }
                                 }
-                            };
-                            match __sep_res {
-                                ::peg::RuleResult::Matched(__newpos, _) => __newpos,
-                                ::peg::RuleResult::Failed => break,
                             }
-                        };
-                        let __step_res = {
-                            let str_start = __pos;
-                            match match __parse_LIFETIME(__input, __state, __err_state, __pos) {
-                                ::peg::RuleResult::Matched(pos, _) => {
-                                    ::peg::RuleResult::Matched(pos, ())
-                                }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                            } {
-                                ::peg::RuleResult::Matched(__newpos, _) => {
-                                    ::peg::RuleResult::Matched(
-                                        __newpos,
-                                        ::peg::ParseSlice::parse_slice(
-                                            __input, str_start, __newpos,
-                                        ),
-                                    )
-                                }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            if __repeat_value.len() >= 1 {
+                                ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                            } else {
+                                ::peg::RuleResult::Failed
                             }
                         };
-                        match __step_res {
-                            ::peg::RuleResult::Matched(__newpos, __value) => {
-                                __repeat_pos = __newpos;
-                                __repeat_value.push(__value);
-                            }
-                            ::peg::RuleResult::Failed => {
-                                break;
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, p) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ">")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        ::peg::RuleResult::Matched(__pos, (|| p)())
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\">\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
                             }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                         }
                     }
-                    if __repeat_value.len() >= 1 {
-                        ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
-                    } else {
+                    ::peg::RuleResult::Failed => {
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Literal("\"<\""));
                         ::peg::RuleResult::Failed
                     }
-                };
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, p) => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ">") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                ::peg::RuleResult::Matched(__pos, (|| p)())
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\">\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        }
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                 }
-            }
-            ::peg::RuleResult::Failed => {
-                __err_state.mark_failure(__pos, "\"<\"");
-                ::peg::RuleResult::Failed
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
-    fn __parse_grammar_args<'input>(
+    fn __parse_incremental_flag<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
-    ) -> ::peg::RuleResult<Vec<(Ident, TokenStream)>> {
+    ) -> ::peg::RuleResult<bool> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "(") {
-            ::peg::RuleResult::Matched(__pos, __val) => {
-                let __seq_res = {
-                    let mut __repeat_pos = __pos;
-                    let mut __repeat_value = vec![];
-                    loop {
-                        let __pos = __repeat_pos;
-                        let __pos = if __repeat_value.is_empty() {
-                            __pos
-                        } else {
-                            let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, ",",
-                            ) {
+        if __err_state.enter_rule("incremental_flag", __pos) {
+            let __rule_result: ::peg::RuleResult<bool> = {
+                {
+                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "#",
+                    ) {
+                        ::peg::RuleResult::Matched(__pos, __val) => {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "!") {
                                 ::peg::RuleResult::Matched(__pos, __val) => {
-                                    ::peg::RuleResult::Matched(__pos, __val)
-                                }
-                                ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\",\"");
-                                    ::peg::RuleResult::Failed
-                                }
-                            };
-                            match __sep_res {
-                                ::peg::RuleResult::Matched(__newpos, _) => __newpos,
-                                ::peg::RuleResult::Failed => break,
-                            }
-                        };
-                        let __step_res = {
-                            let __seq_res = __parse_IDENT(__input, __state, __err_state, __pos);
-                            match __seq_res {
-                                ::peg::RuleResult::Matched(__pos, i) => {
                                     match ::peg::ParseLiteral::parse_string_literal(
-                                        __input, __pos, ":",
+                                        __input, __pos, "[",
                                     ) {
                                         ::peg::RuleResult::Matched(__pos, __val) => {
-                                            let __seq_res = {
-                                                let str_start = __pos;
-                                                match match __parse_rust_type(
-                                                    __input,
-                                                    __state,
-                                                    __err_state,
-                                                    __pos,
-                                                ) {
-                                                    ::peg::RuleResult::Matched(pos, _) => {
-                                                        ::peg::RuleResult::Matched(pos, ())
-                                                    }
-                                                    ::peg::RuleResult::Failed => {
-                                                        ::peg::RuleResult::Failed
-                                                    }
-                                                } {
-                                                    ::peg::RuleResult::Matched(__newpos, _) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input,
+                                                __pos,
+                                                "incremental",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    match ::peg::ParseLiteral::parse_string_literal(
+                                                        __input, __pos, "]",
+                                                    ) {
                                                         ::peg::RuleResult::Matched(
-                                                            __newpos,
-                                                            ::peg::ParseSlice::parse_slice(
-                                                                __input, str_start, __newpos,
-                                                            ),
-                                                        )
-                                                    }
-                                                    ::peg::RuleResult::Failed => {
-                                                        ::peg::RuleResult::Failed
+                                                            __pos,
+                                                            __val,
+                                                        ) => ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| true)(),
+                                                        ),
+                                                        ::peg::RuleResult::Failed => {
+                                                            __err_state.mark_failure(
+                                                                __pos,
+                                                                ::peg::error::Expected::Literal(
+                                                                    "\"]\"",
+                                                                ),
+                                                            );
+                                                            ::peg::RuleResult::Failed
+                                                        }
                                                     }
                                                 }
-                                            };
-                                            match __seq_res {
-                                                ::peg::RuleResult::Matched(__pos, t) => {
-                                                    ::peg::RuleResult::Matched(__pos, (|| (i, t))())
-                                                }
                                                 ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal(
+                                                            "\"incremental\"",
+                                                        ),
+                                                    );
                                                     ::peg::RuleResult::Failed
                                                 }
                                             }
                                         }
                                         ::peg::RuleResult::Failed => {
-                                            __err_state.mark_failure(__pos, "\":\"");
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"[\""),
+                                            );
                                             ::peg::RuleResult::Failed
                                         }
                                     }
                                 }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                            }
-                        };
-                        match __step_res {
-                            ::peg::RuleResult::Matched(__newpos, __value) => {
-                                __repeat_pos = __newpos;
-                                __repeat_value.push(__value);
-                            }
-                            ::peg::RuleResult::Failed => {
-                                break;
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"!\""),
+                                    );
+                                    ::peg::RuleResult::Failed
+                                }
                             }
                         }
+                        ::peg::RuleResult::Failed => {
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
+                            ::peg::RuleResult::Failed
+                        }
+                    };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            ::peg::RuleResult::Matched(__pos, (|| false)())
+                        }
                     }
-                    ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
-                };
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, args) => {
-                        let __seq_res = match match ::peg::ParseLiteral::parse_string_literal(
-                            __input, __pos, ",",
-                        ) {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                ::peg::RuleResult::Matched(__pos, __val)
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\",\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        } {
-                            ::peg::RuleResult::Matched(__newpos, _) => {
-                                ::peg::RuleResult::Matched(__newpos, ())
-                            }
-                            ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, ()),
-                        };
-                        match __seq_res {
-                            ::peg::RuleResult::Matched(__pos, _) => {
-                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ")")
-                                {
-                                    ::peg::RuleResult::Matched(__pos, __val) => {
-                                        ::peg::RuleResult::Matched(__pos, (|| args)())
-                                    }
-                                    ::peg::RuleResult::Failed => {
-                                        __err_state.mark_failure(__pos, "\")\"");
-                                        ::peg::RuleResult::Failed
-                                    }
-                                }
-                            }
-                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                        }
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                 }
-            }
-            ::peg::RuleResult::Failed => {
-                __err_state.mark_failure(__pos, "\"(\"");
-                ::peg::RuleResult::Failed
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
-    fn __parse_peg_rule<'input>(
+    fn __parse_grammar_cache_storage<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
-    ) -> ::peg::RuleResult<Rule> {
+    ) -> ::peg::RuleResult<Option<CacheStorage>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = __parse_rust_doc_comment(__input, __state, __err_state, __pos);
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, doc) => {
-                    let __seq_res = __parse_cacheflag(__input, __state, __err_state, __pos);
-                    match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, cache) => {
-                            let __seq_res =
-                                __parse_no_eof_flag(__input, __state, __err_state, __pos);
-                            match __seq_res {
-                                ::peg::RuleResult::Matched(__pos, no_eof) => {
-                                    let __seq_res = __parse_rust_visibility(
-                                        __input,
-                                        __state,
-                                        __err_state,
-                                        __pos,
-                                    );
-                                    match __seq_res {
-                                        ::peg::RuleResult::Matched(__pos, visibility) => {
-                                            let __seq_res =
-                                                __parse_sp(__input, __state, __err_state, __pos);
-                                            match __seq_res {
-                                                ::peg::RuleResult::Matched(__pos, span) => {
-                                                    match ::peg::ParseLiteral::parse_string_literal(
-                                                        __input, __pos, "rule",
-                                                    ) {
-                                                        ::peg::RuleResult::Matched(
-                                                            __pos,
-                                                            __val,
-                                                        ) => {
-                                                            let __seq_res = {
-                                                                let __choice_res = {
-                                                                    let __seq_res = {
-                                                                        __err_state
-                                                                            .suppress_fail += 1;
-                                                                        let __assert_res = {
-                                                                            let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "_") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"_\"") ; :: peg :: RuleResult :: Failed } } ;
-                                                                            match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "__") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"__\"") ; :: peg :: RuleResult :: Failed } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "___") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"___\"") ; :: peg :: RuleResult :: Failed } } } } }
-                                                                        };
-                                                                        __err_state
-                                                                            .suppress_fail -= 1;
-                                                                        match __assert_res { :: peg :: RuleResult :: Matched (_ , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
-                                                                    };
-                                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { { let __seq_res = __parse_IDENT (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , name) => { { let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\")\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"(\"") ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , (|| { (name , None , Vec :: new ()) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
-                                                                };
-                                                                match __choice_res {
-                                                                    ::peg::RuleResult::Matched(
-                                                                        __pos,
-                                                                        __value,
-                                                                    ) => {
-                                                                        ::peg::RuleResult::Matched(
-                                                                            __pos, __value,
-                                                                        )
-                                                                    }
-                                                                    ::peg::RuleResult::Failed => {
-                                                                        let __seq_res =
-                                                                            __parse_IDENT(
-                                                                                __input,
-                                                                                __state,
-                                                                                __err_state,
-                                                                                __pos,
-                                                                            );
-                                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , name) => { { let __seq_res = match __parse_rust_ty_params (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (__newpos , __value) => { :: peg :: RuleResult :: Matched (__newpos , Some (__value)) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , None) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , ty_params) => { { let __seq_res = __parse_rule_params (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , params) => { :: peg :: RuleResult :: Matched (__pos , (|| { (name , ty_params , params) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
-                                                                    }
-                                                                }
-                                                            };
-                                                            match __seq_res {
-                                                                ::peg::RuleResult::Matched(
-                                                                    __pos,
-                                                                    header,
-                                                                ) => {
-                                                                    let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "->") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let str_start = __pos ; match match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , :: peg :: ParseSlice :: parse_slice (__input , str_start , __newpos)) } , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , t) => { :: peg :: RuleResult :: Matched (__pos , (|| { t }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"->\"") ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , __value) => { :: peg :: RuleResult :: Matched (__newpos , Some (__value)) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , None) } , } ;
-                                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , ret_type) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "=") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , expr) => { { let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ";") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\";\"") ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , (|| { Rule { span , doc , name : header . 0 , ty_params : header . 1 , params : header . 2 , expr , ret_type , visibility , no_eof , cache } }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"=\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
-                                                                }
-                                                                ::peg::RuleResult::Failed => {
-                                                                    ::peg::RuleResult::Failed
-                                                                }
-                                                            }
-                                                        }
-                                                        ::peg::RuleResult::Failed => {
-                                                            __err_state
-                                                                .mark_failure(__pos, "\"rule\"");
-                                                            ::peg::RuleResult::Failed
-                                                        }
-                                                    }
+        if __err_state.enter_rule("grammar_cache_storage", __pos) {
+            let __rule_result: ::peg::RuleResult<Option<CacheStorage>> = {
+                {
+                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "#",
+                    ) {
+                        ::peg::RuleResult::Matched(__pos, __val) => {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "!") {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, "[",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, "cache",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    let __seq_res = __parse_cache_storage(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
+                                                        __pos,
+                                                    );
+                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , s) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "]") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Some (s) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"]\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                 }
                                                 ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal(
+                                                            "\"cache\"",
+                                                        ),
+                                                    );
                                                     ::peg::RuleResult::Failed
                                                 }
                                             }
                                         }
-                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"[\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
                                     }
                                 }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"!\""),
+                                    );
+                                    ::peg::RuleResult::Failed
+                                }
                             }
                         }
-                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        ::peg::RuleResult::Failed => {
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
+                            ::peg::RuleResult::Failed
+                        }
+                    };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, (|| None)()),
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
-    fn __parse_cacheflag<'input>(
+    fn __parse_generator_flag<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
-    ) -> ::peg::RuleResult<Option<Cache>> {
+    ) -> ::peg::RuleResult<bool> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "#")
-            {
-                ::peg::RuleResult::Matched(__pos, __val) => {
-                    match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[") {
+        if __err_state.enter_rule("generator_flag", __pos) {
+            let __rule_result: ::peg::RuleResult<bool> = {
+                {
+                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "#",
+                    ) {
                         ::peg::RuleResult::Matched(__pos, __val) => {
-                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "cache")
-                            {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "!") {
                                 ::peg::RuleResult::Matched(__pos, __val) => {
                                     match ::peg::ParseLiteral::parse_string_literal(
-                                        __input, __pos, "]",
+                                        __input, __pos, "[",
                                     ) {
                                         ::peg::RuleResult::Matched(__pos, __val) => {
-                                            ::peg::RuleResult::Matched(
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input,
                                                 __pos,
-                                                (|| Some(Cache::Simple))(),
-                                            )
+                                                "generator",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    match ::peg::ParseLiteral::parse_string_literal(
+                                                        __input, __pos, "]",
+                                                    ) {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            __val,
+                                                        ) => ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| true)(),
+                                                        ),
+                                                        ::peg::RuleResult::Failed => {
+                                                            __err_state.mark_failure(
+                                                                __pos,
+                                                                ::peg::error::Expected::Literal(
+                                                                    "\"]\"",
+                                                                ),
+                                                            );
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal(
+                                                            "\"generator\"",
+                                                        ),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            }
                                         }
                                         ::peg::RuleResult::Failed => {
-                                            __err_state.mark_failure(__pos, "\"]\"");
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"[\""),
+                                            );
                                             ::peg::RuleResult::Failed
                                         }
                                     }
                                 }
                                 ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\"cache\"");
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"!\""),
+                                    );
                                     ::peg::RuleResult::Failed
                                 }
                             }
                         }
                         ::peg::RuleResult::Failed => {
-                            __err_state.mark_failure(__pos, "\"[\"");
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
                             ::peg::RuleResult::Failed
                         }
-                    }
-                }
-                ::peg::RuleResult::Failed => {
-                    __err_state.mark_failure(__pos, "\"#\"");
-                    ::peg::RuleResult::Failed
-                }
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
-                    let __choice_res =
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "#") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[")
-                                {
-                                    ::peg::RuleResult::Matched(__pos, __val) => {
-                                        match ::peg::ParseLiteral::parse_string_literal(
-                                            __input,
-                                            __pos,
-                                            "cache_left_rec",
-                                        ) {
-                                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                                match ::peg::ParseLiteral::parse_string_literal(
-                                                    __input, __pos, "]",
-                                                ) {
-                                                    ::peg::RuleResult::Matched(__pos, __val) => {
-                                                        ::peg::RuleResult::Matched(
-                                                            __pos,
-                                                            (|| Some(Cache::Recursive))(),
-                                                        )
-                                                    }
-                                                    ::peg::RuleResult::Failed => {
-                                                        __err_state.mark_failure(__pos, "\"]\"");
-                                                        ::peg::RuleResult::Failed
-                                                    }
-                                                }
-                                            }
-                                            ::peg::RuleResult::Failed => {
-                                                __err_state
-                                                    .mark_failure(__pos, "\"cache_left_rec\"");
-                                                ::peg::RuleResult::Failed
-                                            }
-                                        }
-                                    }
-                                    ::peg::RuleResult::Failed => {
-                                        __err_state.mark_failure(__pos, "\"[\"");
-                                        ::peg::RuleResult::Failed
-                                    }
-                                }
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"#\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        };
+                    };
                     match __choice_res {
                         ::peg::RuleResult::Matched(__pos, __value) => {
                             ::peg::RuleResult::Matched(__pos, __value)
                         }
-                        ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, (|| None)()),
+                        ::peg::RuleResult::Failed => {
+                            ::peg::RuleResult::Matched(__pos, (|| false)())
+                        }
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
-    fn __parse_no_eof_flag<'input>(
+    fn __parse_grammar_skip<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
-    ) -> ::peg::RuleResult<bool> {
+    ) -> ::peg::RuleResult<Option<Ident>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "#")
-            {
-                ::peg::RuleResult::Matched(__pos, __val) => {
-                    match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[") {
+        if __err_state.enter_rule("grammar_skip", __pos) {
+            let __rule_result: ::peg::RuleResult<Option<Ident>> = {
+                {
+                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "#",
+                    ) {
                         ::peg::RuleResult::Matched(__pos, __val) => {
-                            match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, "no_eof",
-                            ) {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "!") {
                                 ::peg::RuleResult::Matched(__pos, __val) => {
                                     match ::peg::ParseLiteral::parse_string_literal(
-                                        __input, __pos, "]",
+                                        __input, __pos, "[",
                                     ) {
                                         ::peg::RuleResult::Matched(__pos, __val) => {
-                                            ::peg::RuleResult::Matched(__pos, (|| true)())
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, "skip",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    match ::peg::ParseLiteral::parse_string_literal(
+                                                        __input, __pos, "(",
+                                                    ) {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            __val,
+                                                        ) => {
+                                                            let __seq_res = __parse_IDENT(
+                                                                __input,
+                                                                __state,
+                                                                __err_state,
+                                                                __pos,
+                                                            );
+                                                            match __seq_res { :: peg :: RuleResult :: Matched (__pos , i) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "]") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Some (i) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"]\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            __err_state.mark_failure(
+                                                                __pos,
+                                                                ::peg::error::Expected::Literal(
+                                                                    "\"(\"",
+                                                                ),
+                                                            );
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\"skip\""),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            }
                                         }
                                         ::peg::RuleResult::Failed => {
-                                            __err_state.mark_failure(__pos, "\"]\"");
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"[\""),
+                                            );
                                             ::peg::RuleResult::Failed
                                         }
                                     }
                                 }
                                 ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\"no_eof\"");
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"!\""),
+                                    );
                                     ::peg::RuleResult::Failed
                                 }
                             }
                         }
                         ::peg::RuleResult::Failed => {
-                            __err_state.mark_failure(__pos, "\"[\"");
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
                             ::peg::RuleResult::Failed
                         }
+                    };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, (|| None)()),
                     }
                 }
-                ::peg::RuleResult::Failed => {
-                    __err_state.mark_failure(__pos, "\"#\"");
-                    ::peg::RuleResult::Failed
-                }
             };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, (|| false)()),
-            }
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
-    fn __parse_rust_ty_params<'input>(
+    fn __parse_grammar_max_depth<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
-    ) -> ::peg::RuleResult<Vec<TokenStream>> {
+    ) -> ::peg::RuleResult<Option<Literal>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<") {
-            ::peg::RuleResult::Matched(__pos, __val) => {
-                let __seq_res = {
-                    let mut __repeat_pos = __pos;
-                    let mut __repeat_value = vec![];
-                    loop {
-                        let __pos = __repeat_pos;
-                        let __pos = if __repeat_value.is_empty() {
-                            __pos
-                        } else {
-                            let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, ",",
-                            ) {
+        if __err_state.enter_rule("grammar_max_depth", __pos) {
+            let __rule_result: ::peg::RuleResult<Option<Literal>> = {
+                {
+                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "#",
+                    ) {
+                        ::peg::RuleResult::Matched(__pos, __val) => {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "!") {
                                 ::peg::RuleResult::Matched(__pos, __val) => {
-                                    ::peg::RuleResult::Matched(__pos, __val)
-                                }
-                                ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\",\"");
-                                    ::peg::RuleResult::Failed
-                                }
-                            };
-                            match __sep_res {
-                                ::peg::RuleResult::Matched(__newpos, _) => __newpos,
-                                ::peg::RuleResult::Failed => break,
-                            }
+                                    match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, "[",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input,
+                                                __pos,
+                                                "max_depth",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    match ::peg::ParseLiteral::parse_string_literal(
+                                                        __input, __pos, "(",
+                                                    ) {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            __val,
+                                                        ) => {
+                                                            let __seq_res = __parse_LITERAL(
+                                                                __input,
+                                                                __state,
+                                                                __err_state,
+                                                                __pos,
+                                                            );
+                                                            match __seq_res { :: peg :: RuleResult :: Matched (__pos , n) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "]") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Some (n) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"]\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            __err_state.mark_failure(
+                                                                __pos,
+                                                                ::peg::error::Expected::Literal(
+                                                                    "\"(\"",
+                                                                ),
+                                                            );
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal(
+                                                            "\"max_depth\"",
+                                                        ),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            }
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"[\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    }
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"!\""),
+                                    );
+                                    ::peg::RuleResult::Failed
+                                }
+                            }
+                        }
+                        ::peg::RuleResult::Failed => {
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
+                            ::peg::RuleResult::Failed
+                        }
+                    };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, (|| None)()),
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_grammar_args<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<Vec<(Ident, TokenStream)>> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("grammar_args", __pos) {
+            let __rule_result: ::peg::RuleResult<Vec<(Ident, TokenStream)>> = {
+                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "(") {
+                    ::peg::RuleResult::Matched(__pos, __val) => {
+                        let __seq_res = {
+                            let mut __repeat_pos = __pos;
+                            let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                            loop {
+                                let __pos = __repeat_pos;
+                                let __pos = if __repeat_value.is_empty() {
+                                    __pos
+                                } else {
+                                    let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, ",",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            ::peg::RuleResult::Matched(__pos, __val)
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\",\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    };
+                                    match __sep_res {
+                                        ::peg::RuleResult::Matched(__newpos, _) => __newpos,
+                                        ::peg::RuleResult::Failed => break,
+                                    }
+                                };
+                                let __step_res = {
+                                    let __seq_res =
+                                        __parse_IDENT(__input, __state, __err_state, __pos);
+                                    match __seq_res {
+                                        ::peg::RuleResult::Matched(__pos, i) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, ":",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    let __seq_res = {
+                                                        let str_start = __pos;
+                                                        match match __parse_rust_type(
+                                                            __input,
+                                                            __state,
+                                                            __err_state,
+                                                            __pos,
+                                                        ) {
+                                                            ::peg::RuleResult::Matched(pos, _) => {
+                                                                ::peg::RuleResult::Matched(pos, ())
+                                                            }
+                                                            ::peg::RuleResult::Failed => {
+                                                                ::peg::RuleResult::Failed
+                                                            }
+                                                        } {
+                                                            ::peg::RuleResult::Matched(
+                                                                __newpos,
+                                                                _,
+                                                            ) => ::peg::RuleResult::Matched(
+                                                                __newpos,
+                                                                ::peg::ParseSlice::parse_slice(
+                                                                    __input, str_start, __newpos,
+                                                                ),
+                                                            ),
+                                                            ::peg::RuleResult::Failed => {
+                                                                ::peg::RuleResult::Failed
+                                                            }
+                                                        }
+                                                    };
+                                                    match __seq_res {
+                                                        ::peg::RuleResult::Matched(__pos, t) => {
+                                                            ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                (|| (i, t))(),
+                                                            )
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\":\""),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            }
+                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    }
+                                };
+                                match __step_res {
+                                    ::peg::RuleResult::Matched(__newpos, __value) => {
+                                        __repeat_pos = __newpos;
+                                        __repeat_value.push(__value);
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                        };
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, args) => {
+                                let __seq_res =
+                                    match match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, ",",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            ::peg::RuleResult::Matched(__pos, __val)
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\",\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    } {
+                                        ::peg::RuleResult::Matched(__newpos, _) => {
+                                            ::peg::RuleResult::Matched(__newpos, ())
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            ::peg::RuleResult::Matched(__pos, ())
+                                        }
+                                    };
+                                match __seq_res {
+                                    ::peg::RuleResult::Matched(__pos, _) => {
+                                        match ::peg::ParseLiteral::parse_string_literal(
+                                            __input, __pos, ")",
+                                        ) {
+                                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                                ::peg::RuleResult::Matched(__pos, (|| args)())
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\")\""),
+                                                );
+                                                ::peg::RuleResult::Failed
+                                            }
+                                        }
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                }
+                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        }
+                    }
+                    ::peg::RuleResult::Failed => {
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Literal("\"(\""));
+                        ::peg::RuleResult::Failed
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_peg_rule<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<Rule> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("peg_rule", __pos) {
+            let __rule_result: ::peg::RuleResult<Rule> = {
+                {
+                    let __seq_res = __parse_rust_doc_comment(__input, __state, __err_state, __pos);
+                    match __seq_res {
+                        ::peg::RuleResult::Matched(__pos, doc) => {
+                            let __seq_res = __parse_cacheflag(__input, __state, __err_state, __pos);
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, cache) => {
+                                    let __seq_res =
+                                        __parse_no_eof_flag(__input, __state, __err_state, __pos);
+                                    match __seq_res {
+                                        ::peg::RuleResult::Matched(__pos, no_eof) => {
+                                            let __seq_res = __parse_recover_flag(
+                                                __input,
+                                                __state,
+                                                __err_state,
+                                                __pos,
+                                            );
+                                            match __seq_res {
+                                                ::peg::RuleResult::Matched(__pos, recover) => {
+                                                    let __seq_res = __parse_context_flag(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
+                                                        __pos,
+                                                    );
+                                                    match __seq_res {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            context,
+                                                        ) => {
+                                                            let __seq_res = __parse_lexical_flag(
+                                                                __input,
+                                                                __state,
+                                                                __err_state,
+                                                                __pos,
+                                                            );
+                                                            match __seq_res {
+                                                                ::peg::RuleResult::Matched(
+                                                                    __pos,
+                                                                    lexical,
+                                                                ) => {
+                                                                    let __seq_res =
+                                                                        __parse_rust_visibility(
+                                                                            __input,
+                                                                            __state,
+                                                                            __err_state,
+                                                                            __pos,
+                                                                        );
+                                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , visibility) => { { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , span) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "rule") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let __choice_res = { let __seq_res = { __err_state . suppress_fail += 1 ; let __assert_res = { let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "_") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"_\"")) ; :: peg :: RuleResult :: Failed } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "__") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"__\"")) ; :: peg :: RuleResult :: Failed } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "___") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"___\"")) ; :: peg :: RuleResult :: Failed } } } } } } ; __err_state . suppress_fail -= 1 ; match __assert_res { :: peg :: RuleResult :: Matched (_ , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { { let __seq_res = __parse_IDENT (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , name) => { { let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , (|| { (name , None , Vec :: new ()) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __seq_res = __parse_IDENT (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , name) => { { let __seq_res = match __parse_rust_ty_params (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (__newpos , __value) => { :: peg :: RuleResult :: Matched (__newpos , Some (__value)) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , None) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , ty_params) => { { let __seq_res = __parse_rule_params (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , params) => { :: peg :: RuleResult :: Matched (__pos , (|| { (name , ty_params , params) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , header) => { { let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "->") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let str_start = __pos ; match match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , :: peg :: ParseSlice :: parse_slice (__input , str_start , __newpos)) } , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , t) => { :: peg :: RuleResult :: Matched (__pos , (|| { t }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"->\"")) ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , __value) => { :: peg :: RuleResult :: Matched (__newpos , Some (__value)) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , None) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , ret_type) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "=") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , expr) => { { let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ";") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\";\"")) ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , (|| { Rule { span , doc , name : header . 0 , ty_params : header . 1 , params : header . 2 , expr , ret_type , visibility , no_eof , recover , context , lexical , cache } }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"=\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"rule\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                                }
+                                                                ::peg::RuleResult::Failed => {
+                                                                    ::peg::RuleResult::Failed
+                                                                }
+                                                            }
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            }
+                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    }
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            }
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_cacheflag<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<Option<Cache>> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("cacheflag", __pos) {
+            let __rule_result: ::peg::RuleResult<Option<Cache>> = {
+                {
+                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "#",
+                    ) {
+                        ::peg::RuleResult::Matched(__pos, __val) => {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[") {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, "cache",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            let __seq_res = __parse_cache_args(
+                                                __input,
+                                                __state,
+                                                __err_state,
+                                                __pos,
+                                            );
+                                            match __seq_res {
+                                                ::peg::RuleResult::Matched(__pos, args) => {
+                                                    match ::peg::ParseLiteral::parse_string_literal(
+                                                        __input, __pos, "]",
+                                                    ) {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            __val,
+                                                        ) => ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| {
+                                                                Some(Cache {
+                                                                    kind: CacheKind::Simple,
+                                                                    shared: args.0,
+                                                                    storage: args.1,
+                                                                })
+                                                            })(
+                                                            ),
+                                                        ),
+                                                        ::peg::RuleResult::Failed => {
+                                                            __err_state.mark_failure(
+                                                                __pos,
+                                                                ::peg::error::Expected::Literal(
+                                                                    "\"]\"",
+                                                                ),
+                                                            );
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            }
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"cache\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    }
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"[\""),
+                                    );
+                                    ::peg::RuleResult::Failed
+                                }
+                            }
+                        }
+                        ::peg::RuleResult::Failed => {
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
+                            ::peg::RuleResult::Failed
+                        }
+                    };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                                __input, __pos, "#",
+                            ) {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, "[",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input,
+                                                __pos,
+                                                "cache_left_rec",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    let __seq_res = __parse_cache_args(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
+                                                        __pos,
+                                                    );
+                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "]") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Some (Cache { kind : CacheKind :: Recursive , shared : args . 0 , storage : args . 1 }) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"]\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal(
+                                                            "\"cache_left_rec\"",
+                                                        ),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            }
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"[\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    }
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"#\""),
+                                    );
+                                    ::peg::RuleResult::Failed
+                                }
+                            };
+                            match __choice_res {
+                                ::peg::RuleResult::Matched(__pos, __value) => {
+                                    ::peg::RuleResult::Matched(__pos, __value)
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    ::peg::RuleResult::Matched(__pos, (|| None)())
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_cache_args<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<(bool, Option<CacheStorage>)> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("cache_args", __pos) {
+            let __rule_result: ::peg::RuleResult<(bool, Option<CacheStorage>)> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "(") {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                match ::peg::ParseLiteral::parse_string_literal(
+                                    __input, __pos, "shared",
+                                ) {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        let __seq_res =
+                                            match match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, ",",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    let __seq_res = __parse_cache_storage_name(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
+                                                        __pos,
+                                                    );
+                                                    match __seq_res {
+                                                        ::peg::RuleResult::Matched(__pos, s) => {
+                                                            ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                (|| s)(),
+                                                            )
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\",\""),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            } {
+                                                ::peg::RuleResult::Matched(__newpos, __value) => {
+                                                    ::peg::RuleResult::Matched(
+                                                        __newpos,
+                                                        Some(__value),
+                                                    )
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Matched(__pos, None)
+                                                }
+                                            };
+                                        match __seq_res {
+                                            ::peg::RuleResult::Matched(__pos, storage) => {
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, ")",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| (true, storage))(),
+                                                        )
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\")\"",
+                                                            ),
+                                                        );
+                                                        ::peg::RuleResult::Failed
+                                                    }
+                                                }
+                                            }
+                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                        }
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"shared\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
+                            }
+                            ::peg::RuleResult::Failed => {
+                                __err_state
+                                    .mark_failure(__pos, ::peg::error::Expected::Literal("\"(\""));
+                                ::peg::RuleResult::Failed
+                            }
                         };
-                        let __step_res = {
-                            let str_start = __pos;
-                            match {
-                                let __choice_res =
-                                    match __parse_IDENT(__input, __state, __err_state, __pos) {
-                                        ::peg::RuleResult::Matched(pos, _) => {
-                                            ::peg::RuleResult::Matched(pos, ())
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            let __choice_res = {
+                                let __seq_res =
+                                    __parse_cache_storage(__input, __state, __err_state, __pos);
+                                match __seq_res {
+                                    ::peg::RuleResult::Matched(__pos, s) => {
+                                        ::peg::RuleResult::Matched(__pos, (|| (false, Some(s)))())
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                }
+                            };
+                            match __choice_res {
+                                ::peg::RuleResult::Matched(__pos, __value) => {
+                                    ::peg::RuleResult::Matched(__pos, __value)
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    ::peg::RuleResult::Matched(__pos, (|| (false, None))())
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_cache_storage<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<CacheStorage> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("cache_storage", __pos) {
+            let __rule_result: ::peg::RuleResult<CacheStorage> = {
+                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "(") {
+                    ::peg::RuleResult::Matched(__pos, __val) => {
+                        let __seq_res =
+                            __parse_cache_storage_name(__input, __state, __err_state, __pos);
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, s) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ")")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        ::peg::RuleResult::Matched(__pos, (|| s)())
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\")\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
+                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        }
+                    }
+                    ::peg::RuleResult::Failed => {
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Literal("\"(\""));
+                        ::peg::RuleResult::Failed
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_cache_storage_name<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<CacheStorage> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("cache_storage_name", __pos) {
+            let __rule_result: ::peg::RuleResult<CacheStorage> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "dense") {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                ::peg::RuleResult::Matched(__pos, (|| CacheStorage::Dense)())
+                            }
+                            ::peg::RuleResult::Failed => {
+                                __err_state.mark_failure(
+                                    __pos,
+                                    ::peg::error::Expected::Literal("\"dense\""),
+                                );
+                                ::peg::RuleResult::Failed
+                            }
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                                __input, __pos, "chunked",
+                            ) {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    ::peg::RuleResult::Matched(__pos, (|| CacheStorage::Chunked)())
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"chunked\""),
+                                    );
+                                    ::peg::RuleResult::Failed
+                                }
+                            };
+                            match __choice_res {
+                                ::peg::RuleResult::Matched(__pos, __value) => {
+                                    ::peg::RuleResult::Matched(__pos, __value)
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, "hash",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            ::peg::RuleResult::Matched(
+                                                __pos,
+                                                (|| CacheStorage::Hash)(),
+                                            )
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"hash\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_no_eof_flag<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<bool> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("no_eof_flag", __pos) {
+            let __rule_result: ::peg::RuleResult<bool> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "#") {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        match ::peg::ParseLiteral::parse_string_literal(
+                                            __input, __pos, "no_eof",
+                                        ) {
+                                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, "]",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| true)(),
+                                                        )
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"]\"",
+                                                            ),
+                                                        );
+                                                        ::peg::RuleResult::Failed
+                                                    }
+                                                }
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"no_eof\""),
+                                                );
+                                                ::peg::RuleResult::Failed
+                                            }
                                         }
-                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                                    };
-                                match __choice_res {
-                                    ::peg::RuleResult::Matched(__pos, __value) => {
-                                        ::peg::RuleResult::Matched(__pos, __value)
                                     }
                                     ::peg::RuleResult::Failed => {
-                                        match __parse_LIFETIME(__input, __state, __err_state, __pos)
-                                        {
-                                            ::peg::RuleResult::Matched(pos, _) => {
-                                                ::peg::RuleResult::Matched(pos, ())
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"[\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
+                            }
+                            ::peg::RuleResult::Failed => {
+                                __err_state
+                                    .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
+                                ::peg::RuleResult::Failed
+                            }
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            ::peg::RuleResult::Matched(__pos, (|| false)())
+                        }
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_recover_flag<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<bool> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("recover_flag", __pos) {
+            let __rule_result: ::peg::RuleResult<bool> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "#") {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        match ::peg::ParseLiteral::parse_string_literal(
+                                            __input, __pos, "recover",
+                                        ) {
+                                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, "]",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| true)(),
+                                                        )
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"]\"",
+                                                            ),
+                                                        );
+                                                        ::peg::RuleResult::Failed
+                                                    }
+                                                }
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"recover\""),
+                                                );
+                                                ::peg::RuleResult::Failed
+                                            }
+                                        }
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"[\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
+                            }
+                            ::peg::RuleResult::Failed => {
+                                __err_state
+                                    .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
+                                ::peg::RuleResult::Failed
+                            }
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            ::peg::RuleResult::Matched(__pos, (|| false)())
+                        }
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_context_flag<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<Option<Literal>> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("context_flag", __pos) {
+            let __rule_result: ::peg::RuleResult<Option<Literal>> = {
+                {
+                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "#",
+                    ) {
+                        ::peg::RuleResult::Matched(__pos, __val) => {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[") {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, "context",
+                                    ) {
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, "(",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    let __seq_res = __parse_LITERAL(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
+                                                        __pos,
+                                                    );
+                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , label) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "]") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Some (label) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"]\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\"(\""),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
                                             }
-                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"context\""),
+                                            );
+                                            ::peg::RuleResult::Failed
                                         }
                                     }
                                 }
-                            } {
-                                ::peg::RuleResult::Matched(__newpos, _) => {
-                                    ::peg::RuleResult::Matched(
-                                        __newpos,
-                                        ::peg::ParseSlice::parse_slice(
-                                            __input, str_start, __newpos,
-                                        ),
-                                    )
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"[\""),
+                                    );
+                                    ::peg::RuleResult::Failed
                                 }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                            }
-                        };
-                        match __step_res {
-                            ::peg::RuleResult::Matched(__newpos, __value) => {
-                                __repeat_pos = __newpos;
-                                __repeat_value.push(__value);
-                            }
-                            ::peg::RuleResult::Failed => {
-                                break;
                             }
                         }
+                        ::peg::RuleResult::Failed => {
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
+                            ::peg::RuleResult::Failed
+                        }
+                    };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, (|| None)()),
                     }
-                    if __repeat_value.len() >= 1 {
-                        ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
-                    } else {
-                        ::peg::RuleResult::Failed
-                    }
-                };
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, p) => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ">") {
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_lexical_flag<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<bool> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("lexical_flag", __pos) {
+            let __rule_result: ::peg::RuleResult<bool> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "#") {
                             ::peg::RuleResult::Matched(__pos, __val) => {
-                                ::peg::RuleResult::Matched(__pos, (|| p)())
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        match ::peg::ParseLiteral::parse_string_literal(
+                                            __input, __pos, "lexical",
+                                        ) {
+                                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, "]",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| true)(),
+                                                        )
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"]\"",
+                                                            ),
+                                                        );
+                                                        ::peg::RuleResult::Failed
+                                                    }
+                                                }
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"lexical\""),
+                                                );
+                                                ::peg::RuleResult::Failed
+                                            }
+                                        }
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"[\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
                             }
                             ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\">\"");
+                                __err_state
+                                    .mark_failure(__pos, ::peg::error::Expected::Literal("\"#\""));
                                 ::peg::RuleResult::Failed
                             }
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            ::peg::RuleResult::Matched(__pos, (|| false)())
                         }
                     }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                 }
-            }
-            ::peg::RuleResult::Failed => {
-                __err_state.mark_failure(__pos, "\"<\"");
-                ::peg::RuleResult::Failed
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
-    fn __parse_rule_param_ty<'input>(
+    fn __parse_rust_ty_params<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
-    ) -> ::peg::RuleResult<RuleParamTy> {
+    ) -> ::peg::RuleResult<Vec<TokenStream>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res =
-                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "rule") {
+        if __err_state.enter_rule("rust_ty_params", __pos) {
+            let __rule_result: ::peg::RuleResult<Vec<TokenStream>> = {
+                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<") {
                     ::peg::RuleResult::Matched(__pos, __val) => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                let __seq_res = {
-                                    let str_start = __pos;
-                                    match match __parse_rust_type(
-                                        __input,
-                                        __state,
-                                        __err_state,
-                                        __pos,
+                        let __seq_res = {
+                            let mut __repeat_pos = __pos;
+                            let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                            loop {
+                                let __pos = __repeat_pos;
+                                let __pos = if __repeat_value.is_empty() {
+                                    __pos
+                                } else {
+                                    let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, ",",
                                     ) {
-                                        ::peg::RuleResult::Matched(pos, _) => {
-                                            ::peg::RuleResult::Matched(pos, ())
+                                        ::peg::RuleResult::Matched(__pos, __val) => {
+                                            ::peg::RuleResult::Matched(__pos, __val)
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\",\""),
+                                            );
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    };
+                                    match __sep_res {
+                                        ::peg::RuleResult::Matched(__newpos, _) => __newpos,
+                                        ::peg::RuleResult::Failed => break,
+                                    }
+                                };
+                                let __step_res = {
+                                    let str_start = __pos;
+                                    match {
+                                        let __choice_res = match __parse_IDENT(
+                                            __input,
+                                            __state,
+                                            __err_state,
+                                            __pos,
+                                        ) {
+                                            ::peg::RuleResult::Matched(pos, _) => {
+                                                ::peg::RuleResult::Matched(pos, ())
+                                            }
+                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                        };
+                                        match __choice_res {
+                                            ::peg::RuleResult::Matched(__pos, __value) => {
+                                                ::peg::RuleResult::Matched(__pos, __value)
+                                            }
+                                            ::peg::RuleResult::Failed => match __parse_LIFETIME(
+                                                __input,
+                                                __state,
+                                                __err_state,
+                                                __pos,
+                                            ) {
+                                                ::peg::RuleResult::Matched(pos, _) => {
+                                                    ::peg::RuleResult::Matched(pos, ())
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            },
                                         }
-                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                     } {
                                         ::peg::RuleResult::Matched(__newpos, _) => {
                                             ::peg::RuleResult::Matched(
@@ -782,597 +1833,789 @@ pub mod peg {
                                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                     }
                                 };
-                                match __seq_res {
-                                    ::peg::RuleResult::Matched(__pos, r) => {
-                                        match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, ">",
-                                        ) {
-                                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                                ::peg::RuleResult::Matched(
-                                                    __pos,
-                                                    (|| RuleParamTy::Rule(r))(),
-                                                )
+                                match __step_res {
+                                    ::peg::RuleResult::Matched(__newpos, __value) => {
+                                        __repeat_pos = __newpos;
+                                        __repeat_value.push(__value);
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            if __repeat_value.len() >= 1 {
+                                ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                            } else {
+                                ::peg::RuleResult::Failed
+                            }
+                        };
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, p) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ">")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        ::peg::RuleResult::Matched(__pos, (|| p)())
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\">\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
+                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        }
+                    }
+                    ::peg::RuleResult::Failed => {
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Literal("\"<\""));
+                        ::peg::RuleResult::Failed
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_rule_param_ty<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<RuleParamTy> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("rule_param_ty", __pos) {
+            let __rule_result: ::peg::RuleResult<RuleParamTy> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "rule") {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        let __seq_res = {
+                                            let str_start = __pos;
+                                            match match __parse_rust_type(
+                                                __input,
+                                                __state,
+                                                __err_state,
+                                                __pos,
+                                            ) {
+                                                ::peg::RuleResult::Matched(pos, _) => {
+                                                    ::peg::RuleResult::Matched(pos, ())
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            } {
+                                                ::peg::RuleResult::Matched(__newpos, _) => {
+                                                    ::peg::RuleResult::Matched(
+                                                        __newpos,
+                                                        ::peg::ParseSlice::parse_slice(
+                                                            __input, str_start, __newpos,
+                                                        ),
+                                                    )
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Failed
+                                                }
                                             }
-                                            ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\">\"");
-                                                ::peg::RuleResult::Failed
+                                        };
+                                        match __seq_res {
+                                            ::peg::RuleResult::Matched(__pos, r) => {
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, ">",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            (|| RuleParamTy::Rule(r))(),
+                                                        )
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\">\"",
+                                                            ),
+                                                        );
+                                                        ::peg::RuleResult::Failed
+                                                    }
+                                                }
                                             }
+                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                         }
                                     }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"<\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
+                            }
+                            ::peg::RuleResult::Failed => {
+                                __err_state.mark_failure(
+                                    __pos,
+                                    ::peg::error::Expected::Literal("\"rule\""),
+                                );
+                                ::peg::RuleResult::Failed
+                            }
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            let __seq_res = {
+                                let str_start = __pos;
+                                match match __parse_rust_type(__input, __state, __err_state, __pos)
+                                {
+                                    ::peg::RuleResult::Matched(pos, _) => {
+                                        ::peg::RuleResult::Matched(pos, ())
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                } {
+                                    ::peg::RuleResult::Matched(__newpos, _) => {
+                                        ::peg::RuleResult::Matched(
+                                            __newpos,
+                                            ::peg::ParseSlice::parse_slice(
+                                                __input, str_start, __newpos,
+                                            ),
+                                        )
+                                    }
                                     ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                 }
+                            };
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, t) => {
+                                    ::peg::RuleResult::Matched(__pos, (|| RuleParamTy::Rust(t))())
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                             }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"<\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        }
-                    }
-                    ::peg::RuleResult::Failed => {
-                        __err_state.mark_failure(__pos, "\"rule\"");
-                        ::peg::RuleResult::Failed
-                    }
-                };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
-                    let __seq_res = {
-                        let str_start = __pos;
-                        match match __parse_rust_type(__input, __state, __err_state, __pos) {
-                            ::peg::RuleResult::Matched(pos, _) => {
-                                ::peg::RuleResult::Matched(pos, ())
-                            }
-                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                        } {
-                            ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
-                                __newpos,
-                                ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
-                            ),
-                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                        }
-                    };
-                    match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, t) => {
-                            ::peg::RuleResult::Matched(__pos, (|| RuleParamTy::Rust(t))())
                         }
-                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_rule_params<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Vec<RuleParam>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "(") {
-            ::peg::RuleResult::Matched(__pos, __val) => {
-                let __seq_res = {
-                    let mut __repeat_pos = __pos;
-                    let mut __repeat_value = vec![];
-                    loop {
-                        let __pos = __repeat_pos;
-                        let __pos = if __repeat_value.is_empty() {
-                            __pos
-                        } else {
-                            let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, ",",
-                            ) {
-                                ::peg::RuleResult::Matched(__pos, __val) => {
-                                    ::peg::RuleResult::Matched(__pos, __val)
-                                }
-                                ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\",\"");
-                                    ::peg::RuleResult::Failed
-                                }
-                            };
-                            match __sep_res {
-                                ::peg::RuleResult::Matched(__newpos, _) => __newpos,
-                                ::peg::RuleResult::Failed => break,
-                            }
-                        };
-                        let __step_res = {
-                            let __seq_res = __parse_IDENT(__input, __state, __err_state, __pos);
-                            match __seq_res {
-                                ::peg::RuleResult::Matched(__pos, name) => {
-                                    match ::peg::ParseLiteral::parse_string_literal(
-                                        __input, __pos, ":",
+        if __err_state.enter_rule("rule_params", __pos) {
+            let __rule_result: ::peg::RuleResult<Vec<RuleParam>> = {
+                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "(") {
+                    ::peg::RuleResult::Matched(__pos, __val) => {
+                        let __seq_res = {
+                            let mut __repeat_pos = __pos;
+                            let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                            loop {
+                                let __pos = __repeat_pos;
+                                let __pos = if __repeat_value.is_empty() {
+                                    __pos
+                                } else {
+                                    let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
+                                        __input, __pos, ",",
                                     ) {
                                         ::peg::RuleResult::Matched(__pos, __val) => {
-                                            let __seq_res = __parse_rule_param_ty(
-                                                __input,
-                                                __state,
-                                                __err_state,
+                                            ::peg::RuleResult::Matched(__pos, __val)
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __err_state.mark_failure(
                                                 __pos,
+                                                ::peg::error::Expected::Literal("\",\""),
                                             );
-                                            match __seq_res {
-                                                ::peg::RuleResult::Matched(__pos, ty) => {
-                                                    ::peg::RuleResult::Matched(
+                                            ::peg::RuleResult::Failed
+                                        }
+                                    };
+                                    match __sep_res {
+                                        ::peg::RuleResult::Matched(__newpos, _) => __newpos,
+                                        ::peg::RuleResult::Failed => break,
+                                    }
+                                };
+                                let __step_res = {
+                                    let __seq_res =
+                                        __parse_IDENT(__input, __state, __err_state, __pos);
+                                    match __seq_res {
+                                        ::peg::RuleResult::Matched(__pos, name) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, ":",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    let __seq_res = __parse_rule_param_ty(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
                                                         __pos,
-                                                        (|| RuleParam { name, ty })(),
-                                                    )
+                                                    );
+                                                    match __seq_res {
+                                                        ::peg::RuleResult::Matched(__pos, ty) => {
+                                                            ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                (|| RuleParam { name, ty })(),
+                                                            )
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
                                                 }
                                                 ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\":\""),
+                                                    );
                                                     ::peg::RuleResult::Failed
                                                 }
                                             }
                                         }
-                                        ::peg::RuleResult::Failed => {
-                                            __err_state.mark_failure(__pos, "\":\"");
-                                            ::peg::RuleResult::Failed
-                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    }
+                                };
+                                match __step_res {
+                                    ::peg::RuleResult::Matched(__newpos, __value) => {
+                                        __repeat_pos = __newpos;
+                                        __repeat_value.push(__value);
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        break;
                                     }
                                 }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                             }
+                            ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
                         };
-                        match __step_res {
-                            ::peg::RuleResult::Matched(__newpos, __value) => {
-                                __repeat_pos = __newpos;
-                                __repeat_value.push(__value);
-                            }
-                            ::peg::RuleResult::Failed => {
-                                break;
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, params) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ")")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        ::peg::RuleResult::Matched(__pos, (|| params)())
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\")\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
                             }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                         }
                     }
-                    ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
-                };
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, params) => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ")") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                ::peg::RuleResult::Matched(__pos, (|| params)())
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\")\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        }
+                    ::peg::RuleResult::Failed => {
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Literal("\"(\""));
+                        ::peg::RuleResult::Failed
                     }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                 }
-            }
-            ::peg::RuleResult::Failed => {
-                __err_state.mark_failure(__pos, "\"(\"");
-                ::peg::RuleResult::Failed
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_item<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Item> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = {
-                let __seq_res = __parse_rust_use(__input, __state, __err_state, __pos);
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, u) => {
-                        ::peg::RuleResult::Matched(__pos, (|| Item::Use(u))())
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                }
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
-                    let __seq_res = __parse_peg_rule(__input, __state, __err_state, __pos);
-                    match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, r) => {
-                            ::peg::RuleResult::Matched(__pos, (|| Item::Rule(r))())
+        if __err_state.enter_rule("item", __pos) {
+            let __rule_result: ::peg::RuleResult<Item> = {
+                {
+                    let __choice_res = {
+                        let __seq_res = __parse_rust_use(__input, __state, __err_state, __pos);
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, u) => {
+                                ::peg::RuleResult::Matched(__pos, (|| Item::Use(u))())
+                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        }
+                    };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            let __seq_res = __parse_peg_rule(__input, __state, __err_state, __pos);
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, r) => {
+                                    ::peg::RuleResult::Matched(__pos, (|| Item::Rule(r))())
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            }
                         }
-                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_rust_doc_comment<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Option<TokenStream>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match {
-            let str_start = __pos;
-            match {
-                let mut __repeat_pos = __pos;
-                loop {
-                    let __pos = __repeat_pos;
-                    let __step_res = match ::peg::ParseLiteral::parse_string_literal(
-                        __input, __pos, "#",
-                    ) {
-                        ::peg::RuleResult::Matched(__pos, __val) => {
-                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "[") {
+        if __err_state.enter_rule("rust_doc_comment", __pos) {
+            let __rule_result: ::peg::RuleResult<Option<TokenStream>> = {
+                match {
+                    let str_start = __pos;
+                    match {
+                        let mut __repeat_pos = __pos;
+                        loop {
+                            let __pos = __repeat_pos;
+                            let __step_res = match ::peg::ParseLiteral::parse_string_literal(
+                                __input, __pos, "#",
+                            ) {
                                 ::peg::RuleResult::Matched(__pos, __val) => {
                                     match ::peg::ParseLiteral::parse_string_literal(
-                                        __input, __pos, "doc",
+                                        __input, __pos, "[",
                                     ) {
                                         ::peg::RuleResult::Matched(__pos, __val) => {
                                             match ::peg::ParseLiteral::parse_string_literal(
-                                                __input, __pos, "=",
+                                                __input, __pos, "doc",
                                             ) {
                                                 ::peg::RuleResult::Matched(__pos, __val) => {
-                                                    let __seq_res = match __parse_LITERAL(
-                                                        __input,
-                                                        __state,
-                                                        __err_state,
-                                                        __pos,
+                                                    match ::peg::ParseLiteral::parse_string_literal(
+                                                        __input, __pos, "=",
                                                     ) {
-                                                        ::peg::RuleResult::Matched(pos, _) => {
-                                                            ::peg::RuleResult::Matched(pos, ())
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            __val,
+                                                        ) => {
+                                                            let __seq_res = match __parse_LITERAL(
+                                                                __input,
+                                                                __state,
+                                                                __err_state,
+                                                                __pos,
+                                                            ) {
+                                                                ::peg::RuleResult::Matched(
+                                                                    pos,
+                                                                    _,
+                                                                ) => ::peg::RuleResult::Matched(
+                                                                    pos,
+                                                                    (),
+                                                                ),
+                                                                ::peg::RuleResult::Failed => {
+                                                                    ::peg::RuleResult::Failed
+                                                                }
+                                                            };
+                                                            match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "]") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"]\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                         }
                                                         ::peg::RuleResult::Failed => {
+                                                            __err_state.mark_failure(
+                                                                __pos,
+                                                                ::peg::error::Expected::Literal(
+                                                                    "\"=\"",
+                                                                ),
+                                                            );
                                                             ::peg::RuleResult::Failed
                                                         }
-                                                    };
-                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "]") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"]\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                    }
                                                 }
                                                 ::peg::RuleResult::Failed => {
-                                                    __err_state.mark_failure(__pos, "\"=\"");
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\"doc\""),
+                                                    );
                                                     ::peg::RuleResult::Failed
                                                 }
                                             }
                                         }
                                         ::peg::RuleResult::Failed => {
-                                            __err_state.mark_failure(__pos, "\"doc\"");
+                                            __err_state.mark_failure(
+                                                __pos,
+                                                ::peg::error::Expected::Literal("\"[\""),
+                                            );
                                             ::peg::RuleResult::Failed
                                         }
                                     }
                                 }
                                 ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\"[\"");
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"#\""),
+                                    );
                                     ::peg::RuleResult::Failed
                                 }
+                            };
+                            match __step_res {
+                                ::peg::RuleResult::Matched(__newpos, __value) => {
+                                    __repeat_pos = __newpos;
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    break;
+                                }
                             }
                         }
-                        ::peg::RuleResult::Failed => {
-                            __err_state.mark_failure(__pos, "\"#\"");
-                            ::peg::RuleResult::Failed
-                        }
-                    };
-                    match __step_res {
-                        ::peg::RuleResult::Matched(__newpos, __value) => {
-                            __repeat_pos = __newpos;
-                        }
-                        ::peg::RuleResult::Failed => {
-                            break;
-                        }
+                        ::peg::RuleResult::Matched(__repeat_pos, ())
+                    } {
+                        ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
+                            __newpos,
+                            ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
+                        ),
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                    }
+                } {
+                    ::peg::RuleResult::Matched(__newpos, __value) => {
+                        ::peg::RuleResult::Matched(__newpos, Some(__value))
                     }
+                    ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, None),
                 }
-                ::peg::RuleResult::Matched(__repeat_pos, ())
-            } {
-                ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
-                    __newpos,
-                    ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
-                ),
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
-        } {
-            ::peg::RuleResult::Matched(__newpos, __value) => {
-                ::peg::RuleResult::Matched(__newpos, Some(__value))
-            }
-            ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, None),
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_rust_visibility<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Option<TokenStream>> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match {
-            let str_start = __pos;
-            match {
-                let __choice_res =
-                    match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "pub") {
-                        ::peg::RuleResult::Matched(__pos, __val) => {
-                            let __seq_res = match match __parse_PAREN_GROUP(
-                                __input,
-                                __state,
-                                __err_state,
-                                __pos,
-                            ) {
-                                ::peg::RuleResult::Matched(pos, _) => {
-                                    ::peg::RuleResult::Matched(pos, ())
-                                }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                            } {
-                                ::peg::RuleResult::Matched(__newpos, _) => {
-                                    ::peg::RuleResult::Matched(__newpos, ())
-                                }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, ()),
-                            };
-                            match __seq_res {
-                                ::peg::RuleResult::Matched(__pos, _) => {
-                                    ::peg::RuleResult::Matched(__pos, ())
-                                }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                            }
-                        }
-                        ::peg::RuleResult::Failed => {
-                            __err_state.mark_failure(__pos, "\"pub\"");
-                            ::peg::RuleResult::Failed
-                        }
-                    };
-                match __choice_res {
-                    ::peg::RuleResult::Matched(__pos, __value) => {
-                        ::peg::RuleResult::Matched(__pos, __value)
-                    }
-                    ::peg::RuleResult::Failed => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "crate") {
+        if __err_state.enter_rule("rust_visibility", __pos) {
+            let __rule_result: ::peg::RuleResult<Option<TokenStream>> = {
+                match {
+                    let str_start = __pos;
+                    match {
+                        let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                            __input, __pos, "pub",
+                        ) {
                             ::peg::RuleResult::Matched(__pos, __val) => {
-                                ::peg::RuleResult::Matched(__pos, __val)
+                                let __seq_res = match match __parse_PAREN_GROUP(
+                                    __input,
+                                    __state,
+                                    __err_state,
+                                    __pos,
+                                ) {
+                                    ::peg::RuleResult::Matched(pos, _) => {
+                                        ::peg::RuleResult::Matched(pos, ())
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                } {
+                                    ::peg::RuleResult::Matched(__newpos, _) => {
+                                        ::peg::RuleResult::Matched(__newpos, ())
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        ::peg::RuleResult::Matched(__pos, ())
+                                    }
+                                };
+                                match __seq_res {
+                                    ::peg::RuleResult::Matched(__pos, _) => {
+                                        ::peg::RuleResult::Matched(__pos, ())
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                }
                             }
                             ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"crate\"");
+                                __err_state.mark_failure(
+                                    __pos,
+                                    ::peg::error::Expected::Literal("\"pub\""),
+                                );
                                 ::peg::RuleResult::Failed
                             }
+                        };
+                        match __choice_res {
+                            ::peg::RuleResult::Matched(__pos, __value) => {
+                                ::peg::RuleResult::Matched(__pos, __value)
+                            }
+                            ::peg::RuleResult::Failed => {
+                                match ::peg::ParseLiteral::parse_string_literal(
+                                    __input, __pos, "crate",
+                                ) {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        ::peg::RuleResult::Matched(__pos, __val)
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"crate\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
+                            }
                         }
+                    } {
+                        ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
+                            __newpos,
+                            ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
+                        ),
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                    }
+                } {
+                    ::peg::RuleResult::Matched(__newpos, __value) => {
+                        ::peg::RuleResult::Matched(__newpos, Some(__value))
                     }
+                    ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, None),
                 }
-            } {
-                ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
-                    __newpos,
-                    ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
-                ),
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
-        } {
-            ::peg::RuleResult::Matched(__newpos, __value) => {
-                ::peg::RuleResult::Matched(__newpos, Some(__value))
-            }
-            ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, None),
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_rust_use<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<TokenStream> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = {
-                let str_start = __pos;
-                match match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "use") {
-                    ::peg::RuleResult::Matched(__pos, __val) => {
-                        let __seq_res =
-                            match __parse_rust_path(__input, __state, __err_state, __pos) {
-                                ::peg::RuleResult::Matched(pos, _) => {
-                                    ::peg::RuleResult::Matched(pos, ())
-                                }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                            };
-                        match __seq_res {
-                            ::peg::RuleResult::Matched(__pos, _) => {
-                                let __seq_res = {
-                                    let __choice_res =
-                                        match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, "::",
-                                        ) {
-                                            ::peg::RuleResult::Matched(__pos, __val) => {
+        if __err_state.enter_rule("rust_use", __pos) {
+            let __rule_result: ::peg::RuleResult<TokenStream> = {
+                {
+                    let __seq_res = {
+                        let str_start = __pos;
+                        match match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "use")
+                        {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                let __seq_res =
+                                    match __parse_rust_path(__input, __state, __err_state, __pos) {
+                                        ::peg::RuleResult::Matched(pos, _) => {
+                                            ::peg::RuleResult::Matched(pos, ())
+                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    };
+                                match __seq_res {
+                                    ::peg::RuleResult::Matched(__pos, _) => {
+                                        let __seq_res = {
+                                            let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "::") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "*") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"*\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"::\"")) ; :: peg :: RuleResult :: Failed } } ;
+                                            match __choice_res {
+                                                ::peg::RuleResult::Matched(__pos, __value) => {
+                                                    ::peg::RuleResult::Matched(__pos, __value)
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "::") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "{") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = :: peg :: __alloc :: vec :: Vec :: new () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\",\"")) ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => { break } , } } ; let __step_res = { let __seq_res = match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { { let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "as") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"as\"")) ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } if __repeat_value . len () >= 1 { :: peg :: RuleResult :: Matched (__repeat_pos , ()) } else { :: peg :: RuleResult :: Failed } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"}\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"{\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"::\"")) ; :: peg :: RuleResult :: Failed } } ;
+                                                    match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "as") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"as\"")) ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } }
+                                                }
+                                            }
+                                        };
+                                        match __seq_res {
+                                            ::peg::RuleResult::Matched(__pos, _) => {
                                                 match ::peg::ParseLiteral::parse_string_literal(
-                                                    __input, __pos, "*",
+                                                    __input, __pos, ";",
                                                 ) {
                                                     ::peg::RuleResult::Matched(__pos, __val) => {
                                                         ::peg::RuleResult::Matched(__pos, ())
                                                     }
                                                     ::peg::RuleResult::Failed => {
-                                                        __err_state.mark_failure(__pos, "\"*\"");
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\";\"",
+                                                            ),
+                                                        );
                                                         ::peg::RuleResult::Failed
                                                     }
                                                 }
                                             }
-                                            ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\"::\"");
-                                                ::peg::RuleResult::Failed
-                                            }
-                                        };
-                                    match __choice_res {
-                                        ::peg::RuleResult::Matched(__pos, __value) => {
-                                            ::peg::RuleResult::Matched(__pos, __value)
-                                        }
-                                        ::peg::RuleResult::Failed => {
-                                            let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "::") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "{") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = vec ! () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\",\"") ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => break , } } ; let __step_res = { let __seq_res = match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { { let __seq_res = match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "as") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"as\"") ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } if __repeat_value . len () >= 1 { :: peg :: RuleResult :: Matched (__repeat_pos , ()) } else { :: peg :: RuleResult :: Failed } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"}\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"{\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"::\"") ; :: peg :: RuleResult :: Failed } } ;
-                                            match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "as") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"as\"") ; :: peg :: RuleResult :: Failed } } { :: peg :: RuleResult :: Matched (__newpos , _) => { :: peg :: RuleResult :: Matched (__newpos , ()) } , :: peg :: RuleResult :: Failed => { :: peg :: RuleResult :: Matched (__pos , ()) } , } }
-                                        }
-                                    }
-                                };
-                                match __seq_res {
-                                    ::peg::RuleResult::Matched(__pos, _) => {
-                                        match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, ";",
-                                        ) {
-                                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                                ::peg::RuleResult::Matched(__pos, ())
-                                            }
-                                            ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\";\"");
-                                                ::peg::RuleResult::Failed
-                                            }
+                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                         }
                                     }
                                     ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                 }
                             }
+                            ::peg::RuleResult::Failed => {
+                                __err_state.mark_failure(
+                                    __pos,
+                                    ::peg::error::Expected::Literal("\"use\""),
+                                );
+                                ::peg::RuleResult::Failed
+                            }
+                        } {
+                            ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
+                                __newpos,
+                                ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
+                            ),
                             ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                         }
+                    };
+                    match __seq_res {
+                        ::peg::RuleResult::Matched(__pos, v) => {
+                            ::peg::RuleResult::Matched(__pos, (|| v.to_owned())())
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
-                    ::peg::RuleResult::Failed => {
-                        __err_state.mark_failure(__pos, "\"use\"");
-                        ::peg::RuleResult::Failed
-                    }
-                } {
-                    ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
-                        __newpos,
-                        ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
-                    ),
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                 }
             };
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, v) => {
-                    ::peg::RuleResult::Matched(__pos, (|| v.to_owned())())
-                }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_rust_path<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<()> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res =
-                match match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "crate") {
-                    ::peg::RuleResult::Matched(__pos, __val) => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "::") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                ::peg::RuleResult::Matched(__pos, ())
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"::\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        }
-                    }
-                    ::peg::RuleResult::Failed => {
-                        __err_state.mark_failure(__pos, "\"crate\"");
-                        ::peg::RuleResult::Failed
-                    }
-                } {
-                    ::peg::RuleResult::Matched(__newpos, _) => {
-                        ::peg::RuleResult::Matched(__newpos, ())
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, ()),
-                };
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, _) => {
-                    let __seq_res = {
-                        let mut __repeat_pos = __pos;
-                        let mut __repeat_value = vec![];
-                        loop {
-                            let __pos = __repeat_pos;
-                            let __pos = if __repeat_value.is_empty() {
-                                __pos
-                            } else {
-                                let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
-                                    __input, __pos, "::",
-                                ) {
-                                    ::peg::RuleResult::Matched(__pos, __val) => {
-                                        ::peg::RuleResult::Matched(__pos, __val)
-                                    }
-                                    ::peg::RuleResult::Failed => {
-                                        __err_state.mark_failure(__pos, "\"::\"");
-                                        ::peg::RuleResult::Failed
-                                    }
-                                };
-                                match __sep_res {
-                                    ::peg::RuleResult::Matched(__newpos, _) => __newpos,
-                                    ::peg::RuleResult::Failed => break,
-                                }
-                            };
-                            let __step_res =
-                                match __parse_IDENT(__input, __state, __err_state, __pos) {
-                                    ::peg::RuleResult::Matched(pos, _) => {
-                                        ::peg::RuleResult::Matched(pos, ())
-                                    }
-                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                                };
-                            match __step_res {
-                                ::peg::RuleResult::Matched(__newpos, __value) => {
-                                    __repeat_pos = __newpos;
-                                    __repeat_value.push(__value);
+        if __err_state.enter_rule("rust_path", __pos) {
+            let __rule_result: ::peg::RuleResult<()> = {
+                {
+                    let __seq_res = match match ::peg::ParseLiteral::parse_string_literal(
+                        __input, __pos, "crate",
+                    ) {
+                        ::peg::RuleResult::Matched(__pos, __val) => {
+                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "::") {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    ::peg::RuleResult::Matched(__pos, ())
                                 }
                                 ::peg::RuleResult::Failed => {
-                                    break;
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"::\""),
+                                    );
+                                    ::peg::RuleResult::Failed
                                 }
                             }
                         }
-                        if __repeat_value.len() >= 1 {
-                            ::peg::RuleResult::Matched(__repeat_pos, ())
-                        } else {
+                        ::peg::RuleResult::Failed => {
+                            __err_state
+                                .mark_failure(__pos, ::peg::error::Expected::Literal("\"crate\""));
                             ::peg::RuleResult::Failed
                         }
+                    } {
+                        ::peg::RuleResult::Matched(__newpos, _) => {
+                            ::peg::RuleResult::Matched(__newpos, ())
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, ()),
                     };
                     match __seq_res {
                         ::peg::RuleResult::Matched(__pos, _) => {
-                            ::peg::RuleResult::Matched(__pos, ())
+                            let __seq_res = {
+                                let mut __repeat_pos = __pos;
+                                let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                                loop {
+                                    let __pos = __repeat_pos;
+                                    let __pos = if __repeat_value.is_empty() {
+                                        __pos
+                                    } else {
+                                        let __sep_res =
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, "::",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    ::peg::RuleResult::Matched(__pos, __val)
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\"::\""),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            };
+                                        match __sep_res {
+                                            ::peg::RuleResult::Matched(__newpos, _) => __newpos,
+                                            ::peg::RuleResult::Failed => break,
+                                        }
+                                    };
+                                    let __step_res =
+                                        match __parse_IDENT(__input, __state, __err_state, __pos) {
+                                            ::peg::RuleResult::Matched(pos, _) => {
+                                                ::peg::RuleResult::Matched(pos, ())
+                                            }
+                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                        };
+                                    match __step_res {
+                                        ::peg::RuleResult::Matched(__newpos, __value) => {
+                                            __repeat_pos = __newpos;
+                                            __repeat_value.push(__value);
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            break;
+                                        }
+                                    }
+                                }
+                                if __repeat_value.len() >= 1 {
+                                    ::peg::RuleResult::Matched(__repeat_pos, ())
+                                } else {
+                                    ::peg::RuleResult::Failed
+                                }
+                            };
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, _) => {
+                                    ::peg::RuleResult::Matched(__pos, ())
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            }
                         }
                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_rust_type<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<()> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = match __parse_BRACKET_GROUP(__input, __state, __err_state, __pos) {
-                ::peg::RuleResult::Matched(pos, _) => ::peg::RuleResult::Matched(pos, ()),
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
+        if __err_state.enter_rule("rust_type", __pos) {
+            let __rule_result: ::peg::RuleResult<()> = {
+                {
                     let __choice_res =
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "&") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                let __seq_res =
-                                    match match ::peg::ParseLiteral::parse_string_literal(
-                                        __input, __pos, "mut",
-                                    ) {
-                                        ::peg::RuleResult::Matched(__pos, __val) => {
-                                            ::peg::RuleResult::Matched(__pos, __val)
-                                        }
-                                        ::peg::RuleResult::Failed => {
-                                            __err_state.mark_failure(__pos, "\"mut\"");
-                                            ::peg::RuleResult::Failed
-                                        }
-                                    } {
-                                        ::peg::RuleResult::Matched(__newpos, _) => {
-                                            ::peg::RuleResult::Matched(__newpos, ())
-                                        }
-                                        ::peg::RuleResult::Failed => {
-                                            ::peg::RuleResult::Matched(__pos, ())
-                                        }
-                                    };
-                                match __seq_res {
-                                    ::peg::RuleResult::Matched(__pos, _) => {
-                                        let __seq_res = match match __parse_LIFETIME(
-                                            __input,
-                                            __state,
-                                            __err_state,
-                                            __pos,
+                        match __parse_BRACKET_GROUP(__input, __state, __err_state, __pos) {
+                            ::peg::RuleResult::Matched(pos, _) => {
+                                ::peg::RuleResult::Matched(pos, ())
+                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
+                        }
+                        ::peg::RuleResult::Failed => {
+                            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                                __input, __pos, "&",
+                            ) {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    let __seq_res =
+                                        match match ::peg::ParseLiteral::parse_string_literal(
+                                            __input, __pos, "mut",
                                         ) {
-                                            ::peg::RuleResult::Matched(pos, _) => {
-                                                ::peg::RuleResult::Matched(pos, ())
+                                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                                ::peg::RuleResult::Matched(__pos, __val)
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"mut\""),
+                                                );
+                                                ::peg::RuleResult::Failed
                                             }
-                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                         } {
                                             ::peg::RuleResult::Matched(__newpos, _) => {
                                                 ::peg::RuleResult::Matched(__newpos, ())
@@ -1381,70 +2624,65 @@ pub mod peg {
                                                 ::peg::RuleResult::Matched(__pos, ())
                                             }
                                         };
-                                        match __seq_res {
-                                            ::peg::RuleResult::Matched(__pos, _) => {
-                                                let __seq_res = match __parse_rust_type(
-                                                    __input,
-                                                    __state,
-                                                    __err_state,
-                                                    __pos,
-                                                ) {
-                                                    ::peg::RuleResult::Matched(pos, _) => {
-                                                        ::peg::RuleResult::Matched(pos, ())
-                                                    }
-                                                    ::peg::RuleResult::Failed => {
-                                                        ::peg::RuleResult::Failed
-                                                    }
-                                                };
-                                                match __seq_res {
-                                                    ::peg::RuleResult::Matched(__pos, _) => {
-                                                        ::peg::RuleResult::Matched(__pos, ())
-                                                    }
-                                                    ::peg::RuleResult::Failed => {
-                                                        ::peg::RuleResult::Failed
+                                    match __seq_res {
+                                        ::peg::RuleResult::Matched(__pos, _) => {
+                                            let __seq_res = match match __parse_LIFETIME(
+                                                __input,
+                                                __state,
+                                                __err_state,
+                                                __pos,
+                                            ) {
+                                                ::peg::RuleResult::Matched(pos, _) => {
+                                                    ::peg::RuleResult::Matched(pos, ())
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            } {
+                                                ::peg::RuleResult::Matched(__newpos, _) => {
+                                                    ::peg::RuleResult::Matched(__newpos, ())
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Matched(__pos, ())
+                                                }
+                                            };
+                                            match __seq_res {
+                                                ::peg::RuleResult::Matched(__pos, _) => {
+                                                    let __seq_res = match __parse_rust_type(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
+                                                        __pos,
+                                                    ) {
+                                                        ::peg::RuleResult::Matched(pos, _) => {
+                                                            ::peg::RuleResult::Matched(pos, ())
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    };
+                                                    match __seq_res {
+                                                        ::peg::RuleResult::Matched(__pos, _) => {
+                                                            ::peg::RuleResult::Matched(__pos, ())
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            ::peg::RuleResult::Failed
+                                                        }
                                                     }
                                                 }
+                                                ::peg::RuleResult::Failed => {
+                                                    ::peg::RuleResult::Failed
+                                                }
                                             }
-                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                                        }
-                                    }
-                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                                }
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"&\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        };
-                    match __choice_res {
-                        ::peg::RuleResult::Matched(__pos, __value) => {
-                            ::peg::RuleResult::Matched(__pos, __value)
-                        }
-                        ::peg::RuleResult::Failed => {
-                            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, "dyn",
-                            ) {
-                                ::peg::RuleResult::Matched(__pos, __val) => {
-                                    let __seq_res = match __parse_rust_type(
-                                        __input,
-                                        __state,
-                                        __err_state,
-                                        __pos,
-                                    ) {
-                                        ::peg::RuleResult::Matched(pos, _) => {
-                                            ::peg::RuleResult::Matched(pos, ())
-                                        }
-                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                                    };
-                                    match __seq_res {
-                                        ::peg::RuleResult::Matched(__pos, _) => {
-                                            ::peg::RuleResult::Matched(__pos, ())
                                         }
                                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                     }
                                 }
                                 ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\"dyn\"");
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"&\""),
+                                    );
                                     ::peg::RuleResult::Failed
                                 }
                             };
@@ -1455,7 +2693,7 @@ pub mod peg {
                                 ::peg::RuleResult::Failed => {
                                     let __choice_res =
                                         match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, "impl",
+                                            __input, __pos, "dyn",
                                         ) {
                                             ::peg::RuleResult::Matched(__pos, __val) => {
                                                 let __seq_res = match __parse_rust_type(
@@ -1481,7 +2719,10 @@ pub mod peg {
                                                 }
                                             }
                                             ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\"impl\"");
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"dyn\""),
+                                                );
                                                 ::peg::RuleResult::Failed
                                             }
                                         };
@@ -1490,22 +2731,47 @@ pub mod peg {
                                             ::peg::RuleResult::Matched(__pos, __value)
                                         }
                                         ::peg::RuleResult::Failed => {
-                                            let __choice_res = {
-                                                let __seq_res = match __parse_IDENT(
-                                                    __input,
-                                                    __state,
-                                                    __err_state,
-                                                    __pos,
+                                            let __choice_res =
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, "impl",
                                                 ) {
-                                                    ::peg::RuleResult::Matched(pos, _) => {
-                                                        ::peg::RuleResult::Matched(pos, ())
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        let __seq_res = match __parse_rust_type(
+                                                            __input,
+                                                            __state,
+                                                            __err_state,
+                                                            __pos,
+                                                        ) {
+                                                            ::peg::RuleResult::Matched(pos, _) => {
+                                                                ::peg::RuleResult::Matched(pos, ())
+                                                            }
+                                                            ::peg::RuleResult::Failed => {
+                                                                ::peg::RuleResult::Failed
+                                                            }
+                                                        };
+                                                        match __seq_res {
+                                                            ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                _,
+                                                            ) => ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                (),
+                                                            ),
+                                                            ::peg::RuleResult::Failed => {
+                                                                ::peg::RuleResult::Failed
+                                                            }
+                                                        }
                                                     }
                                                     ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"impl\"",
+                                                            ),
+                                                        );
                                                         ::peg::RuleResult::Failed
                                                     }
                                                 };
-                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "<") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = vec ! () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\",\"") ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => break , } } ; let __step_res = { let __choice_res = match __parse_LIFETIME (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } if __repeat_value . len () >= 1 { :: peg :: RuleResult :: Matched (__repeat_pos , ()) } else { :: peg :: RuleResult :: Failed } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ">") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\">\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"<\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
-                                            };
                                             match __choice_res {
                                                 ::peg::RuleResult::Matched(__pos, __value) => {
                                                     ::peg::RuleResult::Matched(__pos, __value)
@@ -1525,7 +2791,7 @@ pub mod peg {
                                                                 ::peg::RuleResult::Failed
                                                             }
                                                         };
-                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "::") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"::\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "<") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = :: peg :: __alloc :: vec :: Vec :: new () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\",\"")) ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => { break } , } } ; let __step_res = { let __choice_res = match __parse_LIFETIME (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } if __repeat_value . len () >= 1 { :: peg :: RuleResult :: Matched (__repeat_pos , ()) } else { :: peg :: RuleResult :: Failed } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ">") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\">\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"<\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                     };
                                                     match __choice_res {
                                                         ::peg::RuleResult::Matched(
@@ -1535,8 +2801,40 @@ pub mod peg {
                                                             __pos, __value,
                                                         ),
                                                         ::peg::RuleResult::Failed => {
-                                                            let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = vec ! () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\",\"") ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => break , } } ; let __step_res = match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } :: peg :: RuleResult :: Matched (__repeat_pos , ()) } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\")\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"(\"") ; :: peg :: RuleResult :: Failed } } ;
-                                                            match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } }
+                                                            let __choice_res = {
+                                                                let __seq_res = match __parse_IDENT(
+                                                                    __input,
+                                                                    __state,
+                                                                    __err_state,
+                                                                    __pos,
+                                                                ) {
+                                                                    ::peg::RuleResult::Matched(
+                                                                        pos,
+                                                                        _,
+                                                                    ) => {
+                                                                        ::peg::RuleResult::Matched(
+                                                                            pos,
+                                                                            (),
+                                                                        )
+                                                                    }
+                                                                    ::peg::RuleResult::Failed => {
+                                                                        ::peg::RuleResult::Failed
+                                                                    }
+                                                                };
+                                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "::") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"::\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                            };
+                                                            match __choice_res {
+                                                                ::peg::RuleResult::Matched(
+                                                                    __pos,
+                                                                    __value,
+                                                                ) => ::peg::RuleResult::Matched(
+                                                                    __pos, __value,
+                                                                ),
+                                                                ::peg::RuleResult::Failed => {
+                                                                    let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = :: peg :: __alloc :: vec :: Vec :: new () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\",\"")) ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => { break } , } } ; let __step_res = match __parse_rust_type (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } :: peg :: RuleResult :: Matched (__repeat_pos , ()) } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } ;
+                                                                    match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match __parse_IDENT (__input , __state , __err_state , __pos) { :: peg :: RuleResult :: Matched (pos , _) => :: peg :: RuleResult :: Matched (pos , ()) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } }
+                                                                }
+                                                            }
                                                         }
                                                     }
                                                 }
@@ -1548,142 +2846,177 @@ pub mod peg {
                         }
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_expression<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<SpannedExpr> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        __parse_choice(__input, __state, __err_state, __pos)
+        if __err_state.enter_rule("expression", __pos) {
+            let __rule_result: ::peg::RuleResult<SpannedExpr> =
+                { __parse_choice(__input, __state, __err_state, __pos) };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
     }
     fn __parse_choice<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<SpannedExpr> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, sp) => {
-                    let __seq_res = {
-                        let mut __repeat_pos = __pos;
-                        let mut __repeat_value = vec![];
-                        loop {
-                            let __pos = __repeat_pos;
-                            let __pos = if __repeat_value.is_empty() {
-                                __pos
-                            } else {
-                                let __sep_res = match ::peg::ParseLiteral::parse_string_literal(
-                                    __input, __pos, "/",
-                                ) {
-                                    ::peg::RuleResult::Matched(__pos, __val) => {
-                                        ::peg::RuleResult::Matched(__pos, __val)
-                                    }
-                                    ::peg::RuleResult::Failed => {
-                                        __err_state.mark_failure(__pos, "\"/\"");
-                                        ::peg::RuleResult::Failed
+        if __err_state.enter_rule("choice", __pos) {
+            let __rule_result: ::peg::RuleResult<SpannedExpr> = {
+                {
+                    let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
+                    match __seq_res {
+                        ::peg::RuleResult::Matched(__pos, sp) => {
+                            let __seq_res = {
+                                let mut __repeat_pos = __pos;
+                                let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                                loop {
+                                    let __pos = __repeat_pos;
+                                    let __pos = if __repeat_value.is_empty() {
+                                        __pos
+                                    } else {
+                                        let __sep_res =
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, "/",
+                                            ) {
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    ::peg::RuleResult::Matched(__pos, __val)
+                                                }
+                                                ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\"/\""),
+                                                    );
+                                                    ::peg::RuleResult::Failed
+                                                }
+                                            };
+                                        match __sep_res {
+                                            ::peg::RuleResult::Matched(__newpos, _) => __newpos,
+                                            ::peg::RuleResult::Failed => break,
+                                        }
+                                    };
+                                    let __step_res =
+                                        __parse_sequence(__input, __state, __err_state, __pos);
+                                    match __step_res {
+                                        ::peg::RuleResult::Matched(__newpos, __value) => {
+                                            __repeat_pos = __newpos;
+                                            __repeat_value.push(__value);
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            break;
+                                        }
                                     }
-                                };
-                                match __sep_res {
-                                    ::peg::RuleResult::Matched(__newpos, _) => __newpos,
-                                    ::peg::RuleResult::Failed => break,
-                                }
-                            };
-                            let __step_res = __parse_sequence(__input, __state, __err_state, __pos);
-                            match __step_res {
-                                ::peg::RuleResult::Matched(__newpos, __value) => {
-                                    __repeat_pos = __newpos;
-                                    __repeat_value.push(__value);
                                 }
-                                ::peg::RuleResult::Failed => {
-                                    break;
+                                if __repeat_value.len() >= 1 {
+                                    ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                                } else {
+                                    ::peg::RuleResult::Failed
                                 }
+                            };
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, s) => ::peg::RuleResult::Matched(
+                                    __pos,
+                                    (|| {
+                                        if s.len() == 1 {
+                                            s.into_iter().next().unwrap()
+                                        } else {
+                                            ChoiceExpr(s).at(sp)
+                                        }
+                                    })(),
+                                ),
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                             }
                         }
-                        if __repeat_value.len() >= 1 {
-                            ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
-                        } else {
-                            ::peg::RuleResult::Failed
-                        }
-                    };
-                    match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, s) => ::peg::RuleResult::Matched(
-                            __pos,
-                            (|| {
-                                if s.len() == 1 {
-                                    s.into_iter().next().unwrap()
-                                } else {
-                                    ChoiceExpr(s).at(sp)
-                                }
-                            })(),
-                        ),
                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_sequence<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<SpannedExpr> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, sp) => {
-                    let __seq_res = {
-                        let mut __repeat_pos = __pos;
-                        let mut __repeat_value = vec![];
-                        loop {
-                            let __pos = __repeat_pos;
-                            let __step_res = __parse_labeled(__input, __state, __err_state, __pos);
-                            match __step_res {
-                                ::peg::RuleResult::Matched(__newpos, __value) => {
-                                    __repeat_pos = __newpos;
-                                    __repeat_value.push(__value);
-                                }
-                                ::peg::RuleResult::Failed => {
-                                    break;
-                                }
-                            }
-                        }
-                        ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
-                    };
+        if __err_state.enter_rule("sequence", __pos) {
+            let __rule_result: ::peg::RuleResult<SpannedExpr> = {
+                {
+                    let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
                     match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, elements) => {
-                            let __seq_res =
-                                match __parse_BRACE_GROUP(__input, __state, __err_state, __pos) {
-                                    ::peg::RuleResult::Matched(__newpos, __value) => {
-                                        ::peg::RuleResult::Matched(__newpos, Some(__value))
-                                    }
-                                    ::peg::RuleResult::Failed => {
-                                        ::peg::RuleResult::Matched(__pos, None)
+                        ::peg::RuleResult::Matched(__pos, sp) => {
+                            let __seq_res = {
+                                let mut __repeat_pos = __pos;
+                                let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                                loop {
+                                    let __pos = __repeat_pos;
+                                    let __step_res =
+                                        __parse_labeled(__input, __state, __err_state, __pos);
+                                    match __step_res {
+                                        ::peg::RuleResult::Matched(__newpos, __value) => {
+                                            __repeat_pos = __newpos;
+                                            __repeat_value.push(__value);
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            break;
+                                        }
                                     }
-                                };
+                                }
+                                ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                            };
                             match __seq_res {
-                                ::peg::RuleResult::Matched(__pos, code) => {
-                                    ::peg::RuleResult::Matched(
+                                ::peg::RuleResult::Matched(__pos, elements) => {
+                                    let __seq_res = match __parse_BRACE_GROUP(
+                                        __input,
+                                        __state,
+                                        __err_state,
                                         __pos,
-                                        (|| {
-                                            if let Some(code) = code {
-                                                ActionExpr(elements, Some(code)).at(sp)
-                                            } else if elements.len() != 1 {
-                                                ActionExpr(elements, None).at(sp)
-                                            } else {
-                                                elements.into_iter().next().unwrap().expr
-                                            }
-                                        })(),
-                                    )
+                                    ) {
+                                        ::peg::RuleResult::Matched(__newpos, __value) => {
+                                            ::peg::RuleResult::Matched(__newpos, Some(__value))
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            ::peg::RuleResult::Matched(__pos, None)
+                                        }
+                                    };
+                                    match __seq_res {
+                                        ::peg::RuleResult::Matched(__pos, code) => {
+                                            ::peg::RuleResult::Matched(
+                                                __pos,
+                                                (|| {
+                                                    if let Some(code) = code {
+                                                        ActionExpr(elements, Some(code)).at(sp)
+                                                    } else if elements.len() != 1 {
+                                                        ActionExpr(elements, None).at(sp)
+                                                    } else {
+                                                        elements.into_iter().next().unwrap().expr
+                                                    }
+                                                })(
+                                                ),
+                                            )
+                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    }
                                 }
                                 ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                             }
@@ -1691,100 +3024,85 @@ pub mod peg {
                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_labeled<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<TaggedExpr> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = match {
-                let __seq_res = __parse_IDENT(__input, __state, __err_state, __pos);
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, l) => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ":") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                ::peg::RuleResult::Matched(__pos, (|| l)())
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\":\"");
-                                ::peg::RuleResult::Failed
+        if __err_state.enter_rule("labeled", __pos) {
+            let __rule_result: ::peg::RuleResult<TaggedExpr> = {
+                {
+                    let __seq_res = match {
+                        let __seq_res = __parse_IDENT(__input, __state, __err_state, __pos);
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, l) => {
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ":")
+                                {
+                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                        ::peg::RuleResult::Matched(__pos, (|| l)())
+                                    }
+                                    ::peg::RuleResult::Failed => {
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\":\""),
+                                        );
+                                        ::peg::RuleResult::Failed
+                                    }
+                                }
                             }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                         }
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                }
-            } {
-                ::peg::RuleResult::Matched(__newpos, __value) => {
-                    ::peg::RuleResult::Matched(__newpos, Some(__value))
-                }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, None),
-            };
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, label) => {
-                    let __seq_res = __parse_suffixed(__input, __state, __err_state, __pos);
+                    } {
+                        ::peg::RuleResult::Matched(__newpos, __value) => {
+                            ::peg::RuleResult::Matched(__newpos, Some(__value))
+                        }
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, None),
+                    };
                     match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, expression) => {
-                            ::peg::RuleResult::Matched(
-                                __pos,
-                                (|| TaggedExpr {
-                                    name: label,
-                                    expr: expression,
-                                })(),
-                            )
+                        ::peg::RuleResult::Matched(__pos, label) => {
+                            let __seq_res = __parse_suffixed(__input, __state, __err_state, __pos);
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, expression) => {
+                                    ::peg::RuleResult::Matched(
+                                        __pos,
+                                        (|| TaggedExpr {
+                                            name: label,
+                                            expr: expression,
+                                        })(),
+                                    )
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            }
                         }
                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_suffixed<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<SpannedExpr> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = {
-                let __seq_res = __parse_prefixed(__input, __state, __err_state, __pos);
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, e) => {
-                        let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-                        match __seq_res {
-                            ::peg::RuleResult::Matched(__pos, sp) => {
-                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "?")
-                                {
-                                    ::peg::RuleResult::Matched(__pos, __val) => {
-                                        ::peg::RuleResult::Matched(
-                                            __pos,
-                                            (|| OptionalExpr(Box::new(e)).at(sp))(),
-                                        )
-                                    }
-                                    ::peg::RuleResult::Failed => {
-                                        __err_state.mark_failure(__pos, "\"?\"");
-                                        ::peg::RuleResult::Failed
-                                    }
-                                }
-                            }
-                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                        }
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                }
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
+        if __err_state.enter_rule("suffixed", __pos) {
+            let __rule_result: ::peg::RuleResult<SpannedExpr> = {
+                {
                     let __choice_res = {
                         let __seq_res = __parse_prefixed(__input, __state, __err_state, __pos);
                         match __seq_res {
@@ -1793,51 +3111,19 @@ pub mod peg {
                                 match __seq_res {
                                     ::peg::RuleResult::Matched(__pos, sp) => {
                                         match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, "**",
+                                            __input, __pos, "?",
                                         ) {
                                             ::peg::RuleResult::Matched(__pos, __val) => {
-                                                let __seq_res = __parse_repeatcount(
-                                                    __input,
-                                                    __state,
-                                                    __err_state,
+                                                ::peg::RuleResult::Matched(
                                                     __pos,
-                                                );
-                                                match __seq_res {
-                                                    ::peg::RuleResult::Matched(__pos, count) => {
-                                                        let __seq_res = __parse_primary(
-                                                            __input,
-                                                            __state,
-                                                            __err_state,
-                                                            __pos,
-                                                        );
-                                                        match __seq_res {
-                                                            ::peg::RuleResult::Matched(
-                                                                __pos,
-                                                                sep,
-                                                            ) => ::peg::RuleResult::Matched(
-                                                                __pos,
-                                                                (|| {
-                                                                    Repeat {
-                                                                        inner: Box::new(e),
-                                                                        bound: count,
-                                                                        sep: Some(Box::new(sep)),
-                                                                    }
-                                                                    .at(sp)
-                                                                })(
-                                                                ),
-                                                            ),
-                                                            ::peg::RuleResult::Failed => {
-                                                                ::peg::RuleResult::Failed
-                                                            }
-                                                        }
-                                                    }
-                                                    ::peg::RuleResult::Failed => {
-                                                        ::peg::RuleResult::Failed
-                                                    }
-                                                }
+                                                    (|| OptionalExpr(Box::new(e)).at(sp))(),
+                                                )
                                             }
                                             ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\"**\"");
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"?\""),
+                                                );
                                                 ::peg::RuleResult::Failed
                                             }
                                         }
@@ -1863,10 +3149,10 @@ pub mod peg {
                                         match __seq_res {
                                             ::peg::RuleResult::Matched(__pos, sp) => {
                                                 match ::peg::ParseLiteral::parse_string_literal(
-                                                    __input, __pos, "++",
+                                                    __input, __pos, "**",
                                                 ) {
                                                     ::peg::RuleResult::Matched(__pos, __val) => {
-                                                        let __seq_res = __parse_primary(
+                                                        let __seq_res = __parse_repeatcount(
                                                             __input,
                                                             __state,
                                                             __err_state,
@@ -1875,26 +3161,55 @@ pub mod peg {
                                                         match __seq_res {
                                                             ::peg::RuleResult::Matched(
                                                                 __pos,
-                                                                sep,
-                                                            ) => ::peg::RuleResult::Matched(
-                                                                __pos,
-                                                                (|| {
-                                                                    Repeat {
-                                                                        inner: Box::new(e),
-                                                                        bound: BoundedRepeat::Plus,
-                                                                        sep: Some(Box::new(sep)),
+                                                                count,
+                                                            ) => {
+                                                                let __seq_res = __parse_primary(
+                                                                    __input,
+                                                                    __state,
+                                                                    __err_state,
+                                                                    __pos,
+                                                                );
+                                                                match __seq_res {
+                                                                    ::peg::RuleResult::Matched(
+                                                                        __pos,
+                                                                        sep,
+                                                                    ) => {
+                                                                        ::peg::RuleResult::Matched(
+                                                                            __pos,
+                                                                            (|| {
+                                                                                Repeat {
+                                                                                    inner: Box::new(
+                                                                                        e,
+                                                                                    ),
+                                                                                    bound: count,
+                                                                                    sep: Some(
+                                                                                        Box::new(
+                                                                                            sep,
+                                                                                        ),
+                                                                                    ),
+                                                                                }
+                                                                                .at(sp)
+                                                                            })(
+                                                                            ),
+                                                                        )
                                                                     }
-                                                                    .at(sp)
-                                                                })(
-                                                                ),
-                                                            ),
+                                                                    ::peg::RuleResult::Failed => {
+                                                                        ::peg::RuleResult::Failed
+                                                                    }
+                                                                }
+                                                            }
                                                             ::peg::RuleResult::Failed => {
                                                                 ::peg::RuleResult::Failed
                                                             }
                                                         }
                                                     }
                                                     ::peg::RuleResult::Failed => {
-                                                        __err_state.mark_failure(__pos, "\"++\"");
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"**\"",
+                                                            ),
+                                                        );
                                                         ::peg::RuleResult::Failed
                                                     }
                                                 }
@@ -1921,7 +3236,7 @@ pub mod peg {
                                                     __err_state,
                                                     __pos,
                                                 );
-                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "*") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_repeatcount (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , count) => { :: peg :: RuleResult :: Matched (__pos , (|| { Repeat { inner : Box :: new (e) , bound : count , sep : None } . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"*\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "++") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_primary (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sep) => { :: peg :: RuleResult :: Matched (__pos , (|| { Repeat { inner : Box :: new (e) , bound : BoundedRepeat :: Plus , sep : Some (Box :: new (sep)) } . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"++\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                             }
                                             ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                         }
@@ -1946,7 +3261,7 @@ pub mod peg {
                                                             __err_state,
                                                             __pos,
                                                         );
-                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "+") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Repeat { inner : Box :: new (e) , bound : BoundedRepeat :: Plus , sep : None } . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"+\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "*") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_repeatcount (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , count) => { :: peg :: RuleResult :: Matched (__pos , (|| { Repeat { inner : Box :: new (e) , bound : count , sep : None } . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"*\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                     }
                                                     ::peg::RuleResult::Failed => {
                                                         ::peg::RuleResult::Failed
@@ -1957,12 +3272,49 @@ pub mod peg {
                                                 ::peg::RuleResult::Matched(__pos, __value) => {
                                                     ::peg::RuleResult::Matched(__pos, __value)
                                                 }
-                                                ::peg::RuleResult::Failed => __parse_prefixed(
-                                                    __input,
-                                                    __state,
-                                                    __err_state,
-                                                    __pos,
-                                                ),
+                                                ::peg::RuleResult::Failed => {
+                                                    let __choice_res = {
+                                                        let __seq_res = __parse_prefixed(
+                                                            __input,
+                                                            __state,
+                                                            __err_state,
+                                                            __pos,
+                                                        );
+                                                        match __seq_res {
+                                                            ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                e,
+                                                            ) => {
+                                                                let __seq_res = __parse_sp(
+                                                                    __input,
+                                                                    __state,
+                                                                    __err_state,
+                                                                    __pos,
+                                                                );
+                                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "+") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { Repeat { inner : Box :: new (e) , bound : BoundedRepeat :: Plus , sep : None } . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"+\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                            }
+                                                            ::peg::RuleResult::Failed => {
+                                                                ::peg::RuleResult::Failed
+                                                            }
+                                                        }
+                                                    };
+                                                    match __choice_res {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            __value,
+                                                        ) => ::peg::RuleResult::Matched(
+                                                            __pos, __value,
+                                                        ),
+                                                        ::peg::RuleResult::Failed => {
+                                                            __parse_prefixed(
+                                                                __input,
+                                                                __state,
+                                                                __err_state,
+                                                                __pos,
+                                                            )
+                                                        }
+                                                    }
+                                                }
                                             }
                                         }
                                     }
@@ -1971,215 +3323,204 @@ pub mod peg {
                         }
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_repeatcount<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<BoundedRepeat> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<")
-            {
-                ::peg::RuleResult::Matched(__pos, __val) => {
-                    let __seq_res = __parse_repeatnum(__input, __state, __err_state, __pos);
-                    match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, n) => {
-                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ">") {
-                                ::peg::RuleResult::Matched(__pos, __val) => {
-                                    ::peg::RuleResult::Matched(
-                                        __pos,
-                                        (|| BoundedRepeat::Exact(n))(),
-                                    )
-                                }
-                                ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\">\"");
-                                    ::peg::RuleResult::Failed
+        if __err_state.enter_rule("repeatcount", __pos) {
+            let __rule_result: ::peg::RuleResult<BoundedRepeat> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<") {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                let __seq_res =
+                                    __parse_repeatnum(__input, __state, __err_state, __pos);
+                                match __seq_res {
+                                    ::peg::RuleResult::Matched(__pos, n) => {
+                                        match ::peg::ParseLiteral::parse_string_literal(
+                                            __input, __pos, ">",
+                                        ) {
+                                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                                ::peg::RuleResult::Matched(
+                                                    __pos,
+                                                    (|| BoundedRepeat::Exact(n))(),
+                                                )
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\">\""),
+                                                );
+                                                ::peg::RuleResult::Failed
+                                            }
+                                        }
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                 }
                             }
+                            ::peg::RuleResult::Failed => {
+                                __err_state
+                                    .mark_failure(__pos, ::peg::error::Expected::Literal("\"<\""));
+                                ::peg::RuleResult::Failed
+                            }
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
                         }
-                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                    }
-                }
-                ::peg::RuleResult::Failed => {
-                    __err_state.mark_failure(__pos, "\"<\"");
-                    ::peg::RuleResult::Failed
-                }
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
-                    let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
-                        __input, __pos, "<",
-                    ) {
-                        ::peg::RuleResult::Matched(__pos, __val) => {
-                            let __seq_res =
-                                match __parse_repeatnum(__input, __state, __err_state, __pos) {
-                                    ::peg::RuleResult::Matched(__newpos, __value) => {
-                                        ::peg::RuleResult::Matched(__newpos, Some(__value))
-                                    }
-                                    ::peg::RuleResult::Failed => {
-                                        ::peg::RuleResult::Matched(__pos, None)
-                                    }
-                                };
-                            match __seq_res {
-                                ::peg::RuleResult::Matched(__pos, min) => {
-                                    match ::peg::ParseLiteral::parse_string_literal(
-                                        __input, __pos, ",",
+                        ::peg::RuleResult::Failed => {
+                            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
+                                __input, __pos, "<",
+                            ) {
+                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                    let __seq_res = match __parse_repeatnum(
+                                        __input,
+                                        __state,
+                                        __err_state,
+                                        __pos,
                                     ) {
-                                        ::peg::RuleResult::Matched(__pos, __val) => {
-                                            let __seq_res = match __parse_repeatnum(
-                                                __input,
-                                                __state,
-                                                __err_state,
-                                                __pos,
+                                        ::peg::RuleResult::Matched(__newpos, __value) => {
+                                            ::peg::RuleResult::Matched(__newpos, Some(__value))
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            ::peg::RuleResult::Matched(__pos, None)
+                                        }
+                                    };
+                                    match __seq_res {
+                                        ::peg::RuleResult::Matched(__pos, min) => {
+                                            match ::peg::ParseLiteral::parse_string_literal(
+                                                __input, __pos, ",",
                                             ) {
-                                                ::peg::RuleResult::Matched(__newpos, __value) => {
-                                                    ::peg::RuleResult::Matched(
-                                                        __newpos,
-                                                        Some(__value),
-                                                    )
-                                                }
-                                                ::peg::RuleResult::Failed => {
-                                                    ::peg::RuleResult::Matched(__pos, None)
-                                                }
-                                            };
-                                            match __seq_res {
-                                                ::peg::RuleResult::Matched(__pos, max) => {
-                                                    match ::peg::ParseLiteral::parse_string_literal(
-                                                        __input, __pos, ">",
+                                                ::peg::RuleResult::Matched(__pos, __val) => {
+                                                    let __seq_res = match __parse_repeatnum(
+                                                        __input,
+                                                        __state,
+                                                        __err_state,
+                                                        __pos,
                                                     ) {
                                                         ::peg::RuleResult::Matched(
-                                                            __pos,
-                                                            __val,
+                                                            __newpos,
+                                                            __value,
                                                         ) => ::peg::RuleResult::Matched(
-                                                            __pos,
-                                                            (|| BoundedRepeat::Both(min, max))(),
+                                                            __newpos,
+                                                            Some(__value),
                                                         ),
                                                         ::peg::RuleResult::Failed => {
-                                                            __err_state
-                                                                .mark_failure(__pos, "\">\"");
-                                                            ::peg::RuleResult::Failed
+                                                            ::peg::RuleResult::Matched(__pos, None)
                                                         }
-                                                    }
+                                                    };
+                                                    match __seq_res { :: peg :: RuleResult :: Matched (__pos , max) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ">") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { BoundedRepeat :: Both (min , max) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\">\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                 }
                                                 ::peg::RuleResult::Failed => {
+                                                    __err_state.mark_failure(
+                                                        __pos,
+                                                        ::peg::error::Expected::Literal("\",\""),
+                                                    );
                                                     ::peg::RuleResult::Failed
                                                 }
                                             }
                                         }
-                                        ::peg::RuleResult::Failed => {
-                                            __err_state.mark_failure(__pos, "\",\"");
-                                            ::peg::RuleResult::Failed
-                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                     }
                                 }
-                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                            }
-                        }
-                        ::peg::RuleResult::Failed => {
-                            __err_state.mark_failure(__pos, "\"<\"");
-                            ::peg::RuleResult::Failed
-                        }
-                    };
-                    match __choice_res {
-                        ::peg::RuleResult::Matched(__pos, __value) => {
-                            ::peg::RuleResult::Matched(__pos, __value)
-                        }
-                        ::peg::RuleResult::Failed => {
-                            ::peg::RuleResult::Matched(__pos, (|| BoundedRepeat::None)())
+                                ::peg::RuleResult::Failed => {
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"<\""),
+                                    );
+                                    ::peg::RuleResult::Failed
+                                }
+                            };
+                            match __choice_res {
+                                ::peg::RuleResult::Matched(__pos, __value) => {
+                                    ::peg::RuleResult::Matched(__pos, __value)
+                                }
+                                ::peg::RuleResult::Failed => {
+                                    ::peg::RuleResult::Matched(__pos, (|| BoundedRepeat::None)())
+                                }
+                            }
                         }
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_repeatnum<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<TokenStream> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let str_start = __pos;
-            match {
-                let __choice_res = match __parse_INTEGER(__input, __state, __err_state, __pos) {
-                    ::peg::RuleResult::Matched(pos, _) => ::peg::RuleResult::Matched(pos, ()),
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                };
-                match __choice_res {
-                    ::peg::RuleResult::Matched(__pos, __value) => {
-                        ::peg::RuleResult::Matched(__pos, __value)
-                    }
-                    ::peg::RuleResult::Failed => {
-                        match __parse_BRACE_GROUP(__input, __state, __err_state, __pos) {
-                            ::peg::RuleResult::Matched(pos, _) => {
-                                ::peg::RuleResult::Matched(pos, ())
+        if __err_state.enter_rule("repeatnum", __pos) {
+            let __rule_result: ::peg::RuleResult<TokenStream> = {
+                {
+                    let str_start = __pos;
+                    match {
+                        let __choice_res =
+                            match __parse_INTEGER(__input, __state, __err_state, __pos) {
+                                ::peg::RuleResult::Matched(pos, _) => {
+                                    ::peg::RuleResult::Matched(pos, ())
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            };
+                        match __choice_res {
+                            ::peg::RuleResult::Matched(__pos, __value) => {
+                                ::peg::RuleResult::Matched(__pos, __value)
+                            }
+                            ::peg::RuleResult::Failed => {
+                                match __parse_BRACE_GROUP(__input, __state, __err_state, __pos) {
+                                    ::peg::RuleResult::Matched(pos, _) => {
+                                        ::peg::RuleResult::Matched(pos, ())
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                }
                             }
-                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                         }
+                    } {
+                        ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
+                            __newpos,
+                            ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
+                        ),
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-            } {
-                ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
-                    __newpos,
-                    ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
-                ),
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_prefixed<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<SpannedExpr> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = {
-                let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, sp) => {
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "$") {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                let __seq_res =
-                                    __parse_primary(__input, __state, __err_state, __pos);
-                                match __seq_res {
-                                    ::peg::RuleResult::Matched(__pos, expression) => {
-                                        ::peg::RuleResult::Matched(
-                                            __pos,
-                                            (|| MatchStrExpr(Box::new(expression)).at(sp))(),
-                                        )
-                                    }
-                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                                }
-                            }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"$\"");
-                                ::peg::RuleResult::Failed
-                            }
-                        }
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                }
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
+        if __err_state.enter_rule("prefixed", __pos) {
+            let __rule_result: ::peg::RuleResult<SpannedExpr> = {
+                {
                     let __choice_res = {
                         let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
                         match __seq_res {
                             ::peg::RuleResult::Matched(__pos, sp) => {
-                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "&")
+                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "$")
                                 {
                                     ::peg::RuleResult::Matched(__pos, __val) => {
                                         let __seq_res =
@@ -2188,7 +3529,7 @@ pub mod peg {
                                             ::peg::RuleResult::Matched(__pos, expression) => {
                                                 ::peg::RuleResult::Matched(
                                                     __pos,
-                                                    (|| PosAssertExpr(Box::new(expression)).at(sp))(
+                                                    (|| MatchStrExpr(Box::new(expression)).at(sp))(
                                                     ),
                                                 )
                                             }
@@ -2196,7 +3537,10 @@ pub mod peg {
                                         }
                                     }
                                     ::peg::RuleResult::Failed => {
-                                        __err_state.mark_failure(__pos, "\"&\"");
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"$\""),
+                                        );
                                         ::peg::RuleResult::Failed
                                     }
                                 }
@@ -2214,7 +3558,7 @@ pub mod peg {
                                 match __seq_res {
                                     ::peg::RuleResult::Matched(__pos, sp) => {
                                         match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, "!",
+                                            __input, __pos, "&",
                                         ) {
                                             ::peg::RuleResult::Matched(__pos, __val) => {
                                                 let __seq_res = __parse_primary(
@@ -2230,7 +3574,7 @@ pub mod peg {
                                                     ) => ::peg::RuleResult::Matched(
                                                         __pos,
                                                         (|| {
-                                                            NegAssertExpr(Box::new(expression))
+                                                            PosAssertExpr(Box::new(expression))
                                                                 .at(sp)
                                                         })(
                                                         ),
@@ -2241,7 +3585,10 @@ pub mod peg {
                                                 }
                                             }
                                             ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\"!\"");
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"&\""),
+                                                );
                                                 ::peg::RuleResult::Failed
                                             }
                                         }
@@ -2254,115 +3601,178 @@ pub mod peg {
                                     ::peg::RuleResult::Matched(__pos, __value)
                                 }
                                 ::peg::RuleResult::Failed => {
-                                    __parse_primary(__input, __state, __err_state, __pos)
+                                    let __choice_res = {
+                                        let __seq_res =
+                                            __parse_sp(__input, __state, __err_state, __pos);
+                                        match __seq_res {
+                                            ::peg::RuleResult::Matched(__pos, sp) => {
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, "!",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        let __seq_res = __parse_primary(
+                                                            __input,
+                                                            __state,
+                                                            __err_state,
+                                                            __pos,
+                                                        );
+                                                        match __seq_res {
+                                                            ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                expression,
+                                                            ) => ::peg::RuleResult::Matched(
+                                                                __pos,
+                                                                (|| {
+                                                                    NegAssertExpr(Box::new(
+                                                                        expression,
+                                                                    ))
+                                                                    .at(sp)
+                                                                })(
+                                                                ),
+                                                            ),
+                                                            ::peg::RuleResult::Failed => {
+                                                                ::peg::RuleResult::Failed
+                                                            }
+                                                        }
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"!\"",
+                                                            ),
+                                                        );
+                                                        ::peg::RuleResult::Failed
+                                                    }
+                                                }
+                                            }
+                                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                        }
+                                    };
+                                    match __choice_res {
+                                        ::peg::RuleResult::Matched(__pos, __value) => {
+                                            ::peg::RuleResult::Matched(__pos, __value)
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            __parse_primary(__input, __state, __err_state, __pos)
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_primary<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<SpannedExpr> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        if let Some(entry) = __state.primary_cache.get(&__pos) {
+        if let Some(entry) = __state.primary_cache.get(__pos) {
             return entry.clone();
         }
-        let __rule_result = {
-            let __choice_res = {
-                let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, sp) => {
-                        match ::peg::ParseLiteral::parse_string_literal(
-                            __input,
-                            __pos,
-                            "precedence",
-                        ) {
-                            ::peg::RuleResult::Matched(__pos, __val) => {
-                                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "!")
-                                {
+        let __rule_result = if __err_state.enter_rule("primary", __pos) {
+            let __rule_result: ::peg::RuleResult<SpannedExpr> = {
+                {
+                    let __choice_res = {
+                        let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, sp) => {
+                                match ::peg::ParseLiteral::parse_string_literal(
+                                    __input,
+                                    __pos,
+                                    "precedence",
+                                ) {
                                     ::peg::RuleResult::Matched(__pos, __val) => {
                                         match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, "{",
+                                            __input, __pos, "!",
                                         ) {
                                             ::peg::RuleResult::Matched(__pos, __val) => {
-                                                let __seq_res = {
-                                                    let mut __repeat_pos = __pos;
-                                                    let mut __repeat_value = vec![];
-                                                    loop {
-                                                        let __pos = __repeat_pos;
-                                                        let __pos = if __repeat_value.is_empty() {
-                                                            __pos
-                                                        } else {
-                                                            let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "--") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"--\"") ; :: peg :: RuleResult :: Failed } } ;
-                                                            match __sep_res {
-                                                                ::peg::RuleResult::Matched(
-                                                                    __newpos,
-                                                                    _,
-                                                                ) => __newpos,
-                                                                ::peg::RuleResult::Failed => break,
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, "{",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        let __seq_res = {
+                                                            let mut __repeat_pos = __pos;
+                                                            let mut __repeat_value =
+                                                                ::peg::__alloc::vec::Vec::new();
+                                                            loop {
+                                                                let __pos = __repeat_pos;
+                                                                let __pos = if __repeat_value
+                                                                    .is_empty()
+                                                                {
+                                                                    __pos
+                                                                } else {
+                                                                    let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "--") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"--\"")) ; :: peg :: RuleResult :: Failed } } ;
+                                                                    match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => { break } , }
+                                                                };
+                                                                let __step_res =
+                                                                    __parse_precedence_level(
+                                                                        __input,
+                                                                        __state,
+                                                                        __err_state,
+                                                                        __pos,
+                                                                    );
+                                                                match __step_res {
+                                                                    ::peg::RuleResult::Matched(
+                                                                        __newpos,
+                                                                        __value,
+                                                                    ) => {
+                                                                        __repeat_pos = __newpos;
+                                                                        __repeat_value
+                                                                            .push(__value);
+                                                                    }
+                                                                    ::peg::RuleResult::Failed => {
+                                                                        break;
+                                                                    }
+                                                                }
                                                             }
+                                                            ::peg::RuleResult::Matched(
+                                                                __repeat_pos,
+                                                                __repeat_value,
+                                                            )
                                                         };
-                                                        let __step_res = __parse_precedence_level(
-                                                            __input,
-                                                            __state,
-                                                            __err_state,
+                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , levels) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { PrecedenceExpr { levels : levels } . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"}\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
                                                             __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"{\"",
+                                                            ),
                                                         );
-                                                        match __step_res {
-                                                            ::peg::RuleResult::Matched(
-                                                                __newpos,
-                                                                __value,
-                                                            ) => {
-                                                                __repeat_pos = __newpos;
-                                                                __repeat_value.push(__value);
-                                                            }
-                                                            ::peg::RuleResult::Failed => {
-                                                                break;
-                                                            }
-                                                        }
+                                                        ::peg::RuleResult::Failed
                                                     }
-                                                    ::peg::RuleResult::Matched(
-                                                        __repeat_pos,
-                                                        __repeat_value,
-                                                    )
-                                                };
-                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , levels) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { PrecedenceExpr { levels : levels } . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"}\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                }
                                             }
                                             ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\"{\"");
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"!\""),
+                                                );
                                                 ::peg::RuleResult::Failed
                                             }
                                         }
                                     }
                                     ::peg::RuleResult::Failed => {
-                                        __err_state.mark_failure(__pos, "\"!\"");
+                                        __err_state.mark_failure(
+                                            __pos,
+                                            ::peg::error::Expected::Literal("\"precedence\""),
+                                        );
                                         ::peg::RuleResult::Failed
                                     }
                                 }
                             }
-                            ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"precedence\"");
-                                ::peg::RuleResult::Failed
-                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                         }
-                    }
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                }
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
-                    let __choice_res = {
-                        let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "position") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { PositionExpr . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\")\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"(\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"!\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"position\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                     };
                     match __choice_res {
                         ::peg::RuleResult::Matched(__pos, __value) => {
@@ -2371,7 +3781,7 @@ pub mod peg {
                         ::peg::RuleResult::Failed => {
                             let __choice_res = {
                                 let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "quiet") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "{") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , e) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { QuietExpr (Box :: new (e)) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"}\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"{\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"!\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"quiet\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "position") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { PositionExpr . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"position\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                             };
                             match __choice_res {
                                 ::peg::RuleResult::Matched(__pos, __value) => {
@@ -2381,7 +3791,7 @@ pub mod peg {
                                     let __choice_res = {
                                         let __seq_res =
                                             __parse_sp(__input, __state, __err_state, __pos);
-                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "expected") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_LITERAL (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , s) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { FailExpr (s) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\")\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"(\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"!\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"expected\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "quiet") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "{") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , e) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "}") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { QuietExpr (Box :: new (e)) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"}\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"{\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"quiet\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                     };
                                     match __choice_res {
                                         ::peg::RuleResult::Matched(__pos, __value) => {
@@ -2389,85 +3799,13 @@ pub mod peg {
                                         }
                                         ::peg::RuleResult::Failed => {
                                             let __choice_res = {
-                                                let __seq_res = {
-                                                    __err_state.suppress_fail += 1;
-                                                    let __assert_res = {
-                                                        let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "_") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"_\"") ; :: peg :: RuleResult :: Failed } } ;
-                                                        match __choice_res {
-                                                            ::peg::RuleResult::Matched(
-                                                                __pos,
-                                                                __value,
-                                                            ) => ::peg::RuleResult::Matched(
-                                                                __pos, __value,
-                                                            ),
-                                                            ::peg::RuleResult::Failed => {
-                                                                let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "__") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"__\"") ; :: peg :: RuleResult :: Failed } } ;
-                                                                match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "___") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"___\"") ; :: peg :: RuleResult :: Failed } } }
-                                                            }
-                                                        }
-                                                    };
-                                                    __err_state.suppress_fail -= 1;
-                                                    match __assert_res {
-                                                        ::peg::RuleResult::Matched(_, __value) => {
-                                                            ::peg::RuleResult::Matched(
-                                                                __pos, __value,
-                                                            )
-                                                        }
-                                                        ::peg::RuleResult::Failed => {
-                                                            ::peg::RuleResult::Failed
-                                                        }
-                                                    }
-                                                };
-                                                match __seq_res {
-                                                    ::peg::RuleResult::Matched(__pos, _) => {
-                                                        let __seq_res = __parse_sp(
-                                                            __input,
-                                                            __state,
-                                                            __err_state,
-                                                            __pos,
-                                                        );
-                                                        match __seq_res {
-                                                            ::peg::RuleResult::Matched(
-                                                                __pos,
-                                                                sp,
-                                                            ) => {
-                                                                let __seq_res = __parse_IDENT(
-                                                                    __input,
-                                                                    __state,
-                                                                    __err_state,
-                                                                    __pos,
-                                                                );
-                                                                match __seq_res {
-                                                                    ::peg::RuleResult::Matched(
-                                                                        __pos,
-                                                                        name,
-                                                                    ) => {
-                                                                        ::peg::RuleResult::Matched(
-                                                                            __pos,
-                                                                            (|| {
-                                                                                RuleExpr(
-                                                                                    name,
-                                                                                    Vec::new(),
-                                                                                )
-                                                                                .at(sp)
-                                                                            })(
-                                                                            ),
-                                                                        )
-                                                                    }
-                                                                    ::peg::RuleResult::Failed => {
-                                                                        ::peg::RuleResult::Failed
-                                                                    }
-                                                                }
-                                                            }
-                                                            ::peg::RuleResult::Failed => {
-                                                                ::peg::RuleResult::Failed
-                                                            }
-                                                        }
-                                                    }
-                                                    ::peg::RuleResult::Failed => {
-                                                        ::peg::RuleResult::Failed
-                                                    }
-                                                }
+                                                let __seq_res = __parse_sp(
+                                                    __input,
+                                                    __state,
+                                                    __err_state,
+                                                    __pos,
+                                                );
+                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "expected") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_LITERAL (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , s) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { FailExpr (s) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"expected\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                             };
                                             match __choice_res {
                                                 ::peg::RuleResult::Matched(__pos, __value) => {
@@ -2481,23 +3819,7 @@ pub mod peg {
                                                             __err_state,
                                                             __pos,
                                                         );
-                                                        match __seq_res {
-                                                            ::peg::RuleResult::Matched(
-                                                                __pos,
-                                                                sp,
-                                                            ) => {
-                                                                let __seq_res = __parse_IDENT(
-                                                                    __input,
-                                                                    __state,
-                                                                    __err_state,
-                                                                    __pos,
-                                                                );
-                                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , name) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = vec ! () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\",\"") ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => break , } } ; let __step_res = __parse_rule_arg (__input , __state , __err_state , __pos) ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } :: peg :: RuleResult :: Matched (__repeat_pos , __repeat_value) } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { RuleExpr (name , args) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\")\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"(\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
-                                                            }
-                                                            ::peg::RuleResult::Failed => {
-                                                                ::peg::RuleResult::Failed
-                                                            }
-                                                        }
+                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "literal") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_PAREN_GROUP (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { :: peg :: RuleResult :: Matched (__pos , (|| { DynamicLiteralExpr (args . stream ()) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"literal\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                     };
                                                     match __choice_res {
                                                         ::peg::RuleResult::Matched(
@@ -2513,25 +3835,8 @@ pub mod peg {
                                                                     __state,
                                                                     __err_state,
                                                                     __pos,
-                                                                );
-                                                                match __seq_res {
-                                                                    ::peg::RuleResult::Matched(
-                                                                        __pos,
-                                                                        sp,
-                                                                    ) => {
-                                                                        let __seq_res =
-                                                                            __parse_LITERAL(
-                                                                                __input,
-                                                                                __state,
-                                                                                __err_state,
-                                                                                __pos,
-                                                                            );
-                                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , l) => { :: peg :: RuleResult :: Matched (__pos , (|| { LiteralExpr (l) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
-                                                                    }
-                                                                    ::peg::RuleResult::Failed => {
-                                                                        ::peg::RuleResult::Failed
-                                                                    }
-                                                                }
+                                                                );
+                                                                match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "indent") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { IndentExpr (IndentKind :: Indent) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"indent\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                             };
                                                             match __choice_res {
                                                                 ::peg::RuleResult::Matched(
@@ -2548,9 +3853,9 @@ pub mod peg {
                                                                             __err_state,
                                                                             __pos,
                                                                         );
-                                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { { let __seq_res = __parse_BRACKET_GROUP (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , p) => { :: peg :: RuleResult :: Matched (__pos , (|| { PatternExpr (p) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
+                                                                        match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "dedent") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { IndentExpr (IndentKind :: Dedent) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"dedent\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , }
                                                                     };
-                                                                    match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "@") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { MarkerExpr (true) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\")\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"@\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"(\"") ; :: peg :: RuleResult :: Failed } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "@") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { MarkerExpr (false) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"@\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "##") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_IDENT (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , method) => { { let __seq_res = __parse_PAREN_GROUP (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { :: peg :: RuleResult :: Matched (__pos , (|| { MethodExpr (method , args . stream ()) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"##\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , expression) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { expression }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\")\"") ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , "\"(\"") ; :: peg :: RuleResult :: Failed } } } } } } } } }
+                                                                    match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "same_indent") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { IndentExpr (IndentKind :: Same) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"same_indent\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "recover") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , e) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sync) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { RecoverExpr (Box :: new (e) , Box :: new (sync)) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\",\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"recover\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = { __err_state . suppress_fail += 1 ; let __assert_res = { let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "_") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"_\"")) ; :: peg :: RuleResult :: Failed } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "__") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"__\"")) ; :: peg :: RuleResult :: Failed } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "___") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"___\"")) ; :: peg :: RuleResult :: Failed } } } } } } ; __err_state . suppress_fail -= 1 ; match __assert_res { :: peg :: RuleResult :: Matched (_ , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , _) => { { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { { let __seq_res = __parse_IDENT (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , name) => { :: peg :: RuleResult :: Matched (__pos , (|| { RuleExpr (name , Vec :: new ()) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { { let __seq_res = __parse_IDENT (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , name) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = { let mut __repeat_pos = __pos ; let mut __repeat_value = :: peg :: __alloc :: vec :: Vec :: new () ; loop { let __pos = __repeat_pos ; let __pos = if __repeat_value . is_empty () { __pos } else { let __sep_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ",") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , __val) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\",\"")) ; :: peg :: RuleResult :: Failed } } ; match __sep_res { :: peg :: RuleResult :: Matched (__newpos , _) => { __newpos } , :: peg :: RuleResult :: Failed => { break } , } } ; let __step_res = __parse_rule_arg (__input , __state , __err_state , __pos) ; match __step_res { :: peg :: RuleResult :: Matched (__newpos , __value) => { __repeat_pos = __newpos ; __repeat_value . push (__value) ; } , :: peg :: RuleResult :: Failed => { break ; } } } :: peg :: RuleResult :: Matched (__repeat_pos , __repeat_value) } ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { RuleExpr (name , args) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { { let __seq_res = __parse_CASE_INSENSITIVE_LITERAL (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , l) => { :: peg :: RuleResult :: Matched (__pos , (|| { CaseInsensitiveLiteralExpr (l) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { { let __seq_res = __parse_BYTE_STRING_LITERAL (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , l) => { :: peg :: RuleResult :: Matched (__pos , (|| { ByteLiteralExpr (l) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { { let __seq_res = __parse_LITERAL (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , l) => { :: peg :: RuleResult :: Matched (__pos , (|| { LiteralExpr (l) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { { let __seq_res = __parse_BRACKET_GROUP (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , p) => { :: peg :: RuleResult :: Matched (__pos , (|| { PatternExpr (p) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "@") { :: peg :: RuleResult :: Matched (__pos , __val) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { MarkerExpr (true) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"@\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "@") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { MarkerExpr (false) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"@\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "##") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_IDENT (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , method) => { { let __seq_res = __parse_PAREN_GROUP (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , args) => { :: peg :: RuleResult :: Matched (__pos , (|| { MethodExpr (method , args . stream ()) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"##\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "~") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { CutExpr . at (sp) }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"~\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "&") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_BRACE_GROUP (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , code) => { :: peg :: RuleResult :: Matched (__pos , (|| { PosPredicateExpr (code) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"&\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => { let __choice_res = { let __seq_res = __parse_sp (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , sp) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "!") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_BRACE_GROUP (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , code) => { :: peg :: RuleResult :: Matched (__pos , (|| { NegPredicateExpr (code) . at (sp) }) ()) } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"!\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } ; match __choice_res { :: peg :: RuleResult :: Matched (__pos , __value) => :: peg :: RuleResult :: Matched (__pos , __value) , :: peg :: RuleResult :: Failed => match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , "(") { :: peg :: RuleResult :: Matched (__pos , __val) => { { let __seq_res = __parse_expression (__input , __state , __err_state , __pos) ; match __seq_res { :: peg :: RuleResult :: Matched (__pos , expression) => { match :: peg :: ParseLiteral :: parse_string_literal (__input , __pos , ")") { :: peg :: RuleResult :: Matched (__pos , __val) => { :: peg :: RuleResult :: Matched (__pos , (|| { expression }) ()) } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\")\"")) ; :: peg :: RuleResult :: Failed } } } :: peg :: RuleResult :: Failed => :: peg :: RuleResult :: Failed , } } } :: peg :: RuleResult :: Failed => { __err_state . mark_failure (__pos , :: peg :: error :: Expected :: Literal ("\"(\"")) ; :: peg :: RuleResult :: Failed } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } }
                                                                 }
                                                             }
                                                         }
@@ -2564,7 +3869,11 @@ pub mod peg {
                         }
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         };
         __state.primary_cache.insert(__pos, __rule_result.clone());
         __rule_result
@@ -2572,139 +3881,120 @@ pub mod peg {
     fn __parse_rule_arg<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<RuleArg> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res = match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<")
-            {
-                ::peg::RuleResult::Matched(__pos, __val) => {
-                    let __seq_res = __parse_expression(__input, __state, __err_state, __pos);
-                    match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, e) => {
-                            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, ">") {
-                                ::peg::RuleResult::Matched(__pos, __val) => {
-                                    ::peg::RuleResult::Matched(__pos, (|| RuleArg::Peg(e))())
-                                }
-                                ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\">\"");
-                                    ::peg::RuleResult::Failed
+        if __err_state.enter_rule("rule_arg", __pos) {
+            let __rule_result: ::peg::RuleResult<RuleArg> = {
+                {
+                    let __choice_res =
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "<") {
+                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                let __seq_res =
+                                    __parse_expression(__input, __state, __err_state, __pos);
+                                match __seq_res {
+                                    ::peg::RuleResult::Matched(__pos, e) => {
+                                        match ::peg::ParseLiteral::parse_string_literal(
+                                            __input, __pos, ">",
+                                        ) {
+                                            ::peg::RuleResult::Matched(__pos, __val) => {
+                                                ::peg::RuleResult::Matched(
+                                                    __pos,
+                                                    (|| RuleArg::Peg(e))(),
+                                                )
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\">\""),
+                                                );
+                                                ::peg::RuleResult::Failed
+                                            }
+                                        }
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                 }
                             }
+                            ::peg::RuleResult::Failed => {
+                                __err_state
+                                    .mark_failure(__pos, ::peg::error::Expected::Literal("\"<\""));
+                                ::peg::RuleResult::Failed
+                            }
+                        };
+                    match __choice_res {
+                        ::peg::RuleResult::Matched(__pos, __value) => {
+                            ::peg::RuleResult::Matched(__pos, __value)
                         }
-                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                    }
-                }
-                ::peg::RuleResult::Failed => {
-                    __err_state.mark_failure(__pos, "\"<\"");
-                    ::peg::RuleResult::Failed
-                }
-            };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
-                    let __seq_res = {
-                        let str_start = __pos;
-                        match {
-                            let mut __repeat_pos = __pos;
-                            let mut __repeat_value = vec![];
-                            loop {
-                                let __pos = __repeat_pos;
-                                let __step_res = __input.eat_until(__pos, ',');
-                                match __step_res {
-                                    ::peg::RuleResult::Matched(__newpos, __value) => {
-                                        __repeat_pos = __newpos;
-                                        __repeat_value.push(__value);
+                        ::peg::RuleResult::Failed => {
+                            let __seq_res = {
+                                let str_start = __pos;
+                                match {
+                                    let mut __repeat_pos = __pos;
+                                    let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                                    loop {
+                                        let __pos = __repeat_pos;
+                                        let __step_res = __input.eat_until(__pos, ',');
+                                        match __step_res {
+                                            ::peg::RuleResult::Matched(__newpos, __value) => {
+                                                __repeat_pos = __newpos;
+                                                __repeat_value.push(__value);
+                                            }
+                                            ::peg::RuleResult::Failed => {
+                                                break;
+                                            }
+                                        }
                                     }
-                                    ::peg::RuleResult::Failed => {
-                                        break;
+                                    if __repeat_value.len() >= 1 {
+                                        ::peg::RuleResult::Matched(__repeat_pos, ())
+                                    } else {
+                                        ::peg::RuleResult::Failed
                                     }
+                                } {
+                                    ::peg::RuleResult::Matched(__newpos, _) => {
+                                        ::peg::RuleResult::Matched(
+                                            __newpos,
+                                            ::peg::ParseSlice::parse_slice(
+                                                __input, str_start, __newpos,
+                                            ),
+                                        )
+                                    }
+                                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                                 }
+                            };
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, tt) => {
+                                    ::peg::RuleResult::Matched(__pos, (|| RuleArg::Rust(tt))())
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                             }
-                            if __repeat_value.len() >= 1 {
-                                ::peg::RuleResult::Matched(__repeat_pos, ())
-                            } else {
-                                ::peg::RuleResult::Failed
-                            }
-                        } {
-                            ::peg::RuleResult::Matched(__newpos, _) => ::peg::RuleResult::Matched(
-                                __newpos,
-                                ::peg::ParseSlice::parse_slice(__input, str_start, __newpos),
-                            ),
-                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                        }
-                    };
-                    match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, tt) => {
-                            ::peg::RuleResult::Matched(__pos, (|| RuleArg::Rust(tt))())
                         }
-                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_precedence_level<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<PrecedenceLevel> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = {
-                let mut __repeat_pos = __pos;
-                let mut __repeat_value = vec![];
-                loop {
-                    let __pos = __repeat_pos;
-                    let __step_res = __parse_precedence_op(__input, __state, __err_state, __pos);
-                    match __step_res {
-                        ::peg::RuleResult::Matched(__newpos, __value) => {
-                            __repeat_pos = __newpos;
-                            __repeat_value.push(__value);
-                        }
-                        ::peg::RuleResult::Failed => {
-                            break;
-                        }
-                    }
-                }
-                if __repeat_value.len() >= 1 {
-                    ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
-                } else {
-                    ::peg::RuleResult::Failed
-                }
-            };
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, operators) => ::peg::RuleResult::Matched(
-                    __pos,
-                    (|| PrecedenceLevel {
-                        operators: operators,
-                    })(),
-                ),
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
-        }
-    }
-    fn __parse_precedence_op<'input>(
-        __input: &'input Input,
-        __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
-        __pos: usize,
-    ) -> ::peg::RuleResult<PrecedenceOperator> {
-        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, span) => {
+        if __err_state.enter_rule("precedence_level", __pos) {
+            let __rule_result: ::peg::RuleResult<PrecedenceLevel> = {
+                {
                     let __seq_res = {
                         let mut __repeat_pos = __pos;
-                        let mut __repeat_value = vec![];
+                        let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
                         loop {
                             let __pos = __repeat_pos;
-                            let __step_res = __parse_labeled(__input, __state, __err_state, __pos);
+                            let __step_res =
+                                __parse_precedence_op(__input, __state, __err_state, __pos);
                             match __step_res {
                                 ::peg::RuleResult::Matched(__newpos, __value) => {
                                     __repeat_pos = __newpos;
@@ -2715,22 +4005,79 @@ pub mod peg {
                                 }
                             }
                         }
-                        ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                        if __repeat_value.len() >= 1 {
+                            ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                        } else {
+                            ::peg::RuleResult::Failed
+                        }
                     };
                     match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, elements) => {
-                            let __seq_res =
-                                __parse_BRACE_GROUP(__input, __state, __err_state, __pos);
+                        ::peg::RuleResult::Matched(__pos, operators) => ::peg::RuleResult::Matched(
+                            __pos,
+                            (|| PrecedenceLevel {
+                                operators: operators,
+                            })(),
+                        ),
+                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                    }
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_precedence_op<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<PrecedenceOperator> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("precedence_op", __pos) {
+            let __rule_result: ::peg::RuleResult<PrecedenceOperator> = {
+                {
+                    let __seq_res = __parse_sp(__input, __state, __err_state, __pos);
+                    match __seq_res {
+                        ::peg::RuleResult::Matched(__pos, span) => {
+                            let __seq_res = {
+                                let mut __repeat_pos = __pos;
+                                let mut __repeat_value = ::peg::__alloc::vec::Vec::new();
+                                loop {
+                                    let __pos = __repeat_pos;
+                                    let __step_res =
+                                        __parse_labeled(__input, __state, __err_state, __pos);
+                                    match __step_res {
+                                        ::peg::RuleResult::Matched(__newpos, __value) => {
+                                            __repeat_pos = __newpos;
+                                            __repeat_value.push(__value);
+                                        }
+                                        ::peg::RuleResult::Failed => {
+                                            break;
+                                        }
+                                    }
+                                }
+                                ::peg::RuleResult::Matched(__repeat_pos, __repeat_value)
+                            };
                             match __seq_res {
-                                ::peg::RuleResult::Matched(__pos, action) => {
-                                    ::peg::RuleResult::Matched(
-                                        __pos,
-                                        (|| PrecedenceOperator {
-                                            span,
-                                            elements,
-                                            action,
-                                        })(),
-                                    )
+                                ::peg::RuleResult::Matched(__pos, elements) => {
+                                    let __seq_res =
+                                        __parse_BRACE_GROUP(__input, __state, __err_state, __pos);
+                                    match __seq_res {
+                                        ::peg::RuleResult::Matched(__pos, action) => {
+                                            ::peg::RuleResult::Matched(
+                                                __pos,
+                                                (|| PrecedenceOperator {
+                                                    span,
+                                                    elements,
+                                                    action,
+                                                })(
+                                                ),
+                                            )
+                                        }
+                                        ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                                    }
                                 }
                                 ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                             }
@@ -2738,49 +4085,48 @@ pub mod peg {
                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_sp<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Span> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        __input.next_span(__pos)
+        if __err_state.enter_rule("sp", __pos) {
+            let __rule_result: ::peg::RuleResult<Span> = { __input.next_span(__pos) };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
     }
     fn __parse_KEYWORD<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<()> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __choice_res =
-                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "pub") {
-                    ::peg::RuleResult::Matched(__pos, __val) => {
-                        ::peg::RuleResult::Matched(__pos, __val)
-                    }
-                    ::peg::RuleResult::Failed => {
-                        __err_state.mark_failure(__pos, "\"pub\"");
-                        ::peg::RuleResult::Failed
-                    }
-                };
-            match __choice_res {
-                ::peg::RuleResult::Matched(__pos, __value) => {
-                    ::peg::RuleResult::Matched(__pos, __value)
-                }
-                ::peg::RuleResult::Failed => {
+        if __err_state.enter_rule("KEYWORD", __pos) {
+            let __rule_result: ::peg::RuleResult<()> = {
+                {
                     let __choice_res =
-                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "crate") {
+                        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "pub") {
                             ::peg::RuleResult::Matched(__pos, __val) => {
                                 ::peg::RuleResult::Matched(__pos, __val)
                             }
                             ::peg::RuleResult::Failed => {
-                                __err_state.mark_failure(__pos, "\"crate\"");
+                                __err_state.mark_failure(
+                                    __pos,
+                                    ::peg::error::Expected::Literal("\"pub\""),
+                                );
                                 ::peg::RuleResult::Failed
                             }
                         };
@@ -2790,13 +4136,16 @@ pub mod peg {
                         }
                         ::peg::RuleResult::Failed => {
                             let __choice_res = match ::peg::ParseLiteral::parse_string_literal(
-                                __input, __pos, "rule",
+                                __input, __pos, "crate",
                             ) {
                                 ::peg::RuleResult::Matched(__pos, __val) => {
                                     ::peg::RuleResult::Matched(__pos, __val)
                                 }
                                 ::peg::RuleResult::Failed => {
-                                    __err_state.mark_failure(__pos, "\"rule\"");
+                                    __err_state.mark_failure(
+                                        __pos,
+                                        ::peg::error::Expected::Literal("\"crate\""),
+                                    );
                                     ::peg::RuleResult::Failed
                                 }
                             };
@@ -2807,13 +4156,16 @@ pub mod peg {
                                 ::peg::RuleResult::Failed => {
                                     let __choice_res =
                                         match ::peg::ParseLiteral::parse_string_literal(
-                                            __input, __pos, "use",
+                                            __input, __pos, "rule",
                                         ) {
                                             ::peg::RuleResult::Matched(__pos, __val) => {
                                                 ::peg::RuleResult::Matched(__pos, __val)
                                             }
                                             ::peg::RuleResult::Failed => {
-                                                __err_state.mark_failure(__pos, "\"use\"");
+                                                __err_state.mark_failure(
+                                                    __pos,
+                                                    ::peg::error::Expected::Literal("\"rule\""),
+                                                );
                                                 ::peg::RuleResult::Failed
                                             }
                                         };
@@ -2822,15 +4174,47 @@ pub mod peg {
                                             ::peg::RuleResult::Matched(__pos, __value)
                                         }
                                         ::peg::RuleResult::Failed => {
-                                            match ::peg::ParseLiteral::parse_string_literal(
-                                                __input, __pos, "type",
-                                            ) {
-                                                ::peg::RuleResult::Matched(__pos, __val) => {
-                                                    ::peg::RuleResult::Matched(__pos, __val)
+                                            let __choice_res =
+                                                match ::peg::ParseLiteral::parse_string_literal(
+                                                    __input, __pos, "use",
+                                                ) {
+                                                    ::peg::RuleResult::Matched(__pos, __val) => {
+                                                        ::peg::RuleResult::Matched(__pos, __val)
+                                                    }
+                                                    ::peg::RuleResult::Failed => {
+                                                        __err_state.mark_failure(
+                                                            __pos,
+                                                            ::peg::error::Expected::Literal(
+                                                                "\"use\"",
+                                                            ),
+                                                        );
+                                                        ::peg::RuleResult::Failed
+                                                    }
+                                                };
+                                            match __choice_res {
+                                                ::peg::RuleResult::Matched(__pos, __value) => {
+                                                    ::peg::RuleResult::Matched(__pos, __value)
                                                 }
                                                 ::peg::RuleResult::Failed => {
-                                                    __err_state.mark_failure(__pos, "\"type\"");
-                                                    ::peg::RuleResult::Failed
+                                                    match ::peg::ParseLiteral::parse_string_literal(
+                                                        __input, __pos, "type",
+                                                    ) {
+                                                        ::peg::RuleResult::Matched(
+                                                            __pos,
+                                                            __val,
+                                                        ) => {
+                                                            ::peg::RuleResult::Matched(__pos, __val)
+                                                        }
+                                                        ::peg::RuleResult::Failed => {
+                                                            __err_state.mark_failure(
+                                                                __pos,
+                                                                ::peg::error::Expected::Literal(
+                                                                    "\"type\"",
+                                                                ),
+                                                            );
+                                                            ::peg::RuleResult::Failed
+                                                        }
+                                                    }
                                                 }
                                             }
                                         }
@@ -2840,113 +4224,206 @@ pub mod peg {
                         }
                     }
                 }
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_IDENT<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Ident> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        {
-            let __seq_res = {
-                __err_state.suppress_fail += 1;
-                let __assert_res = match __parse_KEYWORD(__input, __state, __err_state, __pos) {
-                    ::peg::RuleResult::Matched(pos, _) => ::peg::RuleResult::Matched(pos, ()),
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                };
-                __err_state.suppress_fail -= 1;
-                match __assert_res {
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, ()),
-                    ::peg::RuleResult::Matched(..) => ::peg::RuleResult::Failed,
-                }
-            };
-            match __seq_res {
-                ::peg::RuleResult::Matched(__pos, _) => {
-                    let __seq_res = __input.ident(__pos);
+        if __err_state.enter_rule("IDENT", __pos) {
+            let __rule_result: ::peg::RuleResult<Ident> = {
+                {
+                    let __seq_res = {
+                        __err_state.suppress_fail += 1;
+                        let __assert_res =
+                            match __parse_KEYWORD(__input, __state, __err_state, __pos) {
+                                ::peg::RuleResult::Matched(pos, _) => {
+                                    ::peg::RuleResult::Matched(pos, ())
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            };
+                        __err_state.suppress_fail -= 1;
+                        match __assert_res {
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, ()),
+                            ::peg::RuleResult::Matched(..) => ::peg::RuleResult::Failed,
+                        }
+                    };
                     match __seq_res {
-                        ::peg::RuleResult::Matched(__pos, i) => {
-                            ::peg::RuleResult::Matched(__pos, (|| i)())
+                        ::peg::RuleResult::Matched(__pos, _) => {
+                            let __seq_res = __input.ident(__pos);
+                            match __seq_res {
+                                ::peg::RuleResult::Matched(__pos, i) => {
+                                    ::peg::RuleResult::Matched(__pos, (|| i)())
+                                }
+                                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                            }
                         }
                         ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                     }
                 }
-                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_LITERAL<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<Literal> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("LITERAL", __pos) {
+            let __rule_result: ::peg::RuleResult<Literal> = { __input.literal(__pos) };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_CASE_INSENSITIVE_LITERAL<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
+        __pos: usize,
+    ) -> ::peg::RuleResult<Literal> {
+        #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+        if __err_state.enter_rule("CASE_INSENSITIVE_LITERAL", __pos) {
+            let __rule_result: ::peg::RuleResult<Literal> =
+                { __input.suffixed_string_literal(__pos, "i") };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    }
+    fn __parse_BYTE_STRING_LITERAL<'input>(
+        __input: &'input Input,
+        __state: &mut ParseState<'input>,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Literal> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        __input.literal(__pos)
+        if __err_state.enter_rule("BYTE_STRING_LITERAL", __pos) {
+            let __rule_result: ::peg::RuleResult<Literal> = { __input.byte_string_literal(__pos) };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
     }
     fn __parse_PAREN_GROUP<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Group> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        __input.group(__pos, Delimiter::Parenthesis)
+        if __err_state.enter_rule("PAREN_GROUP", __pos) {
+            let __rule_result: ::peg::RuleResult<Group> =
+                { __input.group(__pos, Delimiter::Parenthesis) };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
     }
     fn __parse_BRACE_GROUP<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Group> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        __input.group(__pos, Delimiter::Brace)
+        if __err_state.enter_rule("BRACE_GROUP", __pos) {
+            let __rule_result: ::peg::RuleResult<Group> =
+                { __input.group(__pos, Delimiter::Brace) };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
     }
     fn __parse_BRACKET_GROUP<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<Group> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        __input.group(__pos, Delimiter::Bracket)
+        if __err_state.enter_rule("BRACKET_GROUP", __pos) {
+            let __rule_result: ::peg::RuleResult<Group> =
+                { __input.group(__pos, Delimiter::Bracket) };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
     }
     fn __parse_LIFETIME<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<()> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "'") {
-            ::peg::RuleResult::Matched(__pos, __val) => {
-                let __seq_res = match __parse_IDENT(__input, __state, __err_state, __pos) {
-                    ::peg::RuleResult::Matched(pos, _) => ::peg::RuleResult::Matched(pos, ()),
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
-                };
-                match __seq_res {
-                    ::peg::RuleResult::Matched(__pos, _) => ::peg::RuleResult::Matched(__pos, ()),
-                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+        if __err_state.enter_rule("LIFETIME", __pos) {
+            let __rule_result: ::peg::RuleResult<()> = {
+                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, "'") {
+                    ::peg::RuleResult::Matched(__pos, __val) => {
+                        let __seq_res = match __parse_IDENT(__input, __state, __err_state, __pos) {
+                            ::peg::RuleResult::Matched(pos, _) => {
+                                ::peg::RuleResult::Matched(pos, ())
+                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        };
+                        match __seq_res {
+                            ::peg::RuleResult::Matched(__pos, _) => {
+                                ::peg::RuleResult::Matched(__pos, ())
+                            }
+                            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                        }
+                    }
+                    ::peg::RuleResult::Failed => {
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Literal("\"'\""));
+                        ::peg::RuleResult::Failed
+                    }
                 }
-            }
-            ::peg::RuleResult::Failed => {
-                __err_state.mark_failure(__pos, "\"'\"");
-                ::peg::RuleResult::Failed
-            }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
     fn __parse_INTEGER<'input>(
         __input: &'input Input,
         __state: &mut ParseState<'input>,
-        __err_state: &mut ::peg::error::ErrorState,
+        __err_state: &mut ::peg::error::ErrorState<'_>,
         __pos: usize,
     ) -> ::peg::RuleResult<()> {
         #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
-        match __parse_LITERAL(__input, __state, __err_state, __pos) {
-            ::peg::RuleResult::Matched(pos, _) => ::peg::RuleResult::Matched(pos, ()),
-            ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+        if __err_state.enter_rule("INTEGER", __pos) {
+            let __rule_result: ::peg::RuleResult<()> = {
+                match __parse_LITERAL(__input, __state, __err_state, __pos) {
+                    ::peg::RuleResult::Matched(pos, _) => ::peg::RuleResult::Matched(pos, ()),
+                    ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+                }
+            };
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
         }
     }
 }
@@ -10,6 +10,7 @@ extern crate peg_runtime as peg;
 
 mod analysis;
 mod ast;
+mod generate;
 mod grammar;
 mod tokens;
 mod translate;
@@ -79,6 +79,38 @@ impl FlatTokenStream {
         }
     }
 
+    /// A string literal with `suffix`, like `"abc"i`, returned without the suffix.
+    pub fn suffixed_string_literal(&self, pos: usize, suffix: &str) -> RuleResult<Literal> {
+        let lit = match self.tokens.get(pos) {
+            Some(Token::Literal(l)) => l,
+            _ => return RuleResult::Failed,
+        };
+
+        let text = lit.to_string();
+        let unsuffixed = match text.strip_suffix(suffix) {
+            Some(t) if (t.starts_with('"') || t.starts_with('r')) && (t.ends_with('"') || t.ends_with('#')) => t,
+            _ => return RuleResult::Failed,
+        };
+
+        match unsuffixed.parse::<TokenStream>().ok().and_then(|ts| ts.into_iter().next()) {
+            Some(TokenTree::Literal(mut l)) => {
+                l.set_span(lit.span());
+                RuleResult::Matched(pos + 1, l)
+            }
+            _ => RuleResult::Failed,
+        }
+    }
+
+    /// A byte string literal, like `b"abc"` or `br"abc"`.
+    pub fn byte_string_literal(&self, pos: usize) -> RuleResult<Literal> {
+        match self.tokens.get(pos) {
+            Some(Token::Literal(l)) if l.to_string().starts_with("b\"") || l.to_string().starts_with("br") => {
+                RuleResult::Matched(pos + 1, l.clone())
+            }
+            _ => RuleResult::Failed,
+        }
+    }
+
     pub fn group(&self, pos: usize, delim: Delimiter) -> RuleResult<Group> {
         match self.tokens.get(pos) {
             Some(Token::Begin(g, n)) if g.delimiter() == delim => {
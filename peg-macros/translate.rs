@@ -1,10 +1,12 @@
 use proc_macro2::{Group, Ident, Literal, Span, TokenStream, TokenTree};
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 
 use quote::{format_ident, quote, quote_spanned};
 
 pub use self::Expr::*;
 use crate::analysis;
+use crate::generate;
 use crate::ast::*;
 
 pub fn report_error(span: Span, msg: String) -> TokenStream {
@@ -17,7 +19,7 @@ pub fn report_error_expr(span: Span, msg: String) -> TokenStream {
 }
 
 /// Test if the group begins with a specific marker character, and if so, return the remaining tokens.
-fn group_check_prefix(group: &Group, prefix: char) -> Option<TokenStream> {
+pub(crate) fn group_check_prefix(group: &Group, prefix: char) -> Option<TokenStream> {
     let mut iter = group.stream().into_iter();
     match iter.next() {
         Some(TokenTree::Punct(p)) if p.as_char() == prefix => {
@@ -54,16 +56,111 @@ struct Context<'a> {
     parse_state_ty: TokenStream,
     extra_args_call: TokenStream,
     extra_args_def: TokenStream,
+    recovery: bool,
+    incremental: bool,
+    max_depth: TokenStream,
+    uncacheable: &'a HashSet<String>,
+    left_rec_groups: &'a [analysis::LeftRecGroup],
+    callsites: &'a Cell<usize>,
+    coverage: &'a RefCell<Vec<CoveragePoint>>,
+
+    /// The grammar uses `~`, and isn't incremental, so a cut drops the cache entries
+    /// before the earliest position the parser could still backtrack to.
+    prune_on_cut: bool,
+
+    /// Compiling an alternative of a choice, other than the last, in which a `~`
+    /// commits to the alternative by setting `__cut`.
+    cut_alternative: bool,
+
+    /// The grammar uses `indent!()`, `dedent!()` or `same_indent!()`, so the state has an
+    /// indentation stack, restored on backtracking and part of each cache key.
+    indentation: bool,
+
+    /// The `#![skip]` rule, called between the elements of sequences, or `None` in a
+    /// `#[lexical]` rule and the skip rule itself.
+    skip: Option<&'a Ident>,
+}
+
+/// A rule or choice alternative counted with the `coverage` feature. Its index in
+/// `Context::coverage` is its slot in the generated `__COVERAGE_HITS`.
+struct CoveragePoint {
+    rule: String,
+    choice: Option<(usize, usize)>,
+    location: String,
+}
+
+impl CoveragePoint {
+    fn id(&self) -> String {
+        match self.choice {
+            Some((choice, alternative)) => format!("{}/{}/{}", self.rule, choice, alternative),
+            None => self.rule.clone(),
+        }
+    }
+}
+
+/// Where `span` starts in the grammar source, as `file:line:column`. The compiler only
+/// reports this inside a procedural macro, and not to the bootstrap binary.
+#[cfg(feature = "coverage")]
+fn span_location(span: Span) -> String {
+    if proc_macro::is_available() {
+        let span = span.unwrap();
+        format!("{}:{}:{}", span.file(), span.line(), span.column())
+    } else {
+        String::new()
+    }
+}
+
+#[cfg(not(feature = "coverage"))]
+fn span_location(_span: Span) -> String {
+    String::new()
+}
+
+fn add_coverage_point(context: &Context, point: CoveragePoint) -> usize {
+    let mut points = context.coverage.borrow_mut();
+    points.push(point);
+    points.len() - 1
+}
+
+/// Count the matches of `expr` in the coverage point `index`, except while reparsing to
+/// report an error.
+fn count_match(span: Span, index: usize, expr: TokenStream) -> TokenStream {
+    quote_spanned! { span =>
+        __coverage_count_match(#index, !__err_state.reparsing_on_error, #expr)
+    }
 }
 
 pub(crate) fn compile_grammar(grammar: &Grammar) -> TokenStream {
     let name = &grammar.name;
-    let mut items = vec![make_parse_state(&grammar)];
-
     let analysis = analysis::check(&grammar);
 
+    // Rules whose cache keys were rejected are compiled without a cache, so the errors
+    // aren't buried under those from the generated code.
+    let uncacheable: HashSet<String> = analysis.cache_keys.iter().map(|e| e.rule.clone()).collect();
+
+    let prune_on_cut = !grammar.incremental && grammar.iter_rules().any(|rule| contains_cut(&rule.expr, true));
+    let indentation = grammar.iter_rules().any(|rule| contains_expr(&rule.expr, true, |e| matches!(e, IndentExpr(_))));
+
+    let mut items = vec![make_parse_state(&grammar, &uncacheable, &analysis.left_rec_groups, prune_on_cut, indentation)];
+
     let grammar_lifetime_params = ty_params_slice(&grammar.lifetime_params);
 
+    // A skip rule that can't be called is reported once here, rather than between
+    // every pair of elements.
+    let skip = match &grammar.skip {
+        Some(skip) => match analysis.rules.get(&skip.to_string()) {
+            None => {
+                items.push(report_error(skip.span(), format!("undefined skip rule `{}`", skip)));
+                None
+            }
+            Some(rule) if !rule.params.is_empty() => {
+                items.push(report_error(skip.span(), format!("skip rule `{}` must not take parameters", skip)));
+                None
+            }
+            Some(_) => Some(skip),
+        },
+        None => None,
+    };
+
     let context = &Context {
         rules: &analysis.rules,
         rules_from_args: HashSet::new(),
@@ -72,21 +169,35 @@ pub(crate) fn compile_grammar(grammar: &Grammar) -> TokenStream {
         parse_state_ty: quote!(&mut ParseState<'input #(, #grammar_lifetime_params)*>),
         extra_args_call: extra_args_call(grammar),
         extra_args_def: extra_args_def(grammar),
+        recovery: !analysis.recovering_rules.is_empty(),
+        incremental: grammar.incremental,
+        max_depth: match &grammar.max_depth {
+            Some(depth) => quote_spanned!(depth.span() => ::peg::__core::option::Option::Some(#depth)),
+            None => quote!(::peg::__core::option::Option::None),
+        },
+        uncacheable: &uncacheable,
+        left_rec_groups: &analysis.left_rec_groups,
+        callsites: &Cell::new(0),
+        coverage: &RefCell::new(Vec::new()),
+        prune_on_cut,
+        cut_alternative: false,
+        indentation,
+        skip,
     };
 
     let mut seen_rule_names = HashSet::new();
+    let mut session_methods = Vec::new();
 
     for item in &grammar.items {
         match item {
             Item::Use(tt) => items.push(tt.clone()),
             Item::Rule(rule) => {
                 if seen_rule_names.insert(rule.name.to_string()) {
-                    if rule.cache.is_some() && !(rule.params.is_empty() && rule.ty_params.is_none()) {
+                    if matches!(rule.cache, Some(Cache { shared: true, .. })) && rule.ret_type.is_none() {
                         items.push(report_error(
                             rule.name.span(),
-                            format!("rules with generics or parameters cannot use #[cache] or #[cache_left_rec]"),
+                            "#[cache(shared)] is only meaningful for rules that return a value".to_owned(),
                         ));
-                        continue;
                     }
 
                     if rule.visibility.is_some() {
@@ -101,11 +212,26 @@ pub(crate) fn compile_grammar(grammar: &Grammar) -> TokenStream {
                         }
 
                         items.push(compile_rule_export(context, rule));
+                        if context.incremental {
+                            session_methods.push(compile_rule_session_method(context, rule));
+                        }
                     } else if rule.no_eof {
                         items.push(report_error(
                             rule.name.span(),
                             format!("#[no_eof] is only meaningful for `pub rule`"),
                         ));
+                    } else if rule.recover {
+                        items.push(report_error(
+                            rule.name.span(),
+                            "#[recover] is only meaningful for `pub rule`".to_owned(),
+                        ));
+                    }
+
+                    if rule.lexical && grammar.skip.is_none() {
+                        items.push(report_error(
+                            rule.name.span(),
+                            "#[lexical] is only meaningful with #![skip]".to_owned(),
+                        ));
                     }
 
                     items.push(compile_rule(context, rule));
@@ -129,10 +255,46 @@ pub(crate) fn compile_grammar(grammar: &Grammar) -> TokenStream {
         errors.push(report_error(rec.span, rec.msg()));
     }
 
+    for rec in &analysis.left_rec_params {
+        errors.push(report_error(rec.span, rec.msg()));
+    }
+
     for rec in &analysis.loop_nullability {
         errors.push(report_error(rec.span, rec.msg()));
     }
 
+    for rec in &analysis.cached_recovery {
+        errors.push(report_error(rec.span, rec.msg()));
+    }
+
+    for rec in &analysis.incremental_borrow {
+        errors.push(report_error(rec.span, rec.msg()));
+    }
+
+    for rec in &analysis.cache_keys {
+        errors.push(report_error(rec.span, rec.msg()));
+    }
+
+    for rec in &analysis.lexical_calls {
+        errors.push(report_error(rec.span, rec.msg()));
+    }
+
+    if cfg!(feature = "coverage") {
+        items.push(coverage_items(grammar, &context.coverage.borrow()));
+    }
+
+    if grammar.generator {
+        items.push(generate::compile_generator(grammar, &analysis.rules));
+    }
+
+    if grammar.incremental {
+        items.push(quote_spanned! { Span::mixed_site() =>
+            impl<#(#grammar_lifetime_params),*> Session<#(#grammar_lifetime_params),*> {
+                #(#session_methods)*
+            }
+        });
+    }
+
     quote_spanned! { Span::mixed_site() =>
         #doc
         #visibility mod #name {
@@ -147,38 +309,279 @@ pub(crate) fn compile_grammar(grammar: &Grammar) -> TokenStream {
     }
 }
 
-fn make_parse_state(grammar: &Grammar) -> TokenStream {
+/// The table of coverage points and their counts, shared by all parses with the grammar.
+fn coverage_items(grammar: &Grammar, points: &[CoveragePoint]) -> TokenStream {
+    let grammar_name = grammar.name.to_string();
+    let len = points.len();
+    let points = points.iter().map(|point| {
+        let rule = &point.rule;
+        let id = point.id();
+        let location = &point.location;
+        quote! { ::peg::coverage::Point { grammar: #grammar_name, rule: #rule, id: #id, location: #location } }
+    });
+
+    quote_spanned! { Span::mixed_site() =>
+        #[cfg(target_has_atomic = "ptr")]
+        static __COVERAGE_POINTS: [::peg::coverage::Point; #len] = [#(#points),*];
+
+        #[cfg(target_has_atomic = "ptr")]
+        const __COVERAGE_ZERO: ::peg::__core::sync::atomic::AtomicUsize = ::peg::__core::sync::atomic::AtomicUsize::new(0);
+        #[cfg(target_has_atomic = "ptr")]
+        static __COVERAGE_HITS: [::peg::__core::sync::atomic::AtomicUsize; #len] = [__COVERAGE_ZERO; #len];
+
+        // The counters are atomic, so nothing is counted on targets without atomic
+        // pointer-sized operations.
+        #[cfg(target_has_atomic = "ptr")]
+        #[inline]
+        fn __coverage_count_match<T>(index: usize, count: bool, result: ::peg::RuleResult<T>) -> ::peg::RuleResult<T> {
+            ::peg::coverage::count_match(&__COVERAGE_HITS[index], count, result)
+        }
+
+        #[cfg(not(target_has_atomic = "ptr"))]
+        #[inline(always)]
+        fn __coverage_count_match<T>(_index: usize, _count: bool, result: ::peg::RuleResult<T>) -> ::peg::RuleResult<T> {
+            result
+        }
+
+        /// The number of times each rule and alternative of the grammar has matched in all
+        /// parses so far.
+        #[cfg(target_has_atomic = "ptr")]
+        #[allow(dead_code)]
+        pub fn coverage() -> ::peg::coverage::Coverage {
+            ::peg::coverage::Coverage::new(&__COVERAGE_POINTS, &__COVERAGE_HITS)
+        }
+    }
+}
+
+fn make_parse_state(grammar: &Grammar, uncacheable: &HashSet<String>, left_rec_groups: &[analysis::LeftRecGroup], prune_on_cut: bool, indentation: bool) -> TokenStream {
     let span = Span::mixed_site();
     let grammar_lifetime_params = ty_params_slice(&grammar.lifetime_params);
     let mut cache_fields_def: Vec<TokenStream> = Vec::new();
-    let mut cache_fields: Vec<Ident> = Vec::new();
+    let mut cache_apply_edit: Vec<TokenStream> = Vec::new();
+    let mut rebind_fields: Vec<TokenStream> = Vec::new();
+    let mut cache_new: Vec<TokenStream> = Vec::new();
+    let mut cache_drop_before: Vec<TokenStream> = Vec::new();
     for rule in grammar.iter_rules() {
-        if rule.cache.is_some() && rule.params.is_empty() && rule.ty_params.is_none() {
+        if rule.cache.is_some() && !uncacheable.contains(&rule.name.to_string()) {
             let name = format_ident!("{}_cache", rule.name);
-            let ret_ty = rule.ret_type.clone().unwrap_or_else(|| quote!(()));
+            let ret_ty = rule_ret_ty(rule);
+            // With indentation, an entry also holds the indentation stack the rule left
+            let entry_ty = if grammar.incremental {
+                quote_spanned! { span => ::peg::incremental::CacheEntry<#ret_ty> }
+            } else if indentation {
+                quote_spanned! { span => (::peg::RuleResult<#ret_ty>, usize) }
+            } else {
+                quote_spanned! { span => ::peg::RuleResult<#ret_ty> }
+            };
+            let entry_ty = if rule.cache_keyed() || indentation {
+                let key_ty = cache_key_ty(rule, indentation);
+                cache_apply_edit.push(quote_spanned! { span => ::peg::incremental::apply_edit_keyed(&mut self.#name, start, end, new_len); });
+                quote_spanned! { span => ::peg::__alloc::vec::Vec<(#key_ty, #entry_ty)> }
+            } else {
+                cache_apply_edit.push(quote_spanned! { span => ::peg::incremental::apply_edit(&mut self.#name, start, end, new_len); });
+                entry_ty
+            };
+            let storage = match rule.cache.as_ref().and_then(|c| c.storage).or(grammar.cache_storage) {
+                None => quote_spanned! { span => ::peg::memo::Storage::Auto },
+                Some(CacheStorage::Dense) => quote_spanned! { span => ::peg::memo::Storage::Dense },
+                Some(CacheStorage::Chunked) => quote_spanned! { span => ::peg::memo::Storage::Chunked },
+                Some(CacheStorage::Hash) => quote_spanned! { span => ::peg::memo::Storage::Hash },
+            };
             cache_fields_def.push(
-                quote_spanned! { span =>  #name: ::std::collections::HashMap<usize, ::peg::RuleResult<#ret_ty>> },
+                quote_spanned! { span =>  #name: ::peg::memo::MemoTable<#entry_ty> },
             );
-            cache_fields.push(name);
+            // Borrowing the input is reported by analysis; don't pile a lifetime error on top.
+            rebind_fields.push(if analysis::mentions_input_lifetime(entry_ty.clone()) {
+                quote_spanned! { span => #name: ::peg::memo::MemoTable::new(#storage, None) }
+            } else {
+                quote_spanned! { span => #name: self.#name }
+            });
+            cache_new.push(quote_spanned! { span => #name: ::peg::memo::MemoTable::new(#storage, __input_len) });
+            cache_drop_before.push(quote_spanned! { span => self.#name.drop_before(pos); });
+        }
+    }
+
+    // Rules in left-recursive cycles keep their intermediate results only while a result
+    // is being grown, so they start out empty.
+    let mut left_rec_fields = Vec::new();
+    if !left_rec_groups.is_empty() {
+        cache_fields_def.push(quote_spanned! { span => lr_heads: ::peg::leftrec::Heads });
+        left_rec_fields.push(format_ident!("lr_heads"));
+    }
+    for rule in grammar.iter_rules() {
+        if left_rec_groups.iter().any(|group| group.members.contains(&rule.name.to_string())) {
+            let name = format_ident!("{}_lr", rule.name);
+            let ret_ty = rule_ret_ty(rule);
+            cache_fields_def.push(quote_spanned! { span => #name: ::peg::leftrec::Memo<#ret_ty> });
+            left_rec_fields.push(name);
         }
     }
+    for name in &left_rec_fields {
+        cache_new.push(quote_spanned! { span => #name: ::peg::__core::default::Default::default() });
+        rebind_fields.push(quote_spanned! { span => #name: ::peg::__core::default::Default::default() });
+    }
+
+    // The cache keys name indentation stacks by their number, so the stacks are kept
+    // along with the caches.
+    if indentation {
+        cache_fields_def.push(quote_spanned! { span => indents: ::peg::indent::IndentStack });
+        cache_new.push(quote_spanned! { span => indents: ::peg::indent::IndentStack::new() });
+        rebind_fields.push(quote_spanned! { span => indents: self.indents.reset() });
+    }
+
+    if !grammar.incremental {
+        // With cuts, the state counts the expressions that could still backtrack, and where
+        // the outermost of them started. No rule is tried before there again.
+        let (backtrack_fields, backtrack_new, backtrack_methods) = if prune_on_cut {
+            (
+                quote_spanned! { span => backtrack_points: usize, backtrack_start: usize, },
+                quote_spanned! { span => backtrack_points: 0, backtrack_start: 0, },
+                quote_spanned! { span =>
+                    fn open_backtrack(&mut self, pos: usize) {
+                        if self.backtrack_points == 0 {
+                            self.backtrack_start = pos;
+                        }
+                        self.backtrack_points += 1;
+                    }
+
+                    fn close_backtrack(&mut self) {
+                        self.backtrack_points -= 1;
+                    }
+
+                    fn cut(&mut self, pos: usize) {
+                        let pos = if self.backtrack_points == 0 { pos } else { self.backtrack_start };
+                        #(#cache_drop_before)*
+                    }
+                },
+            )
+        } else {
+            (quote!(), quote!(), quote!())
+        };
+
+        return quote_spanned! { span =>
+            #[allow(unused_parens)]
+            struct ParseState<'input #(, #grammar_lifetime_params)*> {
+                _phantom: ::peg::__core::marker::PhantomData<(&'input () #(, &#grammar_lifetime_params ())*)>,
+                #backtrack_fields
+                #(#cache_fields_def),*
+            }
+
+            impl<'input #(, #grammar_lifetime_params)*> ParseState<'input #(, #grammar_lifetime_params)*> {
+                fn new(__input_len: Option<usize>) -> ParseState<'input #(, #grammar_lifetime_params)*> {
+                    ParseState {
+                        _phantom: ::peg::__core::marker::PhantomData,
+                        #backtrack_new
+                        #(#cache_new),*
+                    }
+                }
+
+                #backtrack_methods
+            }
+        };
+    }
 
+    // An incremental grammar's caches outlive a single parse, so the state also tracks how far
+    // into the input the current rule has looked, and can be moved between input lifetimes
+    // (which is possible because analysis rejects cached rule types that borrow the input).
     quote_spanned! { span =>
         #[allow(unused_parens)]
         struct ParseState<'input #(, #grammar_lifetime_params)*> {
-            _phantom: ::std::marker::PhantomData<(&'input () #(, &#grammar_lifetime_params ())*)>,
+            _phantom: ::peg::__core::marker::PhantomData<(&'input () #(, &#grammar_lifetime_params ())*)>,
+            examined_end: usize,
             #(#cache_fields_def),*
         }
 
+        #[allow(unused)]
         impl<'input #(, #grammar_lifetime_params)*> ParseState<'input #(, #grammar_lifetime_params)*> {
-            fn new() -> ParseState<'input #(, #grammar_lifetime_params)*> {
+            fn new(__input_len: Option<usize>) -> ParseState<'input #(, #grammar_lifetime_params)*> {
                 ParseState {
-                    _phantom: ::std::marker::PhantomData,
-                    #(#cache_fields: ::std::collections::HashMap::new()),*
+                    _phantom: ::peg::__core::marker::PhantomData,
+                    examined_end: 0,
+                    #(#cache_new),*
                 }
             }
+
+            #[inline(always)]
+            fn examine(&mut self, end: usize) {
+                if end > self.examined_end {
+                    self.examined_end = end;
+                }
+            }
+
+            fn rebind<'other>(self) -> ParseState<'other #(, #grammar_lifetime_params)*> {
+                ParseState {
+                    _phantom: ::peg::__core::marker::PhantomData,
+                    examined_end: 0,
+                    #(#rebind_fields),*
+                }
+            }
+
+            fn apply_edit(&mut self, start: usize, end: usize, new_len: usize) {
+                #(#cache_apply_edit)*
+            }
+        }
+
+        /// A parse session that keeps the results of `#[cache]` rules between parses, so that
+        /// after an edit, only the parts of the input affected by the edit are parsed again.
+        pub struct Session<#(#grammar_lifetime_params),*> {
+            state: ParseState<'static #(, #grammar_lifetime_params)*>,
+            limits: ::peg::Limits,
+        }
+
+        impl<#(#grammar_lifetime_params),*> Session<#(#grammar_lifetime_params),*> {
+            /// Create a session with empty caches.
+            pub fn new() -> Self {
+                Session { state: ParseState::new(None), limits: ::peg::Limits::default() }
+            }
+
+            /// Set the limits for the following parses.
+            pub fn set_limits(&mut self, limits: ::peg::Limits) {
+                self.limits = limits;
+            }
+
+            /// Update the caches for an edit that replaced `range` of the previously parsed input
+            /// with `new_len` positions of new input.
+            pub fn edit(&mut self, range: ::peg::__core::ops::Range<usize>, new_len: usize) {
+                self.state.apply_edit(range.start, range.end, new_len);
+            }
+        }
+
+        impl<#(#grammar_lifetime_params),*> ::peg::__core::default::Default for Session<#(#grammar_lifetime_params),*> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    }
+}
+
+/// The type of a rule's value as seen by its callers: `Rc<T>` for `#[cache(shared)]`.
+fn rule_ret_ty(rule: &Rule) -> TokenStream {
+    let span = rule.span.resolved_at(Span::mixed_site());
+    match &rule.ret_type {
+        Some(ret_ty) if matches!(rule.cache, Some(Cache { shared: true, .. })) => {
+            quote_spanned! { span => ::peg::__alloc::rc::Rc<#ret_ty> }
         }
+        Some(ret_ty) => ret_ty.clone(),
+        None => quote!(()),
+    }
+}
+
+/// The type of the key for a `#[cache]` rule with parameters: its Rust-typed
+/// arguments, the call site if it takes rule closures, and the indentation stack
+/// in a grammar with one.
+fn cache_key_ty(rule: &Rule, indentation: bool) -> TokenStream {
+    let span = rule.span.resolved_at(Span::mixed_site());
+    let mut tys: Vec<TokenStream> = rule.params.iter().filter_map(|param| match &param.ty {
+        RuleParamTy::Rust(ty) => Some(ty.clone()),
+        RuleParamTy::Rule(_) => None,
+    }).collect();
+    if rule.cache_by_callsite() {
+        tys.push(quote_spanned! { span => usize });
     }
+    if indentation {
+        tys.push(quote_spanned! { span => usize });
+    }
+    quote_spanned! { span => (#(#tys,)*) }
 }
 
 fn ty_params_slice(ty_params: &Option<Vec<TokenStream>>) -> &[TokenStream] {
@@ -194,9 +597,13 @@ fn rule_params_list(context: &Context, rule: &Rule) -> Vec<TokenStream> {
         match &param.ty {
             RuleParamTy::Rust(ty) => quote_spanned!{ span => #name: #ty },
             RuleParamTy::Rule(ty) => quote_spanned!{ span =>
-                #name: impl Fn(#input_ty, #parse_state_ty, &mut ::peg::error::ErrorState, usize) -> ::peg::RuleResult<#ty>
+                #name: impl Fn(#input_ty, #parse_state_ty, &mut ::peg::error::ErrorState<'_>, usize) -> ::peg::RuleResult<#ty>
             },
         }
+    }).chain(if rule.cache_by_callsite() {
+        Some(quote_spanned!{ span => __callsite: usize })
+    } else {
+        None
     }).collect()
 }
 
@@ -204,7 +611,8 @@ fn compile_rule(context: &Context, rule: &Rule) -> TokenStream {
     let span = rule.span.resolved_at(Span::mixed_site());
     let ref rule_name = rule.name;
     let name = format_ident!("__parse_{}", rule.name, span=span);
-    let ret_ty = rule.ret_type.clone().unwrap_or_else(|| quote!(()));
+    let value_ty = rule.ret_type.clone().unwrap_or_else(|| quote!(()));
+    let ret_ty = rule_ret_ty(rule);
     let result_used = rule.ret_type.is_some();
     let ty_params = ty_params_slice(&rule.ty_params);
     let input_ty = &context.input_ty;
@@ -216,109 +624,470 @@ fn compile_rule(context: &Context, rule: &Rule) -> TokenStream {
         .rules_from_args
         .extend(rule.params.iter().map(|param| param.name.to_string()));
 
-    let body = compile_expr(&context, &rule.expr, result_used);
+    // The elements of a lexical rule, and of the skip rule, are matched next to each other
+    if rule.lexical || matches!(context.skip, Some(skip) if *skip == rule.name) {
+        context.skip = None;
+    }
+
+    let body = if cfg!(feature = "coverage") {
+        let index = add_coverage_point(&context, CoveragePoint {
+            rule: rule_name.to_string(),
+            choice: None,
+            location: span_location(rule_name.span()),
+        });
+        count_match(span, index, compile_expr(&context, &rule.expr, result_used))
+    } else {
+        compile_expr(&context, &rule.expr, result_used)
+    };
+
+    // While reparsing for an error, the rule stack is recorded with each expected item
+    let context_name = match &rule.context {
+        Some(label) => quote!(#label),
+        None => {
+            let str_rule_name = rule_name.to_string();
+            quote!(#str_rule_name)
+        }
+    };
+    // The rule also fails without running once the parse was stopped by a limit.
+    let body = quote_spanned! { span =>
+        if __err_state.enter_rule(#context_name, __pos) {
+            let __rule_result: ::peg::RuleResult<#value_ty> = {#body};
+            __err_state.leave_rule();
+            __rule_result
+        } else {
+            ::peg::RuleResult::Failed
+        }
+    };
+
+    // A `#[cache(shared)]` rule's value is moved into an `Rc` once, so cache hits
+    // only bump a reference count.
+    let body = if rule.ret_type.is_some() && matches!(rule.cache, Some(Cache { shared: true, .. })) {
+        quote_spanned! { span =>
+            match #body {
+                ::peg::RuleResult::Matched(__pos, __value) => ::peg::RuleResult::Matched(__pos, ::peg::__alloc::rc::Rc::new(__value)),
+                ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
+            }
+        }
+    } else {
+        body
+    };
 
     let wrapped_body = if cfg!(feature = "trace") {
         let str_rule_name = rule_name.to_string();
         quote_spanned! { span => {
-            let loc = ::peg::Parse::position_repr(__input, __pos);
-            println!("[PEG_TRACE] Attempting to match rule `{}` at {}", #str_rule_name, loc);
+            if let Some(__tracer) = &mut __err_state.tracer {
+                __tracer.rule_enter(#str_rule_name, __pos);
+            }
             let __peg_result: ::peg::RuleResult<#ret_ty> = {#body};
-            match __peg_result {
-                ::peg::RuleResult::Matched(epos, v) => {
-                    let eloc = ::peg::Parse::position_repr(__input, epos);
-                    println!("[PEG_TRACE] Matched rule `{}` at {} to {}", #str_rule_name, loc, eloc);
-                    ::peg::RuleResult::Matched(epos, v)
-                }
-                ::peg::RuleResult::Failed => {
-                    println!("[PEG_TRACE] Failed to match rule `{}` at {}", #str_rule_name, loc);
-                    ::peg::RuleResult::Failed
+            if let Some(__tracer) = &mut __err_state.tracer {
+                match __peg_result {
+                    ::peg::RuleResult::Matched(__epos, _) => __tracer.rule_match(#str_rule_name, __pos, __epos),
+                    ::peg::RuleResult::Failed => __tracer.rule_fail(#str_rule_name, __pos),
                 }
             }
+            __peg_result
         }}
     } else {
         body
     };
 
+    let wrapped_body = if cfg!(feature = "stats") {
+        let str_rule_name = rule_name.to_string();
+        quote_spanned! { span => {
+            let __stats_result: ::peg::RuleResult<#ret_ty> = #wrapped_body;
+            let __end = match &__stats_result {
+                ::peg::RuleResult::Matched(__epos, _) => Some(*__epos),
+                ::peg::RuleResult::Failed => None,
+            };
+            __err_state.stats.rule_result(#str_rule_name, __pos, __end);
+            __stats_result
+        }}
+    } else {
+        wrapped_body
+    };
+
     let extra_args_def = &context.extra_args_def;
 
     let rule_params = rule_params_list(&context, rule);
 
-    let fn_body = match &rule.cache {
-        None => wrapped_body,
+    let cache = rule.cache.as_ref().filter(|_| !context.uncacheable.contains(&rule_name.to_string()));
+    let str_rule_name = rule_name.to_string();
+    let left_rec_group = context.left_rec_groups.iter().find_map(|group| {
+        let id = group.members.iter().position(|member| *member == str_rule_name)?;
+        Some((group, id))
+    });
+    let fn_body = match cache {
+        None => wrapped_body.clone(),
         Some(cache_type) => {
             let cache_field = format_ident!("{}_cache", rule.name);
 
+            // A rule with parameters caches a result for each key of argument values at a
+            // position. The types are checked here so that rustc points at the parameter.
+            // With indentation, the key also holds the indentation stack the rule started with.
+            let keyed = rule.cache_keyed() || context.indentation;
+            let (cache_key, cache_get) = if keyed {
+                let mut key = Vec::new();
+                let mut checks = vec![quote_spanned! { span => fn __check_cache_key<K: ::peg::__core::cmp::Eq + ::peg::__core::clone::Clone>() {} }];
+                for param in &rule.params {
+                    if let RuleParamTy::Rust(ty) = &param.ty {
+                        let name = &param.name;
+                        let ty_span = ty.clone().into_iter().next().map_or(span, |t| t.span());
+                        key.push(quote_spanned! { span => ::peg::__core::clone::Clone::clone(&#name) });
+                        checks.push(quote_spanned! { ty_span => __check_cache_key::<#ty>(); });
+                    }
+                }
+                if rule.cache_by_callsite() {
+                    key.push(quote_spanned! { span => __callsite });
+                }
+                if context.indentation {
+                    key.push(quote_spanned! { span => __state.indents.current() });
+                }
+                (
+                    quote_spanned! { span => #(#checks)* let __cache_key = (#(#key,)*); },
+                    quote_spanned! { span => __state.#cache_field.get_keyed(__pos, &__cache_key) },
+                )
+            } else {
+                (quote!(), quote_spanned! { span => __state.#cache_field.get(__pos) })
+            };
+            let cache_insert = |entry: TokenStream| if keyed {
+                quote_spanned! { span => __state.#cache_field.insert_keyed(__pos, __cache_key.clone(), #entry); }
+            } else {
+                quote_spanned! { span => __state.#cache_field.insert(__pos, #entry); }
+            };
+
             let cache_trace = if cfg!(feature = "trace") {
                 let str_rule_name = rule.name.to_string();
                 quote_spanned! { span =>
-                    let loc = ::peg::Parse::position_repr(__input, __pos);
-                    match &entry {
-                        &::peg::RuleResult::Matched(..) => println!("[PEG_TRACE] Cached match of rule {} at {}", #str_rule_name, loc),
-                        &Failed => println!("[PEG_TRACE] Cached fail of rule {} at {}", #str_rule_name, loc),
-                    };
+                    if let Some(__tracer) = &mut __err_state.tracer {
+                        let __end = match &entry {
+                            ::peg::RuleResult::Matched(__epos, _) => Some(*__epos),
+                            ::peg::RuleResult::Failed => None,
+                        };
+                        __tracer.cache_hit(#str_rule_name, __pos, __end);
+                    }
                 }
             } else {
                 quote!()
             };
 
-            match cache_type {
-                Cache::Simple =>
+            let (hit_stats, miss_stats) = if cfg!(feature = "stats") {
+                let str_rule_name = rule.name.to_string();
+                (
+                    quote_spanned! { span => __err_state.stats.cache_hit(#str_rule_name); },
+                    quote_spanned! { span => __err_state.stats.cache_miss(#str_rule_name); },
+                )
+            } else {
+                (quote!(), quote!())
+            };
+
+            // In an incremental grammar, entries also record how far the rule examined the
+            // input, so a session can tell which entries are still valid after an edit.
+            let (cache_hit, examine_begin, examine_end) = if context.incremental {
+                let restore_indent = if context.indentation {
+                    quote_spanned! { span => __state.indents.restore(indent); }
+                } else {
+                    quote!()
+                };
+                (
                     quote_spanned! { span =>
-                        if let Some(entry) = __state.#cache_field.get(&__pos) {
+                        if let Some(entry) = #cache_get {
+                            let ::peg::incremental::CacheEntry { result: entry, examined_end, indent } = entry.clone();
+                            __state.examine(examined_end);
+                            #restore_indent
                             #cache_trace
-                            return entry.clone();
+                            #hit_stats
+                            return entry;
                         }
-
-                        let __rule_result = #wrapped_body;
-                        __state.#cache_field.insert(__pos, __rule_result.clone());
-                        __rule_result
+                        #miss_stats
+                    },
+                    quote_spanned! { span => let __outer_examined = ::peg::__core::mem::replace(&mut __state.examined_end, __pos); },
+                    quote_spanned! { span =>
+                        let __examined_end = ::peg::__core::mem::replace(&mut __state.examined_end, __outer_examined);
+                        __state.examine(__examined_end);
+                    },
+                )
+            } else if context.indentation {
+                (
+                    quote_spanned! { span =>
+                        if let Some(entry) = #cache_get {
+                            let (entry, __indent) = entry.clone();
+                            __state.indents.restore(__indent);
+                            #cache_trace
+                            #hit_stats
+                            return entry;
+                        }
+                        #miss_stats
                     },
-                Cache::Recursive =>
-                    // `#[cache_left_rec] support for recursive rules using the technique described here:
-                    // <https://medium.com/@gvanrossum_83706/left-recursive-peg-grammars-65dab3c580e1>
+                    quote!(),
+                    quote!(),
+                )
+            } else {
+                (
                     quote_spanned! { span =>
-                        if let Some(entry) = __state.#cache_field.get(&__pos) {
+                        if let Some(entry) = #cache_get {
                             #cache_trace
+                            #hit_stats
                             return entry.clone();
                         }
+                        #miss_stats
+                    },
+                    quote!(),
+                    quote!(),
+                )
+            };
 
-                        __state.#cache_field.insert(__pos, ::peg::RuleResult::Failed);
-                        let mut __last_result = ::peg::RuleResult::Failed;
-                        loop {
-                            let __current_result = { #wrapped_body };
-                            match __current_result {
-                                ::peg::RuleResult::Failed => break,
-                                ::peg::RuleResult::Matched(__current_endpos, _) =>
-                                    match __last_result {
-                                        ::peg::RuleResult::Matched(__last_endpos, _) if __current_endpos <= __last_endpos => break,
-                                        _ => {
-                                            __state.#cache_field.insert(__pos, __current_result.clone());
-                                            __last_result = __current_result;
-                                        },
-                                    }
-                            }
-                        }
+            let indent = if context.indentation {
+                quote_spanned! { span => __state.indents.current() }
+            } else {
+                quote_spanned! { span => 0 }
+            };
+            let cache_entry = |result: TokenStream| if context.incremental {
+                quote_spanned! { span => ::peg::incremental::CacheEntry { result: #result, examined_end: __state.examined_end, indent: #indent } }
+            } else if context.indentation {
+                quote_spanned! { span => (#result, #indent) }
+            } else {
+                result
+            };
+
+            match cache_type.kind {
+                CacheKind::Simple => {
+                    let insert = cache_insert(cache_entry(quote_spanned! { span => __rule_result.clone() }));
+                    quote_spanned! { span =>
+                        #cache_key
+                        #cache_hit
+                        #examine_begin
+
+                        let __rule_result = #wrapped_body;
+                        #insert
+                        #examine_end
+                        __rule_result
+                    }
+                }
+                CacheKind::Recursive => {
+                    let seed_insert = cache_insert(cache_entry(quote_spanned! { span => ::peg::RuleResult::Failed }));
+                    let current_insert = cache_insert(cache_entry(quote_spanned! { span => __current_result.clone() }));
+                    let final_insert = if context.incremental {
+                        cache_insert(cache_entry(quote_spanned! { span => __last_result.clone() }))
+                    } else {
+                        quote!()
+                    };
 
-                        return __last_result;
+                    if let Some((group, id)) = left_rec_group {
+                        // The head of a cycle grows its result in the cycle's memos, and caches
+                        // only the final result.
+                        let lr_field = format_ident!("{}_lr", rule.name);
+                        let group_id = group.id;
+                        let member_lr_fields = group.members.iter().map(|member| format_ident!("{}_lr", member));
+                        let indent = lr_indent(&context, span);
+                        let grow = left_rec_loop(&context, span, &wrapped_body, None,
+                            quote_spanned! { span => __state.lr_heads.grow(#group_id, __pos); },
+                            quote_spanned! { span => __state.#lr_field.remember(__pos, __current_result.clone(), #indent); },
+                        );
+                        let final_insert = cache_insert(cache_entry(quote_spanned! { span => __last_result.clone() }));
+                        quote_spanned! { span =>
+                            #cache_key
+                            #cache_hit
+                            #examine_begin
+
+                            __state.lr_heads.begin(#group_id, __pos, #id);
+                            let mut __last_result = ::peg::RuleResult::Failed;
+                            #grow
+                            __state.lr_heads.end();
+                            #(__state.#member_lr_fields.forget(__pos);)*
+
+                            #final_insert
+                            #examine_end
+                            return __last_result;
+                        }
+                    } else {
+                        // `#[cache_left_rec] support for recursive rules using the technique described here:
+                        // <https://medium.com/@gvanrossum_83706/left-recursive-peg-grammars-65dab3c580e1>
+                        let grow = left_rec_loop(&context, span, &wrapped_body, None, quote!(), current_insert);
+                        quote_spanned! { span =>
+                            #cache_key
+                            #cache_hit
+                            #examine_begin
+
+                            #seed_insert
+                            let mut __last_result = ::peg::RuleResult::Failed;
+                            #grow
+
+                            #final_insert
+                            #examine_end
+                            return __last_result;
+                        }
                     }
+                }
+            }
+        }
+    };
+
+    // While another rule of its cycle grows a result at this position, a rule is evaluated
+    // once per iteration and otherwise gives the result it had last, instead of using its cache.
+    let fn_body = if let Some((group, id)) = left_rec_group {
+        let lr_field = format_ident!("{}_lr", rule.name);
+        let group_id = group.id;
+        let indent = lr_indent(&context, span);
+        let evaluate = if matches!(cache, Some(Cache { kind: CacheKind::Recursive, .. })) {
+            // Growing on from the remembered result, which left its own indentation
+            let grow = left_rec_loop(&context, span, &wrapped_body,
+                Some(quote_spanned! { span => __state.#lr_field.indent(__pos) }),
+                quote_spanned! { span => __state.lr_heads.grow(#group_id, __pos); },
+                quote_spanned! { span => __state.#lr_field.remember(__pos, __current_result.clone(), #indent); },
+            );
+            quote_spanned! { span =>
+                let mut __last_result = __state.#lr_field.recall(__pos);
+                #grow
+            }
+        } else {
+            quote_spanned! { span =>
+                let __last_result = #wrapped_body;
+                __state.#lr_field.remember(__pos, __last_result.clone(), #indent);
+            }
+        };
+
+        let recall_indent = if context.indentation {
+            quote_spanned! { span => __state.indents.restore(__state.#lr_field.indent(__pos)); }
+        } else {
+            quote!()
+        };
+
+        quote_spanned! { span =>
+            match __state.lr_heads.enter(#group_id, __pos, #id) {
+                ::peg::leftrec::Involvement::Recall => {
+                    #recall_indent
+                    return __state.#lr_field.recall(__pos);
+                }
+                ::peg::leftrec::Involvement::Evaluate => {
+                    #evaluate
+                    __state.lr_heads.leave(#group_id, __pos, #id);
+                    return __last_result;
+                }
+                ::peg::leftrec::Involvement::Outside => {}
             }
+
+            #fn_body
         }
+    } else {
+        fn_body
+    };
+
+    let count_call = if cfg!(feature = "stats") {
+        quote_spanned! { span => __err_state.stats.rule_call(#str_rule_name); }
+    } else {
+        quote!()
     };
 
     quote_spanned! { span =>
-        fn #name<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty, __state: #parse_state_ty, __err_state: &mut ::peg::error::ErrorState, __pos: usize #extra_args_def #(, #rule_params)*) -> ::peg::RuleResult<#ret_ty> {
+        fn #name<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty, __state: #parse_state_ty, __err_state: &mut ::peg::error::ErrorState<'_>, __pos: usize #extra_args_def #(, #rule_params)*) -> ::peg::RuleResult<#ret_ty> {
             #![allow(non_snake_case, unused, clippy::redundant_closure_call)]
+            #count_call
             #fn_body
         }
     }
 }
 
+/// Evaluate a left-recursive rule's body until its result stops growing, leaving the
+/// longest match in `__last_result`. `last_indent` is the indentation left by the
+/// initial `__last_result`, if it matched. `start` runs before each iteration, and
+/// `update` records a longer `__current_result`.
+fn left_rec_loop(context: &Context, span: Span, body: &TokenStream, last_indent: Option<TokenStream>, start: TokenStream, update: TokenStream) -> TokenStream {
+    let (open, close) = backtrack_point(context, span);
+    // Each iteration starts from the indentation the rule was called with, and the
+    // rule leaves the indentation of its longest match.
+    let (indent_begin, indent_start, indent_update, indent_end) = if context.indentation {
+        let last_indent = last_indent.unwrap_or_else(|| quote_spanned! { span => __indent });
+        (
+            quote_spanned! { span => let __indent = __state.indents.current(); let mut __last_indent = #last_indent; },
+            quote_spanned! { span => __state.indents.restore(__indent); },
+            quote_spanned! { span => __last_indent = __state.indents.current(); },
+            quote_spanned! { span => __state.indents.restore(__last_indent); },
+        )
+    } else {
+        (quote!(), quote!(), quote!(), quote!())
+    };
+    quote_spanned! { span =>
+        #open
+        #indent_begin
+        loop {
+            #indent_start
+            #start
+            let __current_result = { #body };
+            match __current_result {
+                ::peg::RuleResult::Failed => break,
+                ::peg::RuleResult::Matched(__current_endpos, _) =>
+                    match __last_result {
+                        ::peg::RuleResult::Matched(__last_endpos, _) if __current_endpos <= __last_endpos => break,
+                        _ => {
+                            #update
+                            #indent_update
+                            __last_result = __current_result;
+                        },
+                    }
+            }
+        }
+        #indent_end
+        #close
+    }
+}
+
+/// The indentation stack to remember with a result in a left-recursive cycle.
+fn lr_indent(context: &Context, span: Span) -> TokenStream {
+    if context.indentation {
+        quote_spanned! { span => __state.indents.current() }
+    } else {
+        quote_spanned! { span => 0 }
+    }
+}
+
+/// The return type of an exported rule function, the code that returns a successful
+/// `__value` from it, and the final expression reporting a failure.
+fn export_result(context: &Context, rule: &Rule) -> (TokenStream, TokenStream, TokenStream) {
+    let span = rule.span.resolved_at(Span::mixed_site());
+    let ret_ty = rule_ret_ty(rule);
+    let grammar_lifetime_params = context.grammar_lifetime_params;
+    let error_ty = quote_spanned!{ span => ::peg::error::ParseError<PositionRepr<#(#grammar_lifetime_params),*>> };
+
+    if rule.recover {
+        (
+            quote_spanned!{ span => (::peg::__core::option::Option<#ret_ty>, ::peg::__alloc::vec::Vec<#error_ty>) },
+            quote_spanned!{ span => return (Some(__value), __err_state.into_recovered_errors(__input)) },
+            quote_spanned!{ span => (None, __err_state.into_parse_errors(__input)) },
+        )
+    } else if context.recovery {
+        (
+            quote_spanned!{ span => ::peg::__core::result::Result<#ret_ty, #error_ty> },
+            quote_spanned!{ span =>
+                if __err_state.recovered.is_empty() {
+                    return Ok(__value)
+                } else {
+                    return Err(__err_state.into_recovered_errors(__input).remove(0))
+                }
+            },
+            quote_spanned!{ span => Err(__err_state.into_parse_error(__input)) },
+        )
+    } else {
+        (
+            quote_spanned!{ span => ::peg::__core::result::Result<#ret_ty, #error_ty> },
+            quote_spanned!{ span => return Ok(__value) },
+            quote_spanned!{ span => Err(__err_state.into_parse_error(__input)) },
+        )
+    }
+}
+
+fn export_eof_check(rule: &Rule) -> TokenStream {
+    let span = rule.span.resolved_at(Span::mixed_site());
+    if rule.no_eof {
+        quote_spanned!{ span => true }
+    } else {
+        quote_spanned!{ span => ::peg::Parse::is_eof(__input, __pos) }
+    }
+}
+
 fn compile_rule_export(context: &Context, rule: &Rule) -> TokenStream {
     let span = rule.span.resolved_at(Span::mixed_site());
     let doc = &rule.doc;
     let name = &rule.name;
-    let ret_ty = rule.ret_type.clone().unwrap_or_else(|| quote!(()));
     let visibility = &rule.visibility;
     let parse_fn = format_ident!("__parse_{}", rule.name.to_string(), span = name.span());
     let ty_params = ty_params_slice(&rule.ty_params);
@@ -336,45 +1105,235 @@ fn compile_rule_export(context: &Context, rule: &Rule) -> TokenStream {
 
     let extra_args_def = &context.extra_args_def;
     let extra_args_call = &context.extra_args_call;
-    let eof_check = if rule.no_eof {
-        quote_spanned!{ span => true }
+    let eof_check = export_eof_check(rule);
+    let (result_ty, success, failure) = export_result(context, rule);
+    let max_depth = &context.max_depth;
+    let export_fn = format_ident!("__export_{}", rule.name, span = name.span());
+    let name_with_limits = format_ident!("{}_with_limits", rule.name, span = name.span());
+    let limits_doc = format!(" Like [`{}`], but with `limits` on the parse.", rule.name);
+
+    let clear_recovered = if context.recovery {
+        quote_spanned!{ span => __err_state.recovered.clear(); }
     } else {
-        quote_spanned!{ span => ::peg::Parse::is_eof(__input, __pos) }
+        quote!()
+    };
+    let trace_reparse = trace_error_reparse(span);
+
+    // Tracing goes to stdout unless a tracer is passed in
+    let (set_tracer, with_tracer) = if cfg!(feature = "trace") {
+        let name_with_tracer = format_ident!("{}_with_tracer", rule.name, span = name.span());
+        let tracer_doc = format!(" Like [`{}`], reporting the steps of the parse to `tracer`.", rule.name);
+        (
+            quote_spanned! { span =>
+                let mut __print_tracer = ::peg::trace::PrintTracer::new(__input);
+                __err_state.tracer = match __tracer {
+                    Some(__tracer) => Some(__tracer),
+                    None => Some(&mut __print_tracer),
+                };
+            },
+            quote_spanned! { span =>
+                #[doc = #tracer_doc]
+                #[allow(dead_code)]
+                #visibility fn #name_with_tracer<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty, __tracer: &mut dyn ::peg::trace::Tracer #extra_args_def #(, #rule_params)*) -> #result_ty {
+                    #export_fn(__input, &::peg::Limits::default(), Some(__tracer), None #extra_args_call #(, #rule_params_call)*)
+                }
+            },
+        )
+    } else {
+        (quote!(), quote!())
+    };
+
+    // Statistics are taken after the first parse, so they don't include the reparse that
+    // finds the error position
+    let (take_stats, with_stats) = if cfg!(feature = "stats") {
+        let name_with_stats = format_ident!("{}_with_stats", rule.name, span = name.span());
+        let stats_doc = format!(" Like [`{}`], also returning statistics for each rule.", rule.name);
+        (
+            quote_spanned! { span =>
+                if let Some(__stats) = __stats {
+                    *__stats = ::peg::__core::mem::take(&mut __err_state.stats);
+                }
+            },
+            quote_spanned! { span =>
+                #[doc = #stats_doc]
+                #[allow(dead_code)]
+                #visibility fn #name_with_stats<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty #extra_args_def #(, #rule_params)*) -> (#result_ty, ::peg::stats::ParseStats) {
+                    let mut __stats = ::peg::stats::ParseStats::default();
+                    let __result = #export_fn(__input, &::peg::Limits::default(), None, Some(&mut __stats) #extra_args_call #(, #rule_params_call)*);
+                    (__result, __stats)
+                }
+            },
+        )
+    } else {
+        (quote!(), quote!())
     };
 
     quote_spanned! { span =>
         #doc
-        #visibility fn #name<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty #extra_args_def #(, #rule_params)*) -> ::std::result::Result<#ret_ty, ::peg::error::ParseError<PositionRepr<#(#grammar_lifetime_params),*>>> {
+        #visibility fn #name<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty #extra_args_def #(, #rule_params)*) -> #result_ty {
+            #export_fn(__input, &::peg::Limits::default(), None, None #extra_args_call #(, #rule_params_call)*)
+        }
+
+        #[doc = #limits_doc]
+        #[allow(dead_code)]
+        #visibility fn #name_with_limits<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty, __limits: &::peg::Limits #extra_args_def #(, #rule_params)*) -> #result_ty {
+            #export_fn(__input, __limits, None, None #extra_args_call #(, #rule_params_call)*)
+        }
+
+        #with_tracer
+        #with_stats
+
+        fn #export_fn<'input #(, #grammar_lifetime_params)* #(, #ty_params)*>(__input: #input_ty, __limits: &::peg::Limits, __tracer: ::peg::__core::option::Option<&mut dyn ::peg::trace::Tracer>, __stats: ::peg::__core::option::Option<&mut ::peg::stats::ParseStats> #extra_args_def #(, #rule_params)*) -> #result_ty {
             #![allow(non_snake_case, unused)]
 
             let mut __err_state = ::peg::error::ErrorState::new(::peg::Parse::start(__input));
-            let mut __state = ParseState::new();
-            match #parse_fn(__input, &mut __state, &mut __err_state, ::peg::Parse::start(__input) #extra_args_call #(, #rule_params_call)*) {
+            __err_state.set_limits(__limits, #max_depth);
+            #set_tracer
+            let mut __state = ParseState::new(::peg::Parse::input_len(__input));
+            let __result = #parse_fn(__input, &mut __state, &mut __err_state, ::peg::Parse::start(__input) #extra_args_call #(, #rule_params_call)*);
+            #take_stats
+
+            // Once stopped by a limit every rule fails, so neither a match nor the furthest
+            // failure means anything
+            if __err_state.stopped.is_some() {
+                return #failure;
+            }
+
+            match __result {
                 ::peg::RuleResult::Matched(__pos, __value) => {
                     if #eof_check {
-                        return Ok(__value)
+                        #success
                     } else {
-                        __err_state.mark_failure(__pos, "EOF");
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Eof);
                     }
                 }
                 _ => ()
             }
 
-            __state = ParseState::new();
+            __state = ParseState::new(::peg::Parse::input_len(__input));
             __err_state.reparse_for_error();
+            #clear_recovered
+            #trace_reparse
+
+            let __result = #parse_fn(__input, &mut __state, &mut __err_state, ::peg::Parse::start(__input) #extra_args_call #(, #rule_params_call)*);
+            if __err_state.stopped.is_some() {
+                return #failure;
+            }
 
-            match #parse_fn(__input, &mut __state, &mut __err_state, ::peg::Parse::start(__input) #extra_args_call #(, #rule_params_call)*) {
+            match __result {
                 ::peg::RuleResult::Matched(__pos, __value) => {
                     if #eof_check {
                         panic!("Parser is nondeterministic: succeeded when reparsing for error position");
                     } else {
-                        __err_state.mark_failure(__pos, "EOF");
+                        __err_state.mark_failure(__pos, ::peg::error::Expected::Eof);
                     }
                 }
                 _ => ()
             }
 
-            Err(__err_state.into_parse_error(__input))
+            #failure
+        }
+    }
+}
+
+/// Tell the tracer, if any, that the parse is starting over to find the error.
+fn trace_error_reparse(span: Span) -> TokenStream {
+    if cfg!(feature = "trace") {
+        quote_spanned! { span =>
+            if let Some(__tracer) = &mut __err_state.tracer {
+                __tracer.error_reparse();
+            }
+        }
+    } else {
+        quote!()
+    }
+}
+
+/// The `Session` method parsing with a `pub rule` in an `#![incremental]` grammar.
+fn compile_rule_session_method(context: &Context, rule: &Rule) -> TokenStream {
+    let span = rule.span.resolved_at(Span::mixed_site());
+    let doc = &rule.doc;
+    let name = &rule.name;
+    let visibility = &rule.visibility;
+    let parse_fn = format_ident!("__parse_{}", rule.name.to_string(), span = name.span());
+    let ty_params = ty_params_slice(&rule.ty_params);
+    let input_ty = &context.input_ty;
+    let rule_params = rule_params_list(context, rule);
+    let rule_params_call: Vec<TokenStream> = rule
+        .params
+        .iter()
+        .map(|param| {
+            let param_name = &param.name;
+            quote!(#param_name)
+        })
+        .collect();
+
+    let extra_args_def = &context.extra_args_def;
+    let extra_args_call = &context.extra_args_call;
+    let eof_check = export_eof_check(rule);
+    let (result_ty, success, failure) = export_result(context, rule);
+    let max_depth = &context.max_depth;
+    let export_fn = format_ident!("__export_{}", rule.name, span = name.span());
+    let session_fn = format_ident!("__session_{}", rule.name, span = name.span());
+    let trace_reparse = trace_error_reparse(span);
+
+    // As for the functions outside a session, tracing goes to stdout unless a tracer is passed in
+    let (set_tracer, with_tracer) = if cfg!(feature = "trace") {
+        let name_with_tracer = format_ident!("{}_with_tracer", rule.name, span = name.span());
+        let tracer_doc = format!(" Like [`Self::{}`], reporting the steps of the parse to `tracer`.", rule.name);
+        (
+            quote_spanned! { span =>
+                let mut __print_tracer = ::peg::trace::PrintTracer::new(__input);
+                __err_state.tracer = match __tracer {
+                    Some(__tracer) => Some(__tracer),
+                    None => Some(&mut __print_tracer),
+                };
+            },
+            quote_spanned! { span =>
+                #[doc = #tracer_doc]
+                #[allow(dead_code)]
+                #visibility fn #name_with_tracer<'input #(, #ty_params)*>(&mut self, __input: #input_ty, __tracer: &mut dyn ::peg::trace::Tracer #extra_args_def #(, #rule_params)*) -> #result_ty {
+                    self.#session_fn(__input, Some(__tracer) #extra_args_call #(, #rule_params_call)*)
+                }
+            },
+        )
+    } else {
+        (quote!(), quote!())
+    };
+
+    quote_spanned! { span =>
+        #doc
+        #visibility fn #name<'input #(, #ty_params)*>(&mut self, __input: #input_ty #extra_args_def #(, #rule_params)*) -> #result_ty {
+            self.#session_fn(__input, None #extra_args_call #(, #rule_params_call)*)
+        }
+
+        #with_tracer
+
+        fn #session_fn<'input #(, #ty_params)*>(&mut self, __input: #input_ty, __tracer: ::peg::__core::option::Option<&mut dyn ::peg::trace::Tracer> #extra_args_def #(, #rule_params)*) -> #result_ty {
+            #![allow(non_snake_case, unused)]
+
+            let mut __err_state = ::peg::error::ErrorState::new(::peg::Parse::start(__input));
+            __err_state.set_limits(&self.limits, #max_depth);
+            #set_tracer
+            let mut __state = ::peg::__core::mem::replace(&mut self.state, ParseState::new(None)).rebind();
+            let __result = #parse_fn(__input, &mut __state, &mut __err_state, ::peg::Parse::start(__input) #extra_args_call #(, #rule_params_call)*);
+
+            // Rules cut short by a limit may have cached failures, so those caches are dropped
+            if __err_state.stopped.is_some() {
+                return #failure;
+            }
+            self.state = __state.rebind();
+
+            if let ::peg::RuleResult::Matched(__pos, __value) = __result {
+                if #eof_check {
+                    #success
+                }
+            }
+
+            // Cache hits skip over the failures needed to find the error position, so the error
+            // is found by parsing again without the session's caches.
+            #trace_reparse
+            #export_fn(__input, &self.limits, __err_state.tracer.take(), None #extra_args_call #(, #rule_params_call)*)
         }
     }
 }
@@ -386,22 +1345,199 @@ fn name_or_ignore(n: Option<&Ident>) -> TokenStream {
     }
 }
 
-fn ordered_choice(span: Span, mut rs: impl DoubleEndedIterator<Item = TokenStream>) -> TokenStream {
-    rs.next_back().map(|last| rs.rfold(last, |fallback, preferred| {
+/// Code to save, and to restore, the state that must be rolled back when the
+/// parser backtracks over an expression that failed.
+///
+/// Only `recover!()` and the indentation expressions leave such state behind, so
+/// this is empty for grammars that use neither.
+fn backtrack_checkpoint(context: &Context, span: Span) -> (TokenStream, TokenStream) {
+    let (save_indent, restore_indent) = indent_checkpoint(context, span);
+    if context.recovery {
+        (
+            quote_spanned! { span => let __recovered_len = __err_state.recovered.len(); #save_indent },
+            quote_spanned! { span => __err_state.recovered.truncate(__recovered_len); #restore_indent },
+        )
+    } else {
+        (save_indent, restore_indent)
+    }
+}
+
+/// Code to save, and to restore, the indentation stack, if the grammar has one.
+fn indent_checkpoint(context: &Context, span: Span) -> (TokenStream, TokenStream) {
+    if context.indentation {
+        (
+            quote_spanned! { span => let __indent = __state.indents.current(); },
+            quote_spanned! { span => __state.indents.restore(__indent); },
+        )
+    } else {
+        (quote!(), quote!())
+    }
+}
+
+/// Wrap `code` in a block with the checkpoint from `backtrack_checkpoint`, if any.
+fn with_checkpoint(span: Span, save: TokenStream, code: TokenStream) -> TokenStream {
+    if save.is_empty() {
+        code
+    } else {
+        quote_spanned! { span => { #save #code } }
+    }
+}
+
+/// Code to open, and to close, an expression that may backtrack to `__pos` after a cut
+/// made within it, so the cut doesn't drop cache entries that may be used again.
+///
+/// Only grammars using `~` keep track, so this is empty for the others.
+fn backtrack_point(context: &Context, span: Span) -> (TokenStream, TokenStream) {
+    if context.prune_on_cut {
+        (
+            quote_spanned! { span => __state.open_backtrack(__pos); },
+            quote_spanned! { span => __state.close_backtrack(); },
+        )
+    } else {
+        (quote!(), quote!())
+    }
+}
+
+/// Wrap `code` in the count of expressions that may backtrack, if it's kept.
+fn with_backtrack_point(context: &Context, span: Span, code: TokenStream) -> TokenStream {
+    let (open, close) = backtrack_point(context, span);
+    if open.is_empty() {
+        code
+    } else {
+        quote_spanned! { span => {
+            #open
+            let __backtrack_res = #code;
+            #close
+            __backtrack_res
+        }}
+    }
+}
+
+/// Whether `e` contains a `~` that commits to the alternative of the choice `e` is in.
+/// Cuts inside a nested choice belong to that choice, and those in rule arguments and
+/// `precedence!{}` operators don't commit to any. With `nested`, any cut is found.
+fn contains_cut(e: &SpannedExpr, nested: bool) -> bool {
+    contains_expr(e, nested, |e| matches!(e, CutExpr))
+}
+
+/// Whether `e` contains an expression for which `is_target` is true, looking into
+/// choices, rule arguments and `precedence!{}` operators only with `nested`.
+fn contains_expr(e: &SpannedExpr, nested: bool, is_target: fn(&Expr) -> bool) -> bool {
+    let contains = |e: &SpannedExpr| contains_expr(e, nested, is_target);
+    if is_target(&e.expr) {
+        return true;
+    }
+    match &e.expr {
+        ChoiceExpr(exprs) => nested && exprs.iter().any(contains),
+        RuleExpr(_, args) => nested && args.iter().any(|arg| matches!(arg, RuleArg::Peg(e) if contains(e))),
+        PrecedenceExpr { levels } => {
+            nested && levels.iter().flat_map(|level| &level.operators).flat_map(|op| &op.elements).any(|e| contains(&e.expr))
+        }
+        ActionExpr(exprs, _) => exprs.iter().any(|e| contains(&e.expr)),
+        Repeat { inner, sep, .. } => contains(inner) || matches!(sep, Some(sep) if contains(sep)),
+        OptionalExpr(e) | PosAssertExpr(e) | NegAssertExpr(e) | MatchStrExpr(e) | QuietExpr(e) => contains(e),
+        RecoverExpr(e, sync) => contains(e) || contains(sync),
+        LiteralExpr(_) | CaseInsensitiveLiteralExpr(_) | ByteLiteralExpr(_) | PatternExpr(_) | MethodExpr(_, _) | FailExpr(_) | MarkerExpr(_)
+        | PositionExpr | CutExpr | PosPredicateExpr(_) | NegPredicateExpr(_) | DynamicLiteralExpr(_) | IndentExpr(_) => false,
+    }
+}
+
+/// Compile `e` where a `~` does (or doesn't) commit to an alternative of the enclosing choice.
+fn compile_cut_scope(context: &Context, e: &SpannedExpr, result_used: bool, cut_alternative: bool) -> TokenStream {
+    if context.cut_alternative == cut_alternative {
+        compile_expr(context, e, result_used)
+    } else {
+        compile_expr(&Context { cut_alternative, ..context.clone() }, e, result_used)
+    }
+}
+
+/// Compile the alternatives of a choice. Those but the last commit to themselves at a `~`,
+/// if any of them has one, which is returned as well.
+fn compile_alternatives<'e>(
+    context: &'e Context<'e>,
+    exprs: &'e [SpannedExpr],
+    result_used: bool,
+) -> (bool, impl DoubleEndedIterator<Item = TokenStream> + ExactSizeIterator + 'e) {
+    let last = exprs.len().saturating_sub(1);
+    let cut = exprs[..last].iter().any(|e| contains_cut(e, false));
+    (cut, exprs.iter().enumerate().map(move |(i, e)| compile_cut_scope(context, e, result_used, cut && i < last)))
+}
+
+fn ordered_choice(context: &Context, span: Span, cut: bool, mut rs: impl DoubleEndedIterator<Item = TokenStream>) -> TokenStream {
+    let (save, restore) = backtrack_checkpoint(context, span);
+    let (open, close) = backtrack_point(context, span);
+
+    // Once an alternative passes a `~` it's no longer a point to backtrack to.
+    let close = if cut && !close.is_empty() {
+        quote_spanned! { span => if !__cut { #close } }
+    } else {
+        close
+    };
+
+    let choice = rs.next_back().map(|last| rs.rfold(last, |fallback, preferred| {
+        let fallback = with_checkpoint(span, restore.clone(), fallback);
+        let fallback = if cut {
+            quote_spanned! { span => if __cut { ::peg::RuleResult::Failed } else { #fallback } }
+        } else {
+            fallback
+        };
         quote_spanned! { span => {
+            #save
+            #open
             let __choice_res = #preferred;
+            #close
             match __choice_res {
                 ::peg::RuleResult::Matched(__pos, __value) => ::peg::RuleResult::Matched(__pos, __value),
                 ::peg::RuleResult::Failed => #fallback
             }
         }}
-    })).expect("ordered choice must not be empty")
+    })).expect("ordered choice must not be empty");
+
+    if cut {
+        quote_spanned! { span => {
+            let mut __cut = false;
+            #choice
+        }}
+    } else {
+        choice
+    }
 }
 
 fn labeled_seq(context: &Context, exprs: &[TaggedExpr], inner: TokenStream) -> TokenStream {
-    exprs.iter().rfold(inner, |then, expr| {
+    skipped_seq(context, exprs, (false, false), inner)
+}
+
+/// Compile a sequence, calling the `#![skip]` rule between its elements, except before a
+/// `position!()`, which is then the end of the element before it. With `around`, it's
+/// also called before the first element and after the last, for the elements between
+/// the operands of a `precedence!{}` operator.
+fn skipped_seq(context: &Context, exprs: &[TaggedExpr], around: (bool, bool), inner: TokenStream) -> TokenStream {
+    let (before, after) = around;
+    let inner = if after { skip_then(context, inner) } else { inner };
+    let seq = exprs.iter().enumerate().rfold(inner, |then, (i, expr)| {
+        let then = match exprs.get(i + 1) {
+            Some(next) if !matches!(next.expr.expr, PositionExpr) => skip_then(context, then),
+            _ => then,
+        };
         compile_expr_continuation(context, &expr.expr, expr.name.as_ref(), then)
-    })
+    });
+    if before { skip_then(context, seq) } else { seq }
+}
+
+/// Call the `#![skip]` rule, if the rule being compiled skips, and then `continuation`.
+/// The call is quiet, so the errors don't list what the skip rule would have accepted.
+fn skip_then(context: &Context, continuation: TokenStream) -> TokenStream {
+    match context.skip {
+        Some(skip) => {
+            let span = skip.span();
+            let call = SpannedExpr {
+                span,
+                expr: QuietExpr(Box::new(SpannedExpr { span, expr: RuleExpr(skip.clone(), Vec::new()) })),
+            };
+            compile_expr_continuation(context, &call, None, continuation)
+        }
+        None => continuation,
+    }
 }
 
 fn compile_expr_continuation(context: &Context, e: &SpannedExpr, result_name: Option<&Ident>, continuation: TokenStream) -> TokenStream {
@@ -410,11 +1546,19 @@ fn compile_expr_continuation(context: &Context, e: &SpannedExpr, result_name: Op
     let result_pat = name_or_ignore(result_name);
     match e.expr {
         LiteralExpr(ref s) => {
-            compile_literal_expr(s, continuation)
+            compile_literal_expr(context, s, LiteralKind::Exact, continuation)
+        }
+
+        CaseInsensitiveLiteralExpr(ref s) => {
+            compile_literal_expr(context, s, LiteralKind::CaseInsensitive, continuation)
+        }
+
+        ByteLiteralExpr(ref s) => {
+            compile_literal_expr(context, s, LiteralKind::Bytes, continuation)
         }
 
         PatternExpr(ref pattern) => {
-            compile_pattern_expr(pattern, quote_spanned!{ span =>
+            compile_pattern_expr(context, pattern, quote_spanned!{ span =>
                 { let __pos = __next; let #result_pat = (); { #continuation } }
             })
         }
@@ -432,21 +1576,110 @@ fn compile_expr_continuation(context: &Context, e: &SpannedExpr, result_name: Op
     }
 }
 
-fn compile_literal_expr(s: &Literal, continuation: TokenStream) -> TokenStream {
+/// Code recording that the input up to `end` was examined, for `#![incremental]` grammars.
+fn examine(context: &Context, span: Span, end: TokenStream) -> TokenStream {
+    if context.incremental {
+        quote_spanned! { span => __state.examine(#end); }
+    } else {
+        quote!()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum LiteralKind {
+    Exact,
+    CaseInsensitive,
+    Bytes,
+}
+
+fn compile_literal_expr(context: &Context, s: &Literal, kind: LiteralKind, continuation: TokenStream) -> TokenStream {
     let span = s.span().resolved_at(Span::mixed_site());
-    let escaped_str = s.to_string();
+    let (escaped_str, parse_literal, max_len) = match kind {
+        LiteralKind::Exact => (
+            s.to_string(),
+            quote_spanned! { span => ::peg::ParseLiteral::parse_string_literal },
+            quote_spanned! { span => #s.len() },
+        ),
+        // Case folding can change the UTF-8 length of a character, up to 4 bytes
+        LiteralKind::CaseInsensitive => (
+            format!("{}i", s),
+            quote_spanned! { span => ::peg::ParseLiteralCaseInsensitive::parse_string_literal_ci },
+            quote_spanned! { span => #s.len() * 4 },
+        ),
+        LiteralKind::Bytes => (
+            byte_string_value(s).map_or_else(|| s.to_string(), |bytes| display_byte_string(&bytes)),
+            quote_spanned! { span => ::peg::ParseByteLiteral::parse_byte_literal },
+            quote_spanned! { span => #s.len() },
+        ),
+    };
+    let examine_matched = examine(context, span, quote_spanned! { span => __pos });
+    let examine_failed = examine(context, span, quote_spanned! { span => __pos + ::peg::__core::cmp::max(#max_len, 1) });
     quote_spanned! { span => 
-            match ::peg::ParseLiteral::parse_string_literal(__input, __pos, #s) {
-            ::peg::RuleResult::Matched(__pos, __val) => { #continuation }
-            ::peg::RuleResult::Failed => { __err_state.mark_failure(__pos, #escaped_str); ::peg::RuleResult::Failed }
+            match #parse_literal(__input, __pos, #s) {
+            ::peg::RuleResult::Matched(__pos, __val) => { #examine_matched #continuation }
+            ::peg::RuleResult::Failed => { #examine_failed __err_state.mark_failure(__pos, ::peg::error::Expected::Literal(#escaped_str)); ::peg::RuleResult::Failed }
+        }
+    }
+}
+
+/// The value of a byte string literal token, `b"..."` or `br#"..."#`.
+fn byte_string_value(lit: &Literal) -> Option<Vec<u8>> {
+    let text = lit.to_string();
+    let text = text.strip_prefix('b')?;
+
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw.get(hashes + 1..raw.len().checked_sub(hashes + 1)?)?;
+        return Some(body.as_bytes().to_vec());
+    }
+
+    let mut chars = text.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut bytes = Vec::new();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            bytes.push(c as u8);
+            continue;
+        }
+        match chars.next()? {
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            '0' => bytes.push(b'\0'),
+            c @ '\\' | c @ '\'' | c @ '"' => bytes.push(c as u8),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                bytes.push(u8::from_str_radix(&hex, 16).ok()?);
+            }
+            '\n' => {
+                let rest = chars.as_str().trim_start();
+                chars = rest.chars();
+            }
+            _ => return None,
+        }
+    }
+    Some(bytes)
+}
+
+/// Format bytes as a byte string literal for the expected set, with printable
+/// ASCII as is and everything else escaped, like `b"\x89PNG"`.
+fn display_byte_string(bytes: &[u8]) -> String {
+    let mut s = String::from("b\"");
+    for &b in bytes {
+        if b == b'\'' {
+            s.push('\'');
+        } else {
+            s.extend(std::ascii::escape_default(b).map(char::from));
         }
     }
+    s.push('"');
+    s
 }
 
-fn compile_pattern_expr(pattern_group: &Group, success_res: TokenStream) -> TokenStream {
+fn compile_pattern_expr(context: &Context, pattern_group: &Group, success_res: TokenStream) -> TokenStream {
     let span = pattern_group.span().resolved_at(Span::mixed_site());
     let pat_str = pattern_group.to_string();
-    let failure_res = quote_spanned! { span => { __err_state.mark_failure(__pos, #pat_str); ::peg::RuleResult::Failed } };
+    let failure_res = quote_spanned! { span => { __err_state.mark_failure(__pos, ::peg::error::Expected::Pattern(#pat_str)); ::peg::RuleResult::Failed } };
+    let examine_failed = examine(context, span, quote_spanned! { span => __pos + 1 });
 
     let (pattern, in_set, not_in_set) = if let Some(pattern) = group_check_prefix(pattern_group, '^') {
         (pattern, failure_res, success_res)
@@ -454,13 +1687,20 @@ fn compile_pattern_expr(pattern_group: &Group, success_res: TokenStream) -> Toke
         (pattern_group.stream(), success_res, failure_res)
     };
 
+    let mut match_elem = quote_spanned! { span =>
+        match __ch {
+            #pattern => #in_set,
+            _ => #not_in_set,
+        }
+    };
+    if context.incremental {
+        match_elem = quote_spanned! { span => { __state.examine(__next); #match_elem } };
+    }
+
     quote_spanned! { span =>
         match ::peg::ParseElem::parse_elem(__input, __pos) {
-            ::peg::RuleResult::Matched(__next, __ch) => match __ch {
-                #pattern => #in_set,
-                _ => #not_in_set,
-            }
-            ::peg::RuleResult::Failed => { __err_state.mark_failure(__pos, #pat_str); ::peg::RuleResult::Failed }
+            ::peg::RuleResult::Matched(__next, __ch) => #match_elem
+            ::peg::RuleResult::Failed => { #examine_failed __err_state.mark_failure(__pos, ::peg::error::Expected::Pattern(#pat_str)); ::peg::RuleResult::Failed }
         }
     }
 }
@@ -470,13 +1710,37 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
 
     match e.expr {
         LiteralExpr(ref s) => {
-            compile_literal_expr(s, quote_spanned! { span =>
+            compile_literal_expr(context, s, LiteralKind::Exact, quote_spanned! { span =>
                  ::peg::RuleResult::Matched(__pos, __val)
             })
         }
 
+        CaseInsensitiveLiteralExpr(ref s) => {
+            compile_literal_expr(context, s, LiteralKind::CaseInsensitive, quote_spanned! { span =>
+                 ::peg::RuleResult::Matched(__pos, __val)
+            })
+        }
+
+        ByteLiteralExpr(ref s) => {
+            compile_literal_expr(context, s, LiteralKind::Bytes, quote_spanned! { span =>
+                 ::peg::RuleResult::Matched(__pos, __val)
+            })
+        }
+
+        DynamicLiteralExpr(ref expr) => {
+            let examine_matched = examine(context, span, quote_spanned! { span => __pos });
+            let examine_failed = examine(context, span, quote_spanned! { span => __pos + ::peg::__core::cmp::max(__literal.len(), 1) });
+            quote_spanned! { span => {
+                let __literal: &str = &(#expr);
+                match ::peg::ParseLiteral::parse_string_literal(__input, __pos, __literal) {
+                    ::peg::RuleResult::Matched(__pos, __val) => { #examine_matched ::peg::RuleResult::Matched(__pos, __val) }
+                    ::peg::RuleResult::Failed => { #examine_failed __err_state.mark_failure_dynamic(__pos, __literal) }
+                }
+            }}
+        }
+
         PatternExpr(ref pattern_group) => {
-            compile_pattern_expr(pattern_group, quote_spanned! { span =>
+            compile_pattern_expr(context, pattern_group, quote_spanned! { span =>
                 ::peg::RuleResult::Matched(__next, ())
             })
         }
@@ -528,17 +1792,24 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
             let func = format_ident!("__parse_{}", rule_name, span = rule_name.span());
             let extra_args_call = &context.extra_args_call;
 
-            let rule_args_call: Vec<TokenStream> = rule_args
+            let mut rule_args_call: Vec<TokenStream> = rule_args
                 .iter()
                 .map(|arg| match arg {
                     RuleArg::Peg(e) => {
-                        let expr = compile_expr(context, e, true);
+                        let expr = compile_cut_scope(context, e, true, false);
                         quote_spanned! { span=> |__input, __state, __err_state, __pos| { #expr } }
                     }
                     RuleArg::Rust(e) => e.clone(),
                 })
                 .collect();
 
+            // Each call site gets its own id to key the cache by the rule closures it passes.
+            if rule_def.cache_by_callsite() {
+                let callsite = context.callsites.get();
+                context.callsites.set(callsite + 1);
+                rule_args_call.push(quote_spanned! { span=> #callsite });
+            }
+
             if result_used {
                 quote_spanned!{ span=> #func(__input, __state, __err_state, __pos #extra_args_call #(, #rule_args_call)*) }
             } else {
@@ -552,33 +1823,67 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
         }
 
         MethodExpr(ref method, ref args) => {
-            quote_spanned!{ span=> __input.#method(__pos, #args) }
+            if context.incremental {
+                // There's no way to know how far a custom method looks, so assume it depends on
+                // everything that follows.
+                quote_spanned!{ span=> { __state.examine(usize::MAX); __input.#method(__pos, #args) } }
+            } else {
+                quote_spanned!{ span=> __input.#method(__pos, #args) }
+            }
         }
 
-        ChoiceExpr(ref exprs) => ordered_choice(span,
-            exprs
+        ChoiceExpr(ref exprs) if cfg!(feature = "coverage") => {
+            // Choices are numbered within the rule in the order they're compiled. The points
+            // for all the alternatives are added first, so nested choices come after this one.
+            let (rule, choice) = {
+                let points = context.coverage.borrow();
+                let rule = points.last().expect("the rule's point is added first").rule.clone();
+                let choices = points.iter().filter(|p| p.rule == rule && matches!(p.choice, Some((_, 0)))).count();
+                (rule, choices)
+            };
+            let indexes: Vec<usize> = exprs
                 .iter()
-                .map(|expr| compile_expr(context, expr, result_used)),
-        ),
+                .enumerate()
+                .map(|(i, expr)| add_coverage_point(context, CoveragePoint {
+                    rule: rule.clone(),
+                    choice: Some((choice, i)),
+                    location: span_location(expr.span),
+                }))
+                .collect();
+            let (cut, alternatives) = compile_alternatives(context, exprs, result_used);
+            ordered_choice(context, span, cut,
+                alternatives
+                    .zip(indexes)
+                    .map(|(alternative, index)| count_match(span, index, alternative)),
+            )
+        }
+
+        ChoiceExpr(ref exprs) => {
+            let (cut, alternatives) = compile_alternatives(context, exprs, result_used);
+            ordered_choice(context, span, cut, alternatives)
+        }
 
         OptionalExpr(ref e) => {
             let optional_res = compile_expr(context, e, result_used);
+            let (save, restore) = backtrack_checkpoint(context, span);
 
-            if result_used {
+            let optional = if result_used {
                 quote_spanned!{ span=>
                     match #optional_res {
                         ::peg::RuleResult::Matched(__newpos, __value) => { ::peg::RuleResult::Matched(__newpos, Some(__value)) },
-                        ::peg::RuleResult::Failed => { ::peg::RuleResult::Matched(__pos, None) },
+                        ::peg::RuleResult::Failed => { #restore ::peg::RuleResult::Matched(__pos, None) },
                     }
                 }
             } else {
                 quote_spanned!{ span=>
                     match #optional_res {
                         ::peg::RuleResult::Matched(__newpos, _) => { ::peg::RuleResult::Matched(__newpos, ()) },
-                        ::peg::RuleResult::Failed => { ::peg::RuleResult::Matched(__pos, ()) },
+                        ::peg::RuleResult::Failed => { #restore ::peg::RuleResult::Matched(__pos, ()) },
                     }
                 }
-            }
+            };
+
+            with_backtrack_point(context, span, with_checkpoint(span, save, optional))
         }
 
         Repeat{ ref inner, ref bound, ref sep} => {
@@ -591,14 +1896,29 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                 BoundedRepeat::Both(ref min, ref max) => (min.clone(), max.clone()),
             };
 
-            let match_sep = if let Some(sep) = sep {
-                let sep_inner = compile_expr(context, sep, false);
+            let (save, restore) = backtrack_checkpoint(context, span);
+
+            // Each iteration may backtrack to where it started, but not to earlier ones
+            let (open, close) = backtrack_point(context, span);
+
+            // With `#![skip]`, the skip rule is called between the items, and on both
+            // sides of the separator.
+            let skipped = quote_spanned!{ span=> ::peg::RuleResult::Matched(__pos, ()) };
+            let between = match (sep, context.skip) {
+                (Some(sep), None) => Some(compile_expr(context, sep, false)),
+                (Some(sep), Some(_)) => Some(skip_then(context, compile_expr_continuation(context, sep, None, skip_then(context, skipped)))),
+                (None, Some(_)) => Some(skip_then(context, skipped)),
+                (None, None) => None,
+            };
+
+            let match_sep = if let Some(sep_inner) = between {
+                let sep_failed = with_checkpoint(span, restore.clone(), quote!({ #close break }));
                 quote_spanned!{ span=>
                     let __pos = if __repeat_value.is_empty() { __pos } else {
                         let __sep_res = #sep_inner;
                         match __sep_res {
                             ::peg::RuleResult::Matched(__newpos, _) => { __newpos },
-                            ::peg::RuleResult::Failed => break,
+                            ::peg::RuleResult::Failed => #sep_failed,
                         }
                     };
                 }
@@ -613,9 +1933,9 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
             };
 
             let (repeat_vec, repeat_step) =
-                if result_used || min.is_some() || max.is_some() || sep.is_some() {
+                if result_used || min.is_some() || max.is_some() || sep.is_some() || context.skip.is_some() {
                     (
-                        Some(quote_spanned! { span => let mut __repeat_value = vec!(); }),
+                        Some(quote_spanned! { span => let mut __repeat_value = ::peg::__alloc::vec::Vec::new(); }),
                         Some(quote_spanned! { span => __repeat_value.push(__value); }),
                     )
                 } else {
@@ -623,7 +1943,7 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                 };
 
             let max_check = max.map(|max| {
-                quote_spanned!{ span=> if __repeat_value.len() >= #max { break } }
+                quote_spanned!{ span=> if __repeat_value.len() >= #max { #close break } }
             });
 
             let result_check = if let Some(min) = min {
@@ -644,17 +1964,21 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
 
                 loop {
                     let __pos = __repeat_pos;
+                    #save
+                    #open
 
                     #match_sep
                     #max_check
 
                     let __step_res = #inner;
+                    #close
                     match __step_res {
                         ::peg::RuleResult::Matched(__newpos, __value) => {
                             __repeat_pos = __newpos;
                             #repeat_step
                         },
                         ::peg::RuleResult::Failed => {
+                            #restore
                             break;
                         }
                     }
@@ -666,28 +1990,47 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
 
         PosAssertExpr(ref e) => {
             let assert_res = compile_expr(context, e, result_used);
-            quote_spanned!{ span=> {
+            // A lookahead leaves the indentation as it was, even when it matches
+            let (save_indent, restore_indent) = indent_checkpoint(context, span);
+            with_backtrack_point(context, span, quote_spanned!{ span=> {
+                #save_indent
                 __err_state.suppress_fail += 1;
                 let __assert_res = #assert_res;
                 __err_state.suppress_fail -= 1;
+                #restore_indent
                 match __assert_res {
                     ::peg::RuleResult::Matched(_, __value) => ::peg::RuleResult::Matched(__pos, __value),
                     ::peg::RuleResult::Failed => ::peg::RuleResult::Failed,
                 }
-            }}
+            }})
         }
 
         NegAssertExpr(ref e) => {
             let assert_res = compile_expr(context, e, false);
-            quote_spanned!{ span=> {
+            let (save_indent, restore_indent) = indent_checkpoint(context, span);
+            with_backtrack_point(context, span, quote_spanned!{ span=> {
+                #save_indent
                 __err_state.suppress_fail += 1;
                 let __assert_res = #assert_res;
                 __err_state.suppress_fail -= 1;
+                #restore_indent
                 match __assert_res {
                     ::peg::RuleResult::Failed => ::peg::RuleResult::Matched(__pos, ()),
                     ::peg::RuleResult::Matched(..) => ::peg::RuleResult::Failed,
                 }
-            }}
+            }})
+        }
+
+        PosPredicateExpr(ref code) | NegPredicateExpr(ref code) => {
+            let code_span = code.span().resolved_at(Span::mixed_site());
+            let negate = if let NegPredicateExpr(..) = e.expr { quote!(!) } else { quote!() };
+            quote_spanned!{ code_span =>
+                if #negate (|| -> bool #code)() {
+                    ::peg::RuleResult::Matched(__pos, ())
+                } else {
+                    ::peg::RuleResult::Failed
+                }
+            }
         }
 
         ActionExpr(ref exprs, ref code) => labeled_seq(context, &exprs, {
@@ -700,7 +2043,7 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                         match (||{ #body })() {
                             Ok(res) => ::peg::RuleResult::Matched(__pos, res),
                             Err(expected) => {
-                                __err_state.mark_failure(__pos, expected);
+                                __err_state.mark_failure(__pos, ::peg::error::Expected::Named(expected));
                                 ::peg::RuleResult::Failed
                             },
                         }
@@ -735,13 +2078,24 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
             }}
         }
         FailExpr(ref expected) => {
-            quote_spanned! { span => { __err_state.mark_failure(__pos, #expected); ::peg::RuleResult::Failed }}
+            quote_spanned! { span => { __err_state.mark_failure(__pos, ::peg::error::Expected::Named(#expected)); ::peg::RuleResult::Failed }}
         }
 
         PrecedenceExpr { ref levels } => {
+            // The operators are compiled into closures, which can't commit to an
+            // alternative outside them.
+            let no_cut_context;
+            let context = if context.cut_alternative {
+                no_cut_context = Context { cut_alternative: false, ..context.clone() };
+                &no_cut_context
+            } else {
+                context
+            };
+
             let mut pre_rules = Vec::new();
             let mut level_code = Vec::new();
             let mut span_capture: Option<(TokenStream, TokenStream, TokenStream, &Group)> = None;
+            let (save, restore) = backtrack_checkpoint(context, span);
 
             for (prec, level) in levels.iter().enumerate() {
                 let prec = prec as i32;
@@ -799,7 +2153,7 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                             };
 
                             post_rules.push(
-                                labeled_seq(context, &op.elements[1..op.elements.len()-1], {
+                                skipped_seq(context, &op.elements[1..op.elements.len()-1], (true, true), {
                                     quote_spanned!{ op_span =>
                                         if let ::peg::RuleResult::Matched(__pos, #r_arg) = __recurse(__pos, #new_prec, __state, __err_state) {
                                             let #l_arg = __infix_result;
@@ -812,9 +2166,10 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                         }
                         (&MarkerExpr(_), _) if op.elements.len() >= 2 => {
                             // postfix
-                            post_rules.push(labeled_seq(
+                            post_rules.push(skipped_seq(
                                 context,
                                 &op.elements[1..op.elements.len()],
+                                (true, false),
                                 {
                                     quote_spanned! { op_span =>
                                         let #l_arg = __infix_result;
@@ -831,7 +2186,7 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                                 false => prec + 1,
                             };
                             pre_rules.push(
-                                labeled_seq(context, &op.elements[..op.elements.len()-1], {
+                                skipped_seq(context, &op.elements[..op.elements.len()-1], (false, true), {
                                     quote_spanned!{ op_span =>
                                         if let ::peg::RuleResult::Matched(__pos, #r_arg) = __recurse(__pos, #new_prec, __state, __err_state) {
                                             ::peg::RuleResult::Matched(__pos, #action)
@@ -853,9 +2208,11 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                     level_code.push(quote_spanned! { span =>
                         if #prec >= __min_prec {
                             #(
+                                #save
                                 if let ::peg::RuleResult::Matched(__pos, ()) = #post_rules {
                                     return (__infix_result, ::peg::RuleResult::Matched(__pos, ()));
                                 }
+                                #restore
                             )*
                         }
                     });
@@ -864,8 +2221,16 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
 
             let (enter, leave) = if cfg!(feature = "trace") {
                 (
-                    quote! {println!("[PEG_TRACE] Entering level {}", min_prec);},
-                    quote! {println!("[PEG_TRACE] Leaving level {}", min_prec);},
+                    quote_spanned! { span =>
+                        if let Some(tracer) = &mut err_state.tracer {
+                            tracer.precedence_enter(min_prec, pos);
+                        }
+                    },
+                    quote_spanned! { span =>
+                        if let Some(tracer) = &mut err_state.tracer {
+                            tracer.precedence_leave(min_prec, repeat_pos);
+                        }
+                    },
                 )
             } else {
                 (quote!(), quote!())
@@ -874,15 +2239,20 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
             // The closures below must be defined within the function call to which they are passed
             // due to https://github.com/rust-lang/rust/issues/41078
 
-            quote_spanned! { span => {
+            with_backtrack_point(context, span, quote_spanned! { span => {
                 fn __infix_parse<T, S>(
                     state: &mut S,
-                    err_state: &mut ::peg::error::ErrorState,
+                    err_state: &mut ::peg::error::ErrorState<'_>,
                     min_prec: i32,
                     lpos: usize,
-                    prefix_atom: &Fn(usize, &mut S, &mut ::peg::error::ErrorState, &Fn(usize, i32, &mut S, &mut ::peg::error::ErrorState) -> ::peg::RuleResult<T>) -> ::peg::RuleResult<T>,
-                    level_code: &Fn(usize, usize, i32, T, &mut S, &mut ::peg::error::ErrorState, &Fn(usize, i32, &mut S, &mut ::peg::error::ErrorState) -> ::peg::RuleResult<T>) -> (T, ::peg::RuleResult<()>),
+                    prefix_atom: &Fn(usize, &mut S, &mut ::peg::error::ErrorState<'_>, &Fn(usize, i32, &mut S, &mut ::peg::error::ErrorState<'_>) -> ::peg::RuleResult<T>) -> ::peg::RuleResult<T>,
+                    level_code: &Fn(usize, usize, i32, T, &mut S, &mut ::peg::error::ErrorState<'_>, &Fn(usize, i32, &mut S, &mut ::peg::error::ErrorState<'_>) -> ::peg::RuleResult<T>) -> (T, ::peg::RuleResult<()>),
                 ) -> ::peg::RuleResult<T> {
+                    // Operands nest by recursing here rather than through a rule
+                    if !err_state.enter_nesting(lpos) {
+                        return ::peg::RuleResult::Failed;
+                    }
+
                     let initial = {
                         prefix_atom(lpos, state, err_state, &|pos, min_prec, state, err_state| {
                             __infix_parse(state, err_state, min_prec, pos, prefix_atom, level_code)
@@ -914,8 +2284,10 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                             break;
                         }
                         #leave
+                        err_state.leave_nesting();
                         ::peg::RuleResult::Matched(repeat_pos, infix_result)
                     } else {
+                        err_state.leave_nesting();
                         ::peg::RuleResult::Failed
                     }
                 }
@@ -924,9 +2296,11 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                     &|__pos, __state, __err_state, __recurse| {
                         let __lpos = __pos;
                         #(
+                            #save
                             if let ::peg::RuleResult::Matched(__pos, __v) = #pre_rules {
                                 return ::peg::RuleResult::Matched(__pos, __v);
                             }
+                            #restore
                         )*
 
                         ::peg::RuleResult::Failed
@@ -936,10 +2310,132 @@ fn compile_expr(context: &Context, e: &SpannedExpr, result_used: bool) -> TokenS
                         (__infix_result, ::peg::RuleResult::Failed)
                     }
                 )
-            }}
+            }})
         }
         MarkerExpr { .. } => {
             return report_error(span, format!("`@` is only allowed in `precedence!{{}}`"));
         }
+
+        RecoverExpr(ref expr, ref sync) => {
+            let expr_res = compile_expr(context, expr, result_used);
+            let sync_res = compile_expr(context, sync, result_used);
+            let (save, restore) = backtrack_checkpoint(context, span);
+
+            // The re-runs start from the indentation the expression started with, so the
+            // fresh state gets a copy of the stacks the identifiers refer to.
+            let (save_indent, restore_indent) = indent_checkpoint(context, span);
+            let fresh_state = if context.indentation {
+                quote_spanned! { span => {
+                    let mut __fresh = ParseState::new(::peg::Parse::input_len(__input));
+                    __fresh.indents = __state.indents.clone();
+                    __fresh
+                }}
+            } else {
+                quote_spanned! { span => ParseState::new(::peg::Parse::input_len(__input)) }
+            };
+
+            // On failure, the expression is re-run twice, like the whole parse is re-run on
+            // error: once to find the furthest failure position, and again to collect the
+            // expected set there. Both runs use fresh caches so memoized failures don't hide it.
+            with_backtrack_point(context, span, quote_spanned! { span => {
+                let __recover_checkpoint = __err_state.recover_checkpoint();
+                let mut __recover_outer_state = None;
+                let mut __recover_pass = 0;
+                #save_indent
+
+                loop {
+                    #restore_indent
+                    let __recover_res = #expr_res;
+                    match (__recover_pass, __recover_res) {
+                        (0, ::peg::RuleResult::Matched(__newpos, __value)) => break ::peg::RuleResult::Matched(__newpos, __value),
+                        (0, _) => {
+                            __recover_outer_state = Some(::peg::__core::mem::replace(__state, #fresh_state));
+                            __err_state.recover_isolate(&__recover_checkpoint, __pos);
+                        }
+                        (1, _) => __err_state.reparse_for_error(),
+                        _ => {
+                            *__state = __recover_outer_state.unwrap();
+                            #restore_indent
+                            let __recover_error = __err_state.recover_end(__recover_checkpoint);
+
+                            // Skip input until the synchronization expression matches
+                            __err_state.suppress_fail += 1;
+                            let mut __skip_pos = __pos;
+                            let __sync_res = loop {
+                                let __pos = __skip_pos;
+                                #save
+                                match #sync_res {
+                                    ::peg::RuleResult::Matched(__newpos, __value) => break ::peg::RuleResult::Matched(__newpos, __value),
+                                    ::peg::RuleResult::Failed => { #restore }
+                                }
+                                match ::peg::ParseElem::parse_elem(__input, __pos) {
+                                    ::peg::RuleResult::Matched(__next, _) => __skip_pos = __next,
+                                    ::peg::RuleResult::Failed => break ::peg::RuleResult::Failed,
+                                }
+                            };
+                            __err_state.suppress_fail -= 1;
+
+                            match __sync_res {
+                                ::peg::RuleResult::Matched(..) => __err_state.record_recovered_error(__recover_error),
+                                ::peg::RuleResult::Failed => __err_state.mark_unrecovered_error(__recover_error),
+                            }
+                            break __sync_res;
+                        }
+                    }
+                    __recover_pass += 1;
+                }
+            }})
+        }
+
+        CutExpr => {
+            let commit = if context.cut_alternative {
+                let (_, close) = backtrack_point(context, span);
+                quote_spanned! { span => if !__cut { __cut = true; #close } }
+            } else {
+                quote!()
+            };
+            let drop_before = if context.prune_on_cut {
+                quote_spanned! { span => __state.cut(__pos); }
+            } else {
+                quote!()
+            };
+            quote_spanned! { span => { #commit #drop_before ::peg::RuleResult::Matched(__pos, ()) } }
+        }
+
+        IndentExpr(IndentKind::Dedent) => {
+            quote_spanned! { span =>
+                if __state.indents.pop() {
+                    ::peg::RuleResult::Matched(__pos, ())
+                } else {
+                    __err_state.mark_failure(__pos, ::peg::error::Expected::Named("dedent"));
+                    ::peg::RuleResult::Failed
+                }
+            }
+        }
+
+        IndentExpr(kind) => {
+            // The indentation ends at the first other character, which is examined to find it
+            let examine = examine(context, span, quote_spanned! { span => __next + 1 });
+            let (matches, expected) = match kind {
+                IndentKind::Same => (quote_spanned! { span => __width == __state.indents.width() }, "same indentation"),
+                _ => (quote_spanned! { span => __width > __state.indents.width() }, "indentation"),
+            };
+            let push = if let IndentKind::Indent = kind {
+                quote_spanned! { span => __state.indents.push(__width); }
+            } else {
+                quote!()
+            };
+            quote_spanned! { span => {
+                let (__next, __width) = ::peg::ParseIndentation::parse_indentation(__input, __pos);
+                #examine
+                if #matches {
+                    #push
+                    ::peg::RuleResult::Matched(__next, ())
+                } else {
+                    __err_state.mark_failure(__next, ::peg::error::Expected::Named(#expected));
+                    ::peg::RuleResult::Failed
+                }
+            }}
+        }
     }
 }
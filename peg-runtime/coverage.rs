@@ -0,0 +1,114 @@
+//! Which rules and alternatives of a grammar were used, with the `coverage` feature
+
+use crate::RuleResult;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A rule, or an alternative of a `/` choice, whose matches are counted
+#[derive(Debug, PartialEq, Eq)]
+pub struct Point {
+    /// The name of the grammar
+    pub grammar: &'static str,
+
+    /// The rule containing the point
+    pub rule: &'static str,
+
+    /// Identifies the point within its grammar: the rule name for a rule, and
+    /// `rule/choice/alternative` for an alternative, numbering the choices of
+    /// the rule in the order they appear, and the alternatives of each from 0.
+    pub id: &'static str,
+
+    /// Where the rule name or the alternative is in the grammar source, as
+    /// `file:line:column`, if the compiler reports it
+    pub location: &'static str,
+}
+
+/// Counts of matches of the rules and alternatives of one or more grammars.
+///
+/// A grammar compiled with the `coverage` feature has a `coverage()` function
+/// returning the counts for all the parses with it so far. Its `Display`
+/// implementation summarizes the coverage and lists the points that never
+/// matched.
+#[derive(Clone, Debug, Default)]
+pub struct Coverage {
+    points: Vec<(&'static Point, u64)>,
+}
+
+impl Coverage {
+    #[doc(hidden)]
+    pub fn new(points: &'static [Point], hits: &[AtomicUsize]) -> Coverage {
+        Coverage {
+            points: points
+                .iter()
+                .zip(hits)
+                .map(|(point, hits)| (point, hits.load(Ordering::Relaxed) as u64))
+                .collect(),
+        }
+    }
+
+    /// Add the counts from `other`, which may be for the same grammars or
+    /// others.
+    pub fn merge(&mut self, other: &Coverage) {
+        for &(point, hits) in &other.points {
+            let existing = self
+                .points
+                .iter_mut()
+                .find(|(p, _)| p.grammar == point.grammar && p.id == point.id);
+            match existing {
+                Some((_, count)) => *count += hits,
+                None => self.points.push((point, hits)),
+            }
+        }
+    }
+
+    /// Each point, with the number of times it matched.
+    pub fn points(&self) -> impl Iterator<Item = (&'static Point, u64)> + '_ {
+        self.points.iter().copied()
+    }
+
+    /// The number of times the point `id` of `grammar` matched, if there is
+    /// such a point.
+    pub fn hits(&self, grammar: &str, id: &str) -> Option<u64> {
+        self.points
+            .iter()
+            .find(|(p, _)| p.grammar == grammar && p.id == id)
+            .map(|&(_, hits)| hits)
+    }
+
+    /// The points that never matched.
+    pub fn uncovered(&self) -> impl Iterator<Item = &'static Point> + '_ {
+        self.points.iter().filter(|&&(_, hits)| hits == 0).map(|&(point, _)| point)
+    }
+}
+
+/// Pass on `result`, adding a match to `hits` if `count` is set. The value's type is
+/// inferred from the expected result, as for an expression in its place.
+#[doc(hidden)]
+#[inline]
+pub fn count_match<T>(hits: &AtomicUsize, count: bool, result: RuleResult<T>) -> RuleResult<T> {
+    if count && matches!(result, RuleResult::Matched(..)) {
+        hits.fetch_add(1, Ordering::Relaxed);
+    }
+    result
+}
+
+impl Display for Coverage {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let uncovered = self.uncovered().count();
+        writeln!(
+            fmt,
+            "{} of {} rules and alternatives matched",
+            self.points.len() - uncovered,
+            self.points.len()
+        )?;
+        for point in self.uncovered() {
+            write!(fmt, "  never matched: {}::{}", point.grammar, point.id)?;
+            if !point.location.is_empty() {
+                write!(fmt, " at {}", point.location)?;
+            }
+            writeln!(fmt)?;
+        }
+        Ok(())
+    }
+}
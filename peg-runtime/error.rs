@@ -1,30 +1,184 @@
 //! Parse error reporting
 
-use crate::{Parse, RuleResult};
-use std::collections::HashSet;
-use std::fmt::{self, Debug, Display};
+use crate::stats::ParseStats;
+use crate::trace::Tracer;
+use crate::{Limits, Parse, RuleResult, CANCEL_CHECK_INTERVAL};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display};
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Something the parser expected, but did not find, at the error position
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub enum Expected {
+    /// A literal, as written in the grammar: `"("`, `"select"i` or `b"\x00"`
+    Literal(&'static str),
+
+    /// A `[...]` pattern, as written in the grammar
+    Pattern(&'static str),
+
+    /// A name given by `expected!()` or returned from a `{? }` block
+    Named(&'static str),
+
+    /// The end of the input
+    Eof,
+}
+
+impl Expected {
+    /// The text shown for this item in error messages
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Expected::Literal(s) | Expected::Pattern(s) | Expected::Named(s) => s,
+            Expected::Eof => "EOF",
+        }
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str(self.as_str())
+    }
+}
 
 /// A set of literals or names that failed to match
-#[derive(PartialEq, Eq, Debug, Clone)]
+///
+/// Items are kept in a deterministic order: literals, then patterns, then
+/// names, then end of input, each sorted by their text.
+///
+/// The strings matched by `literal!()` are only known at runtime, so they
+/// aren't [`Expected`] items; they are listed by
+/// [`dynamic_literals`](ExpectedSet::dynamic_literals) rather than
+/// [`tokens`](ExpectedSet::tokens), nor counted by [`len`](ExpectedSet::len),
+/// and are included when the set is displayed.
+///
+/// Sets are equal when they expect the same items. The rule stacks recorded
+/// with the items, available from [`contexts`](ExpectedSet::contexts), aren't
+/// compared.
+#[derive(Debug, Clone, Default)]
 pub struct ExpectedSet {
-    expected: HashSet<&'static str>,
+    /// Each item, with the stack of rules active when it was first recorded
+    expected: BTreeMap<Expected, Vec<&'static str>>,
+
+    /// The quoted text of each `literal!()`, with its rule stack
+    dynamic: BTreeMap<String, Vec<&'static str>>,
 }
 
 impl ExpectedSet {
     /// Iterator of expected literals
     pub fn tokens<'a>(&'a self) -> impl Iterator<Item = &'static str> + 'a {
-        self.expected.iter().map(|x| *x)
+        self.expected.keys().map(Expected::as_str)
+    }
+
+    /// Iterator of the expected items, with their kind. Doesn't include the
+    /// strings of `literal!()`.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = Expected> + 'a {
+        self.expected.keys().copied()
+    }
+
+    /// Iterator of the expected `"literals"`
+    pub fn literals<'a>(&'a self) -> impl Iterator<Item = &'static str> + 'a {
+        self.iter().filter_map(|e| match e {
+            Expected::Literal(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Iterator of the strings expected by `literal!()`, quoted like the
+    /// `"literals"`
+    pub fn dynamic_literals<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a {
+        self.dynamic.keys().map(String::as_str)
+    }
+
+    /// Iterator of the expected `[patterns]`
+    pub fn patterns<'a>(&'a self) -> impl Iterator<Item = &'static str> + 'a {
+        self.iter().filter_map(|e| match e {
+            Expected::Pattern(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Iterator of the names from `expected!()` and `{? }` blocks
+    pub fn names<'a>(&'a self) -> impl Iterator<Item = &'static str> + 'a {
+        self.iter().filter_map(|e| match e {
+            Expected::Named(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Whether the end of input would have been accepted
+    pub fn expects_eof(&self) -> bool {
+        self.expected.contains_key(&Expected::Eof)
+    }
+
+    /// Whether `item` is one of the expected items
+    pub fn contains(&self, item: &Expected) -> bool {
+        self.expected.contains_key(item)
+    }
+
+    /// Number of expected items, as listed by `tokens()`. Doesn't count the
+    /// strings of `literal!()`.
+    pub fn len(&self) -> usize {
+        self.expected.len()
+    }
+
+    /// Whether no expected items were reported. There may still be strings
+    /// of `literal!()`.
+    pub fn is_empty(&self) -> bool {
+        self.expected.is_empty()
+    }
+
+    /// The names of the rules, outermost first, that were being parsed when
+    /// `item` was expected. A rule with `#[context("...")]` is listed by that
+    /// label instead of its name.
+    pub fn context_of(&self, item: &Expected) -> Option<&[&'static str]> {
+        self.expected.get(item).map(Vec::as_slice)
+    }
+
+    /// Iterator of the expected items, each with the names of the rules,
+    /// outermost first, that were being parsed when it was expected. See
+    /// [`context_of`](ExpectedSet::context_of).
+    pub fn contexts<'a>(&'a self) -> impl Iterator<Item = (Expected, &'a [&'static str])> + 'a {
+        self.expected.iter().map(|(item, stack)| (*item, stack.as_slice()))
+    }
+
+    /// The rule stack shared by all expected items: the rules, outermost
+    /// first, that were being parsed at the error position.
+    pub fn context(&self) -> &[&'static str] {
+        let mut stacks = self.expected.values().chain(self.dynamic.values());
+        let first = match stacks.next() {
+            Some(first) => first,
+            None => return &[],
+        };
+        let len = stacks.fold(first.len(), |len, stack| {
+            first[..len].iter().zip(stack).take_while(|(a, b)| a == b).count()
+        });
+        &first[..len]
     }
 }
 
+impl PartialEq for ExpectedSet {
+    fn eq(&self, other: &ExpectedSet) -> bool {
+        self.expected.keys().eq(other.expected.keys()) && self.dynamic.keys().eq(other.dynamic.keys())
+    }
+}
+
+impl Eq for ExpectedSet {}
+
 impl Display for ExpectedSet {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        if self.expected.is_empty() {
+        let tokens = self.tokens().map(|s| -> &str { s });
+        let mut errors = tokens.chain(self.dynamic_literals()).collect::<Vec<_>>();
+
+        if errors.is_empty() {
             write!(fmt, "<unreported>")?;
-        } else if self.expected.len() == 1 {
-            write!(fmt, "{}", self.expected.iter().next().unwrap())?;
+        } else if errors.len() == 1 {
+            write!(fmt, "{}", errors[0])?;
         } else {
-            let mut errors = self.tokens().collect::<Vec<_>>();
             errors.sort();
             let mut iter = errors.into_iter();
 
@@ -38,26 +192,59 @@ impl Display for ExpectedSet {
     }
 }
 
+/// Why a parse failed
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ErrorKind {
+    /// The input doesn't match the grammar.
+    Syntax,
+
+    /// Rules were nested deeper than the limit set with `#![max_depth(n)]` or
+    /// [`Limits::max_depth`]. The parse stopped at the error location.
+    NestingTooDeep,
+
+    /// The parse used up [`Limits::max_steps`] or was cancelled through
+    /// [`Limits::cancel`]. The parse stopped at the error location.
+    Aborted,
+}
+
 /// An error from a parse failure
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct ParseError<L> {
-    /// The furthest position the parser reached in the input
+    /// The furthest position the parser reached in the input, or where it
+    /// stopped for errors other than `ErrorKind::Syntax`
     pub location: L,
 
-    /// The set of literals that failed to match at that position
+    /// The set of literals that failed to match at that position. Empty for
+    /// errors other than `ErrorKind::Syntax`.
     pub expected: ExpectedSet,
+
+    /// Why the parse failed
+    pub kind: ErrorKind,
+}
+
+impl<L> ParseError<L> {
+    /// The rules, outermost first, that were being parsed at the error
+    /// position. See [`ExpectedSet::context`].
+    pub fn context(&self) -> &[&'static str] {
+        self.expected.context()
+    }
 }
 
 impl<L: Display> Display for ParseError<L> {
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
-        write!(
-            fmt,
-            "error at {}: expected {}",
-            self.location, self.expected
-        )
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.kind {
+            ErrorKind::Syntax => write!(
+                fmt,
+                "error at {}: expected {}",
+                self.location, self.expected
+            ),
+            ErrorKind::NestingTooDeep => write!(fmt, "nesting too deep at {}", self.location),
+            ErrorKind::Aborted => write!(fmt, "parse aborted at {}", self.location),
+        }
     }
 }
 
+#[cfg(feature = "std")]
 impl<L: Display + Debug> ::std::error::Error for ParseError<L> {
     fn description(&self) -> &str {
         "parse error"
@@ -65,39 +252,210 @@ impl<L: Display + Debug> ::std::error::Error for ParseError<L> {
 }
 
 #[doc(hidden)]
-pub struct ErrorState {
+pub struct ErrorState<'t> {
     pub max_err_pos: usize,
     pub suppress_fail: usize,
     pub reparsing_on_error: bool,
     pub expected: ExpectedSet,
+
+    /// Rules entered during the error-reporting reparse and not yet left
+    pub rule_stack: Vec<&'static str>,
+
+    /// Number of rules (and `precedence!{}` levels) currently being parsed
+    depth: usize,
+
+    /// `usize::MAX` when there is no limit, and 0 once the parse has stopped
+    max_depth: usize,
+
+    /// Rules that can be entered before `check_budget` is called again.
+    /// `usize::MAX` when there is neither a step budget nor a cancellation
+    /// flag, so the check is never reached.
+    countdown: usize,
+
+    /// Steps of the budget not yet handed out to `countdown`
+    steps_left: u64,
+
+    max_steps: Option<u64>,
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    cancel: Option<Arc<AtomicBool>>,
+
+    /// Set when a limit stops the parse, after which every rule fails
+    pub stopped: Option<(ErrorKind, usize)>,
+
+    /// Errors skipped over by `recover!()`, with positions not yet
+    /// converted to the input's `PositionRepr`
+    pub recovered: Vec<ParseError<usize>>,
+
+    /// Receives the steps of the parse, in parsers generated with the `trace`
+    /// feature
+    pub tracer: Option<&'t mut dyn Tracer>,
+
+    /// Counts for each rule, in parsers generated with the `stats` feature
+    pub stats: ParseStats,
+}
+
+/// Error tracking state saved by `recover!()` before trying its expression
+#[doc(hidden)]
+pub struct RecoverCheckpoint {
+    max_err_pos: usize,
+    suppress_fail: usize,
+    reparsing_on_error: bool,
+    expected: Option<ExpectedSet>,
+    recovered_len: usize,
 }
 
-impl ErrorState {
-    pub fn new(initial_pos: usize) -> ErrorState {
+impl<'t> ErrorState<'t> {
+    pub fn new(initial_pos: usize) -> ErrorState<'t> {
         ErrorState {
             max_err_pos: initial_pos,
             suppress_fail: 0,
             reparsing_on_error: false,
-            expected: ExpectedSet {
-                expected: HashSet::new(),
-            },
+            expected: ExpectedSet::default(),
+            rule_stack: Vec::new(),
+            depth: 0,
+            max_depth: usize::MAX,
+            countdown: usize::MAX,
+            steps_left: 0,
+            max_steps: None,
+            #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+            cancel: None,
+            stopped: None,
+            recovered: Vec::new(),
+            tracer: None,
+            stats: ParseStats::default(),
+        }
+    }
+
+    /// Apply the limits for a parse, where `max_depth` is the grammar's
+    /// `#![max_depth(n)]`, used if `limits` doesn't set one.
+    pub fn set_limits(&mut self, limits: &Limits, max_depth: Option<usize>) {
+        self.max_depth = limits.max_depth.or(max_depth).unwrap_or(usize::MAX);
+        self.max_steps = limits.max_steps;
+        #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+        {
+            self.cancel = limits.cancel.clone();
+        }
+        self.reset_budget();
+    }
+
+    /// Whether the parse has a cancellation flag, which makes it check the budget
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    fn cancellable(&self) -> bool {
+        self.cancel.is_some()
+    }
+
+    #[cfg(not(all(target_has_atomic = "8", target_has_atomic = "ptr")))]
+    fn cancellable(&self) -> bool {
+        false
+    }
+
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    fn cancelled(&self) -> bool {
+        match &self.cancel {
+            Some(cancel) => cancel.load(Ordering::Relaxed),
+            None => false,
+        }
+    }
+
+    #[cfg(not(all(target_has_atomic = "8", target_has_atomic = "ptr")))]
+    fn cancelled(&self) -> bool {
+        false
+    }
+
+    fn reset_budget(&mut self) {
+        match self.max_steps {
+            Some(steps) => {
+                self.steps_left = steps;
+                self.countdown = 0;
+            }
+            None if self.cancellable() => {
+                self.steps_left = u64::MAX;
+                self.countdown = 0;
+            }
+            None => {
+                self.countdown = usize::MAX;
+            }
+        }
+    }
+
+    /// Called when `countdown` runs out. Stops the parse if it was cancelled
+    /// or the budget is used up, and otherwise hands out the next steps.
+    #[inline(never)]
+    fn check_budget(&mut self, pos: usize) -> bool {
+        if self.cancelled() || self.steps_left == 0 {
+            self.stop(ErrorKind::Aborted, pos);
+            return false;
+        }
+
+        let steps = self.steps_left.min(CANCEL_CHECK_INTERVAL as u64);
+        self.steps_left -= steps;
+        self.countdown = steps as usize;
+        true
+    }
+
+    #[inline(never)]
+    fn stop(&mut self, kind: ErrorKind, pos: usize) {
+        if self.stopped.is_none() {
+            self.stopped = Some((kind, pos));
+            self.max_depth = 0;
+        }
+    }
+
+    /// Called before parsing a rule or `precedence!{}` level at `pos`. Returns
+    /// false if the parse has stopped, and the rule should fail without
+    /// calling `leave_nesting`.
+    #[inline(always)]
+    pub fn enter_nesting(&mut self, pos: usize) -> bool {
+        if self.depth >= self.max_depth {
+            self.stop(ErrorKind::NestingTooDeep, pos);
+            return false;
         }
+        self.depth += 1;
+        true
+    }
+
+    #[inline(always)]
+    pub fn leave_nesting(&mut self) {
+        self.depth -= 1;
     }
 
     pub fn reparse_for_error(&mut self) {
         self.suppress_fail = 0;
         self.reparsing_on_error = true;
+        self.reset_budget();
+    }
+
+    #[inline(never)]
+    pub fn mark_failure_slow_path(&mut self, pos: usize, expected: Expected) {
+        if pos == self.max_err_pos {
+            let stack = &self.rule_stack;
+            self.expected.expected.entry(expected).or_insert_with(|| stack.clone());
+        }
     }
 
     #[inline(never)]
-    pub fn mark_failure_slow_path(&mut self, pos: usize, expected: &'static str) {
+    fn mark_failure_dynamic_slow_path(&mut self, pos: usize, literal: &str) {
         if pos == self.max_err_pos {
-            self.expected.expected.insert(expected);
+            let stack = &self.rule_stack;
+            self.expected.dynamic.entry(format!("{:?}", literal)).or_insert_with(|| stack.clone());
+        }
+    }
+
+    /// Record the failure of `literal!()` to match `literal`.
+    #[inline(always)]
+    pub fn mark_failure_dynamic(&mut self, pos: usize, literal: &str) -> RuleResult<()> {
+        if self.suppress_fail == 0 {
+            if self.reparsing_on_error {
+                self.mark_failure_dynamic_slow_path(pos, literal);
+            } else if pos > self.max_err_pos {
+                self.max_err_pos = pos;
+            }
         }
+        RuleResult::Failed
     }
 
     #[inline(always)]
-    pub fn mark_failure(&mut self, pos: usize, expected: &'static str) -> RuleResult<()> {
+    pub fn mark_failure(&mut self, pos: usize, expected: Expected) -> RuleResult<()> {
         if self.suppress_fail == 0 {
             if self.reparsing_on_error {
                 self.mark_failure_slow_path(pos, expected);
@@ -108,10 +466,143 @@ impl ErrorState {
         RuleResult::Failed
     }
 
+    /// Record entry into a rule, when reparsing to find the context of an error.
+    /// Called before parsing a rule at `pos`, with the same result as
+    /// `enter_nesting`.
+    #[inline(always)]
+    pub fn enter_rule(&mut self, name: &'static str, pos: usize) -> bool {
+        if self.countdown == 0 && !self.check_budget(pos) {
+            return false;
+        }
+        self.countdown -= 1;
+
+        if !self.enter_nesting(pos) {
+            return false;
+        }
+        if self.reparsing_on_error {
+            self.rule_stack.push(name);
+        }
+        true
+    }
+
+    #[inline(always)]
+    pub fn leave_rule(&mut self) {
+        self.leave_nesting();
+        if self.reparsing_on_error {
+            self.rule_stack.pop();
+        }
+    }
+
     pub fn into_parse_error<I: Parse + ?Sized>(self, input: &I) -> ParseError<I::PositionRepr> {
-        ParseError {
-            location: Parse::position_repr(input, self.max_err_pos.into()),
-            expected: self.expected,
+        match self.stopped {
+            Some((kind, pos)) => ParseError {
+                location: Parse::position_repr(input, pos),
+                expected: ExpectedSet::default(),
+                kind,
+            },
+            None => ParseError {
+                location: Parse::position_repr(input, self.max_err_pos.into()),
+                expected: self.expected,
+                kind: ErrorKind::Syntax,
+            },
+        }
+    }
+
+    /// Errors recorded by `recover!()`, in input order.
+    pub fn into_recovered_errors<I: Parse + ?Sized>(self, input: &I) -> Vec<ParseError<I::PositionRepr>> {
+        with_position_repr(self.recovered, input)
+    }
+
+    /// Errors recorded by `recover!()`, followed by the error that stopped the parse.
+    /// A parse stopped by a limit didn't get through the input, so the errors it
+    /// recorded on the way are left out, and only the limit's error is returned.
+    pub fn into_parse_errors<I: Parse + ?Sized>(mut self, input: &I) -> Vec<ParseError<I::PositionRepr>> {
+        if self.stopped.is_some() {
+            return alloc::vec![self.into_parse_error(input)];
+        }
+
+        let mut errors = with_position_repr(core::mem::take(&mut self.recovered), input);
+        errors.push(self.into_parse_error(input));
+        errors
+    }
+
+    /// Save the error tracking state before `recover!()` tries its expression.
+    pub fn recover_checkpoint(&self) -> RecoverCheckpoint {
+        RecoverCheckpoint {
+            max_err_pos: self.max_err_pos,
+            suppress_fail: self.suppress_fail,
+            reparsing_on_error: self.reparsing_on_error,
+            expected: if self.reparsing_on_error { Some(self.expected.clone()) } else { None },
+            recovered_len: self.recovered.len(),
         }
     }
+
+    /// After the expression of `recover!()` failed at `pos`, start tracking its
+    /// errors in isolation so it can be re-run to find the furthest failure.
+    pub fn recover_isolate(&mut self, checkpoint: &RecoverCheckpoint, pos: usize) {
+        self.recovered.truncate(checkpoint.recovered_len);
+        self.max_err_pos = pos;
+        self.suppress_fail = 0;
+        self.reparsing_on_error = false;
+        self.expected.expected.clear();
+        self.expected.dynamic.clear();
+    }
+
+    /// Restore the error tracking state of the enclosing parse after
+    /// `recover!()` re-ran its failed expression, returning the error found.
+    pub fn recover_end(&mut self, checkpoint: RecoverCheckpoint) -> ParseError<usize> {
+        let outer_expected = checkpoint.expected.unwrap_or_default();
+        let error = ParseError {
+            location: self.max_err_pos,
+            expected: core::mem::replace(&mut self.expected, outer_expected),
+            kind: ErrorKind::Syntax,
+        };
+
+        self.recovered.truncate(checkpoint.recovered_len);
+        self.max_err_pos = checkpoint.max_err_pos;
+        self.suppress_fail = checkpoint.suppress_fail;
+        self.reparsing_on_error = checkpoint.reparsing_on_error;
+
+        error
+    }
+
+    /// `recover!()` skipped to its synchronization expression: keep the error.
+    pub fn record_recovered_error(&mut self, error: ParseError<usize>) {
+        if self.suppress_fail == 0 {
+            self.recovered.push(error);
+        }
+    }
+
+    /// `recover!()` found nothing to synchronize on: report the error as if
+    /// the expression had failed without `recover!()`.
+    pub fn mark_unrecovered_error(&mut self, error: ParseError<usize>) {
+        if self.suppress_fail == 0 {
+            if self.reparsing_on_error {
+                if error.location == self.max_err_pos {
+                    for (item, stack) in error.expected.expected {
+                        self.expected.expected.entry(item).or_insert(stack);
+                    }
+                    for (literal, stack) in error.expected.dynamic {
+                        self.expected.dynamic.entry(literal).or_insert(stack);
+                    }
+                }
+            } else if error.location > self.max_err_pos {
+                self.max_err_pos = error.location;
+            }
+        }
+    }
+}
+
+fn with_position_repr<I: Parse + ?Sized>(errors: Vec<ParseError<usize>>, input: &I) -> Vec<ParseError<I::PositionRepr>> {
+    let positions: Vec<usize> = errors.iter().map(|e| e.location).collect();
+    input
+        .position_reprs(&positions)
+        .into_iter()
+        .zip(errors)
+        .map(|(location, e)| ParseError {
+            location,
+            expected: e.expected,
+            kind: e.kind,
+        })
+        .collect()
 }
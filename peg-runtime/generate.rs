@@ -0,0 +1,133 @@
+//! Random sentences of a grammar, generated by the `generator` module of a
+//! grammar with `#![generator]`
+
+use core::fmt::{self, Display};
+
+/// The most items a repetition without an upper bound generates beyond its
+/// minimum.
+pub const MAX_EXTRA_REPEATS: usize = 4;
+
+/// Attempts at drawing a character matching a `[...]` pattern before giving
+/// up on it.
+const PATTERN_ATTEMPTS: usize = 256;
+
+/// The depth needed by an expression the generator can't produce, never
+/// available.
+#[doc(hidden)]
+pub const UNREACHABLE: usize = usize::MAX;
+
+/// A small seeded random number generator, so that a generated sentence can be
+/// reproduced from its seed.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// The next random number, from the SplitMix64 sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A random number in `0..n`. `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Returned when asked to generate a rule that can't be generated. The
+/// grammar's `generator::UNSATISFIABLE` lists these rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unsatisfiable {
+    /// The rule containing the expression that couldn't be generated
+    pub rule: &'static str,
+
+    /// What the expression was
+    pub reason: &'static str,
+}
+
+impl Display for Unsatisfiable {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "cannot generate rule `{}`: {}", self.rule, self.reason)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for Unsatisfiable {
+    fn description(&self) -> &str {
+        "cannot generate rule"
+    }
+}
+
+/// Pick one of the alternatives of a choice, each given by the depth it needs.
+/// Any alternative that fits in `depth` is equally likely; if none does, the
+/// first one needing the least depth is picked.
+#[doc(hidden)]
+pub fn choose(rng: &mut Rng, depth: usize, needed: &[usize]) -> usize {
+    let fitting = needed.iter().filter(|&&n| n <= depth).count();
+    if fitting == 0 {
+        let least = needed.iter().copied().min().unwrap_or(UNREACHABLE);
+        return needed.iter().position(|&n| n == least).unwrap_or(0);
+    }
+
+    let pick = rng.below(fitting);
+    needed
+        .iter()
+        .enumerate()
+        .filter(|&(_, &n)| n <= depth)
+        .nth(pick)
+        .map(|(i, _)| i)
+        .unwrap()
+}
+
+/// The number of items for a repetition of `min` to `max` items, each needing
+/// `needed` depth. Only the minimum is generated once there isn't the depth
+/// for more.
+#[doc(hidden)]
+pub fn repeat_count(rng: &mut Rng, depth: usize, needed: usize, min: usize, max: Option<usize>) -> usize {
+    if needed > depth {
+        return min;
+    }
+    let extra = match max {
+        Some(max) => max.saturating_sub(min),
+        None => MAX_EXTRA_REPEATS,
+    };
+    min + rng.below(extra + 1)
+}
+
+/// Whether to generate an optional expression needing `needed` depth.
+#[doc(hidden)]
+pub fn optional(rng: &mut Rng, depth: usize, needed: usize) -> bool {
+    needed <= depth && rng.below(2) == 0
+}
+
+/// Draw a character accepted by a `[...]` pattern. Candidates come from the
+/// character ranges spelled out in the pattern, and, if the pattern accepts
+/// other characters too, from printable ASCII.
+#[doc(hidden)]
+pub fn pattern_char(rng: &mut Rng, ranges: &[(char, char)], ascii: bool, accept: impl Fn(char) -> bool) -> Option<char> {
+    for _ in 0..PATTERN_ATTEMPTS {
+        let candidate = if ranges.is_empty() || (ascii && rng.below(2) == 0) {
+            char::from(b' ' + rng.below(95) as u8)
+        } else {
+            let (lo, hi) = ranges[rng.below(ranges.len())];
+            let span = (hi as u32).saturating_sub(lo as u32) as usize + 1;
+            match char::from_u32(lo as u32 + rng.below(span) as u32) {
+                Some(c) => c,
+                None => continue,
+            }
+        };
+        if accept(candidate) {
+            return Some(candidate);
+        }
+    }
+    None
+}
@@ -0,0 +1,81 @@
+//! Support for `#![incremental]` grammars, which keep `#[cache]` results across edits
+
+use crate::memo::MemoTable;
+use crate::RuleResult;
+use alloc::vec::Vec;
+
+/// A memoized rule result, along with how far the rule looked into the input.
+#[derive(Clone)]
+pub struct CacheEntry<T> {
+    pub result: RuleResult<T>,
+
+    /// Position just past the last input examined while matching the rule,
+    /// including a failed lookup at the end of input.
+    pub examined_end: usize,
+
+    /// The indentation stack the rule left, in a grammar using `indent!()`.
+    pub indent: usize,
+}
+
+/// Update a rule cache for an edit that replaced `start..end` of the old input
+/// with `new_len` positions of new input.
+///
+/// Entries that examined only input before the edit are kept as is, and those
+/// starting after it are moved to their new position. All others are dropped.
+pub fn apply_edit<T>(
+    cache: &mut MemoTable<CacheEntry<T>>,
+    start: usize,
+    end: usize,
+    new_len: usize,
+) {
+    assert!(start <= end, "invalid edit range {}..{}", start, end);
+    for (pos, entry) in cache.take_entries() {
+        if let Some((pos, entry)) = edit_entry(pos, entry, start, end, new_len) {
+            cache.insert(pos, entry);
+        }
+    }
+}
+
+/// `apply_edit` for the cache of a rule with parameters, which holds the
+/// entries for each key of argument values at a position.
+pub fn apply_edit_keyed<K: Eq, T>(
+    cache: &mut MemoTable<Vec<(K, CacheEntry<T>)>>,
+    start: usize,
+    end: usize,
+    new_len: usize,
+) {
+    assert!(start <= end, "invalid edit range {}..{}", start, end);
+    for (pos, entries) in cache.take_entries() {
+        for (key, entry) in entries {
+            if let Some((pos, entry)) = edit_entry(pos, entry, start, end, new_len) {
+                cache.insert_keyed(pos, key, entry);
+            }
+        }
+    }
+}
+
+fn edit_entry<T>(
+    pos: usize,
+    entry: CacheEntry<T>,
+    start: usize,
+    end: usize,
+    new_len: usize,
+) -> Option<(usize, CacheEntry<T>)> {
+    let shift = |p: usize| p - end + start + new_len;
+
+    if entry.examined_end <= start {
+        Some((pos, entry))
+    } else if pos >= end {
+        let result = match entry.result {
+            RuleResult::Matched(p, v) => RuleResult::Matched(shift(p), v),
+            RuleResult::Failed => RuleResult::Failed,
+        };
+        let examined_end = match entry.examined_end {
+            usize::MAX => usize::MAX,
+            e => shift(e),
+        };
+        Some((shift(pos), CacheEntry { result, examined_end, indent: entry.indent }))
+    } else {
+        None
+    }
+}
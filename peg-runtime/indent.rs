@@ -0,0 +1,98 @@
+//! Support for indentation-sensitive grammars using `indent!()`, `dedent!()`
+//! and `same_indent!()`
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Tabs advance the indentation to the next multiple of this many columns.
+pub const TAB_WIDTH: usize = 8;
+
+/// Measure the spaces and tabs at the start of `bytes`, returning how many
+/// bytes they take up and the width of the indentation they make. A space is
+/// one column wide, and a tab advances to the next multiple of [`TAB_WIDTH`],
+/// so that a line indented with a tab lines up with one indented with eight
+/// spaces.
+pub fn measure(bytes: &[u8]) -> (usize, usize) {
+    let mut width = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b' ' => width += 1,
+            b'\t' => width += TAB_WIDTH - width % TAB_WIDTH,
+            _ => return (i, width),
+        }
+    }
+    (bytes.len(), width)
+}
+
+/// The stack of indentation widths of the blocks being parsed.
+///
+/// Each distinct stack is stored once and identified by a number, so that the
+/// current stack can be saved and restored when backtracking, and used in the
+/// key of a `#[cache]` entry, by copying that number.
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct IndentStack {
+    /// The innermost width of each stack, and the stack below it
+    levels: Vec<(usize, usize)>,
+    ids: BTreeMap<(usize, usize), usize>,
+    current: usize,
+}
+
+impl IndentStack {
+    /// A stack holding only the base level, at width 0.
+    pub fn new() -> IndentStack {
+        IndentStack {
+            levels: alloc::vec![(0, 0)],
+            ids: BTreeMap::new(),
+            current: 0,
+        }
+    }
+
+    /// The same stacks, with the current one back at the base level, for the
+    /// next parse.
+    pub fn reset(self) -> IndentStack {
+        IndentStack { current: 0, ..self }
+    }
+
+    /// The number identifying the current stack.
+    #[inline(always)]
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Go back to a stack identified by `current()`.
+    #[inline(always)]
+    pub fn restore(&mut self, id: usize) {
+        self.current = id;
+    }
+
+    /// The width of the innermost level.
+    #[inline(always)]
+    pub fn width(&self) -> usize {
+        self.levels[self.current].0
+    }
+
+    pub fn push(&mut self, width: usize) {
+        let below = self.current;
+        let levels = &mut self.levels;
+        self.current = *self.ids.entry((width, below)).or_insert_with(|| {
+            levels.push((width, below));
+            levels.len() - 1
+        });
+    }
+
+    /// Remove the innermost level, returning false if only the base level is left.
+    pub fn pop(&mut self) -> bool {
+        if self.current == 0 {
+            return false;
+        }
+        self.current = self.levels[self.current].1;
+        true
+    }
+}
+
+impl Default for IndentStack {
+    fn default() -> IndentStack {
+        IndentStack::new()
+    }
+}
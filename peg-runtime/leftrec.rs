@@ -0,0 +1,130 @@
+//! Support for `#[cache_left_rec]` rules that are left recursive through other rules
+//!
+//! This is the algorithm from "Packrat Parsers Can Support Left Recursion" (Warth,
+//! Douglass and Millstein, 2008), with the rules that can be involved in growing a
+//! result found ahead of time: they are the other rules in the head's cycle.
+
+use crate::RuleResult;
+use alloc::vec::Vec;
+
+/// How a call to a rule in a left-recursive cycle should be handled.
+pub enum Involvement {
+    /// No rule of the cycle is growing a result at this position, so the rule
+    /// runs as usual.
+    Outside,
+
+    /// A result is being grown at this position, and the rule hasn't been
+    /// evaluated in the current iteration. It should be evaluated, and its
+    /// result remembered for the rest of the iteration.
+    Evaluate,
+
+    /// The rule is being evaluated or was already evaluated in the current
+    /// iteration, so the result it last had is used.
+    Recall,
+}
+
+struct Head {
+    group: usize,
+    pos: usize,
+    active: Vec<usize>,
+    done: Vec<usize>,
+}
+
+/// The rules growing a left-recursive result, innermost last.
+#[derive(Default)]
+pub struct Heads {
+    stack: Vec<Head>,
+}
+
+impl Heads {
+    fn find(&mut self, group: usize, pos: usize) -> Option<&mut Head> {
+        self.stack
+            .iter_mut()
+            .rev()
+            .find(|head| head.group == group && head.pos == pos)
+    }
+
+    /// Start growing a result for `rule` of cycle `group` at `pos`.
+    pub fn begin(&mut self, group: usize, pos: usize, rule: usize) {
+        self.stack.push(Head { group, pos, active: alloc::vec![rule], done: Vec::new() });
+    }
+
+    /// Finish the innermost result started with `begin`.
+    pub fn end(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Start another iteration of growing the result for `group` at `pos`, in
+    /// which the rules that aren't being evaluated will be evaluated again.
+    pub fn grow(&mut self, group: usize, pos: usize) {
+        if let Some(head) = self.find(group, pos) {
+            head.done.clear();
+        }
+    }
+
+    /// Called when `rule` of cycle `group` is called at `pos`. On `Evaluate`,
+    /// `leave` must be called once the rule is evaluated.
+    pub fn enter(&mut self, group: usize, pos: usize, rule: usize) -> Involvement {
+        match self.find(group, pos) {
+            None => Involvement::Outside,
+            Some(head) if head.active.contains(&rule) || head.done.contains(&rule) => Involvement::Recall,
+            Some(head) => {
+                head.active.push(rule);
+                Involvement::Evaluate
+            }
+        }
+    }
+
+    pub fn leave(&mut self, group: usize, pos: usize, rule: usize) {
+        if let Some(head) = self.find(group, pos) {
+            head.active.retain(|&r| r != rule);
+            head.done.push(rule);
+        }
+    }
+}
+
+/// The results a rule had while growing left-recursive results, by position,
+/// each with the indentation stack it left (see `IndentStack::current`), or 0
+/// in grammars without indentation.
+pub struct Memo<T> {
+    entries: Vec<(usize, RuleResult<T>, usize)>,
+}
+
+impl<T> Default for Memo<T> {
+    fn default() -> Self {
+        Memo { entries: Vec::new() }
+    }
+}
+
+impl<T: Clone> Memo<T> {
+    pub fn recall(&self, pos: usize) -> RuleResult<T> {
+        match self.entries.iter().find(|(p, _, _)| *p == pos) {
+            Some((_, result, _)) => result.clone(),
+            None => RuleResult::Failed,
+        }
+    }
+}
+
+impl<T> Memo<T> {
+    /// The indentation stack left by the result `recall` gives.
+    pub fn indent(&self, pos: usize) -> usize {
+        match self.entries.iter().find(|(p, _, _)| *p == pos) {
+            Some((_, _, indent)) => *indent,
+            None => 0,
+        }
+    }
+
+    pub fn remember(&mut self, pos: usize, result: RuleResult<T>, indent: usize) {
+        match self.entries.iter_mut().find(|(p, _, _)| *p == pos) {
+            Some(entry) => {
+                entry.1 = result;
+                entry.2 = indent;
+            }
+            None => self.entries.push((pos, result, indent)),
+        }
+    }
+
+    pub fn forget(&mut self, pos: usize) {
+        self.entries.retain(|(p, _, _)| *p != pos);
+    }
+}
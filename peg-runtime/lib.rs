@@ -1,8 +1,45 @@
-use std::fmt::Display;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+// Re-exported for generated code, which can't name `alloc` itself in a crate
+// without `extern crate alloc`.
+#[doc(hidden)]
+pub extern crate alloc as __alloc;
+#[doc(hidden)]
+pub extern crate core as __core;
+
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Display;
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+use core::sync::atomic::AtomicBool;
+
+/// Map used for `#[cache(hash)]` tables: a `HashMap` with the `std` feature, and a
+/// `BTreeMap` without it.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub type CacheMap<K, V> = std::collections::HashMap<K, V>;
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+pub type CacheMap<K, V> = alloc::collections::BTreeMap<K, V>;
+
+#[cfg(all(feature = "coverage", target_has_atomic = "ptr"))]
+pub mod coverage;
 pub mod error;
+pub mod generate;
+#[doc(hidden)]
+pub mod incremental;
+pub mod indent;
+#[doc(hidden)]
+pub mod leftrec;
+#[doc(hidden)]
+pub mod memo;
 mod slice;
+pub mod stats;
 pub mod str;
+pub mod trace;
 
 /// The result type used internally in the parser.
 ///
@@ -14,12 +51,110 @@ pub enum RuleResult<T> {
     Failed,
 }
 
+/// Limits on a parse, for inputs that could otherwise exhaust the stack or
+/// take too long to parse.
+///
+/// Passed to the `<rule>_with_limits` function generated for each `pub rule`.
+/// A limit that isn't set here falls back to the grammar's, if any. More limits
+/// may be added, so a `Limits` is built from `Limits::default()` with the
+/// `with_*` methods:
+///
+/// ```
+/// let limits = peg_runtime::Limits::default().with_max_depth(100).with_max_steps(1_000_000);
+/// assert_eq!(limits.max_depth, Some(100));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct Limits {
+    /// The maximum number of rules (and `precedence!{}` levels) that can be
+    /// nested at any point. A parse reaching it fails with
+    /// [`ErrorKind::NestingTooDeep`](error::ErrorKind::NestingTooDeep).
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of rules run, not counting results reused from a
+    /// `#[cache]`. A parse using them up fails with
+    /// [`ErrorKind::Aborted`](error::ErrorKind::Aborted). When the input
+    /// doesn't parse, reparsing it to report the error gets the same budget
+    /// again.
+    pub max_steps: Option<u64>,
+
+    /// A flag that can be set from another thread to abort the parse with
+    /// [`ErrorKind::Aborted`](error::ErrorKind::Aborted). It is checked every
+    /// [`CANCEL_CHECK_INTERVAL`] rules run. Only on targets with atomics.
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    pub cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Limits {
+    /// Set [`max_depth`](Limits::max_depth).
+    pub fn with_max_depth(mut self, max_depth: usize) -> Limits {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Set [`max_steps`](Limits::max_steps).
+    pub fn with_max_steps(mut self, max_steps: u64) -> Limits {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Set [`cancel`](Limits::cancel).
+    #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Limits {
+        self.cancel = Some(cancel);
+        self
+    }
+}
+
+/// Limits are equal when they set the same numbers, and the same cancellation
+/// flag, if any.
+impl PartialEq for Limits {
+    fn eq(&self, other: &Limits) -> bool {
+        #[cfg(all(target_has_atomic = "8", target_has_atomic = "ptr"))]
+        let same_cancel = match (&self.cancel, &other.cancel) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        #[cfg(not(all(target_has_atomic = "8", target_has_atomic = "ptr")))]
+        let same_cancel = true;
+
+        self.max_depth == other.max_depth && self.max_steps == other.max_steps && same_cancel
+    }
+}
+
+impl Eq for Limits {}
+
+/// How many rules are run between checks of [`Limits::cancel`].
+pub const CANCEL_CHECK_INTERVAL: usize = 1024;
+
 /// A type that can be used as input to a parser.
 pub trait Parse {
     type PositionRepr: Display;
     fn start<'input>(&'input self) -> usize;
     fn is_eof<'input>(&'input self, p: usize) -> bool;
+
+    /// Convert a position to the form shown in errors.
+    ///
+    /// Each call may scan the input: for `str`, it counts the lines before `p`,
+    /// which takes time linear in `p`. Converting many positions this way, such
+    /// as the `position!()` spans of every node of a syntax tree, is quadratic
+    /// in the input length. Use [`position_reprs`](Parse::position_reprs) for a
+    /// batch of positions, or build a [`str::LineIndex`] once per input and
+    /// convert with its `line_col`.
     fn position_repr<'input>(&'input self, p: usize) -> Self::PositionRepr;
+
+    /// Convert several positions with `position_repr`. Input types where that is
+    /// expensive can override this to share work between the positions.
+    fn position_reprs(&self, positions: &[usize]) -> Vec<Self::PositionRepr> {
+        positions.iter().map(|&p| self.position_repr(p)).collect()
+    }
+
+    /// The number of positions in the input, if known. Used to choose the
+    /// storage for `#[cache]` tables.
+    fn input_len(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// A parser input type supporting the `[...]` syntax.
@@ -38,6 +173,27 @@ pub trait ParseLiteral: Parse {
     fn parse_string_literal(&self, pos: usize, literal: &str) -> RuleResult<()>;
 }
 
+/// A parser input type supporting the case-insensitive `"literal"i` syntax.
+pub trait ParseLiteralCaseInsensitive: Parse {
+    /// Attempt to match the `literal` string at `pos` ignoring case, returning
+    /// whether it matched or failed.
+    fn parse_string_literal_ci(&self, pos: usize, literal: &str) -> RuleResult<()>;
+}
+
+/// A parser input type supporting the `b"literal"` byte string syntax.
+pub trait ParseByteLiteral: Parse {
+    /// Attempt to match the `literal` bytes at `pos`, returning whether it
+    /// matched or failed.
+    fn parse_byte_literal(&self, pos: usize, literal: &[u8]) -> RuleResult<()>;
+}
+
+/// A parser input type supporting `indent!()`, `dedent!()` and `same_indent!()`.
+pub trait ParseIndentation: Parse {
+    /// Skip the spaces and tabs at `pos`, returning the position after them and
+    /// the width of the indentation, as counted by [`indent::measure`].
+    fn parse_indentation(&self, pos: usize) -> (usize, usize);
+}
+
 /// A parser input type supporting the `$()` syntax.
 pub trait ParseSlice<'input>: Parse {
     /// Type of a slice of the input.
@@ -0,0 +1,198 @@
+//! Storage for the results of `#[cache]` rules, indexed by input position
+
+use crate::CacheMap;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Inputs up to this many positions use `Dense` tables when no storage is
+/// selected, and longer ones use `Chunked` tables.
+pub const AUTO_DENSE_MAX_LEN: usize = 1 << 16;
+
+const CHUNK_BITS: usize = 6;
+const CHUNK_LEN: usize = 1 << CHUNK_BITS;
+
+/// How a `MemoTable` stores its entries
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Storage {
+    /// `Dense` for short inputs and `Chunked` for long or unknown lengths.
+    Auto,
+
+    /// A `Vec` with a slot for every position up to the furthest one stored.
+    /// Fastest, but uses memory for positions where the rule was never tried.
+    Dense,
+
+    /// Fixed-size blocks of slots, allocated when the first position in the
+    /// block is stored. Suits rules tried at a scattered subset of positions.
+    Chunked,
+
+    /// A hash map (or `BTreeMap` without the `std` feature). Uses the least
+    /// memory when the rule is tried at only a few positions.
+    Hash,
+}
+
+enum Table<T> {
+    Dense(Vec<Option<T>>),
+    Chunked(Vec<Option<Box<[Option<T>]>>>),
+    Hash(CacheMap<usize, T>),
+}
+
+/// A memo table mapping input positions to cached rule results
+pub struct MemoTable<T> {
+    table: Table<T>,
+
+    /// Entries before this position have been dropped already
+    dropped_before: usize,
+}
+
+impl<T> MemoTable<T> {
+    /// Create an empty table, resolving `Storage::Auto` by the length of the
+    /// input, if known.
+    pub fn new(storage: Storage, input_len: Option<usize>) -> MemoTable<T> {
+        let storage = match (storage, input_len) {
+            (Storage::Auto, Some(len)) if len <= AUTO_DENSE_MAX_LEN => Storage::Dense,
+            (Storage::Auto, _) => Storage::Chunked,
+            (storage, _) => storage,
+        };
+
+        let table = match storage {
+            Storage::Dense => Table::Dense(Vec::new()),
+            Storage::Chunked => Table::Chunked(Vec::new()),
+            Storage::Auto | Storage::Hash => Table::Hash(CacheMap::new()),
+        };
+
+        MemoTable { table, dropped_before: 0 }
+    }
+
+    /// The storage the table uses, never `Auto`.
+    pub fn storage(&self) -> Storage {
+        match self.table {
+            Table::Dense(_) => Storage::Dense,
+            Table::Chunked(_) => Storage::Chunked,
+            Table::Hash(_) => Storage::Hash,
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        match &self.table {
+            Table::Dense(slots) => slots.get(pos)?.as_ref(),
+            Table::Chunked(chunks) => chunks.get(pos >> CHUNK_BITS)?.as_ref()?[pos & (CHUNK_LEN - 1)].as_ref(),
+            Table::Hash(map) => map.get(&pos),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        match &mut self.table {
+            Table::Dense(slots) => slots.get_mut(pos)?.as_mut(),
+            Table::Chunked(chunks) => chunks.get_mut(pos >> CHUNK_BITS)?.as_mut()?[pos & (CHUNK_LEN - 1)].as_mut(),
+            Table::Hash(map) => map.get_mut(&pos),
+        }
+    }
+
+    #[inline]
+    pub fn insert(&mut self, pos: usize, value: T) {
+        match &mut self.table {
+            Table::Dense(slots) => {
+                if pos >= slots.len() {
+                    slots.resize_with(pos + 1, || None);
+                }
+                slots[pos] = Some(value);
+            }
+            Table::Chunked(chunks) => {
+                let chunk = pos >> CHUNK_BITS;
+                if chunk >= chunks.len() {
+                    chunks.resize_with(chunk + 1, || None);
+                }
+                let slots = chunks[chunk].get_or_insert_with(|| (0..CHUNK_LEN).map(|_| None).collect());
+                slots[pos & (CHUNK_LEN - 1)] = Some(value);
+            }
+            Table::Hash(map) => {
+                map.insert(pos, value);
+            }
+        }
+    }
+
+    /// Drop the entries for positions before `pos`, which the parser will not
+    /// return to.
+    pub fn drop_before(&mut self, pos: usize) {
+        if pos <= self.dropped_before {
+            return;
+        }
+        let start = self.dropped_before;
+        self.dropped_before = pos;
+
+        match &mut self.table {
+            Table::Dense(slots) => {
+                let end = pos.min(slots.len());
+                for slot in slots.get_mut(start..end).unwrap_or_default() {
+                    *slot = None;
+                }
+            }
+            Table::Chunked(chunks) => {
+                let end = (pos >> CHUNK_BITS).min(chunks.len());
+                for chunk in chunks.get_mut(start >> CHUNK_BITS..end).unwrap_or_default() {
+                    *chunk = None;
+                }
+                if let Some(Some(slots)) = chunks.get_mut(pos >> CHUNK_BITS) {
+                    for slot in &mut slots[..pos & (CHUNK_LEN - 1)] {
+                        *slot = None;
+                    }
+                }
+            }
+            Table::Hash(map) => map.retain(|&p, _| p >= pos),
+        }
+    }
+
+    /// Remove all entries, returning them with their positions.
+    pub fn take_entries(&mut self) -> Vec<(usize, T)> {
+        match &mut self.table {
+            Table::Dense(slots) => core::mem::take(slots)
+                .into_iter()
+                .enumerate()
+                .filter_map(|(pos, v)| Some((pos, v?)))
+                .collect(),
+            Table::Chunked(chunks) => {
+                let mut entries = Vec::new();
+                for (chunk, slots) in core::mem::take(chunks).into_iter().enumerate() {
+                    let slots = match slots {
+                        Some(slots) => slots,
+                        None => continue,
+                    };
+                    for (i, v) in slots.into_vec().into_iter().enumerate() {
+                        if let Some(v) = v {
+                            entries.push(((chunk << CHUNK_BITS) + i, v));
+                        }
+                    }
+                }
+                entries
+            }
+            Table::Hash(map) => core::mem::take(map).into_iter().collect(),
+        }
+    }
+}
+
+/// Tables for rules with parameters hold, at each position, the results for
+/// each distinct key of argument values. Only a few different keys are
+/// expected at any one position, so they are searched linearly.
+impl<K: Eq, V> MemoTable<Vec<(K, V)>> {
+    #[inline]
+    pub fn get_keyed(&self, pos: usize, key: &K) -> Option<&V> {
+        self.get(pos)?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn insert_keyed(&mut self, pos: usize, key: K, value: V) {
+        let entries = match self.get_mut(pos) {
+            Some(entries) => entries,
+            None => {
+                self.insert(pos, Vec::new());
+                self.get_mut(pos).unwrap()
+            }
+        };
+
+        match entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((key, value)),
+        }
+    }
+}
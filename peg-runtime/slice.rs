@@ -1,4 +1,5 @@
-use super::{Parse, ParseElem, ParseLiteral, ParseSlice, RuleResult};
+use super::{Parse, ParseByteLiteral, ParseElem, ParseIndentation, ParseLiteral, ParseLiteralCaseInsensitive, ParseSlice, RuleResult};
+use crate::indent;
 
 impl<T> Parse for [T] {
     type PositionRepr = usize;
@@ -10,6 +11,10 @@ impl<T> Parse for [T] {
         pos >= self.len()
     }
 
+    fn input_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
     fn position_repr(&self, pos: usize) -> usize {
         pos
     }
@@ -37,6 +42,35 @@ impl ParseLiteral for [u8] {
     }
 }
 
+impl ParseIndentation for [u8] {
+    fn parse_indentation(&self, pos: usize) -> (usize, usize) {
+        let (len, width) = indent::measure(&self[pos..]);
+        (pos + len, width)
+    }
+}
+
+impl ParseByteLiteral for [u8] {
+    fn parse_byte_literal(&self, pos: usize, literal: &[u8]) -> RuleResult<()> {
+        let l = literal.len();
+        if self.len() >= pos + l && &self[pos..pos + l] == literal {
+            RuleResult::Matched(pos + l, ())
+        } else {
+            RuleResult::Failed
+        }
+    }
+}
+
+impl ParseLiteralCaseInsensitive for [u8] {
+    fn parse_string_literal_ci(&self, pos: usize, literal: &str) -> RuleResult<()> {
+        let l = literal.len();
+        if self.len() >= pos + l && self[pos..pos + l].eq_ignore_ascii_case(literal.as_bytes()) {
+            RuleResult::Matched(pos + l, ())
+        } else {
+            RuleResult::Failed
+        }
+    }
+}
+
 impl<'input, T: 'input> ParseSlice<'input> for [T] {
     type Slice = &'input [T];
     fn parse_slice(&'input self, p1: usize, p2: usize) -> &'input [T] {
@@ -0,0 +1,156 @@
+//! Counts of what each rule did during a parse, with the `stats` feature
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+/// A rule evaluated at least this many times at one position, without a
+/// `#[cache]`, is reported as a candidate for one.
+pub const CACHE_CANDIDATE_REPEATS: u32 = 3;
+
+/// What one rule did during a parse
+#[derive(Clone, Debug, Default)]
+pub struct RuleStats {
+    /// Times the rule was called, including calls answered from its cache
+    pub calls: u64,
+
+    /// Evaluations of the rule that matched
+    pub matches: u64,
+
+    /// Evaluations of the rule that failed
+    pub failures: u64,
+
+    /// Total length of input matched by the evaluations that matched
+    pub consumed: u64,
+
+    /// Calls answered from the rule's `#[cache]`
+    pub cache_hits: u64,
+
+    /// Calls to a `#[cache]` rule that had to evaluate it
+    pub cache_misses: u64,
+
+    /// Evaluations at a position where the rule had already been evaluated
+    pub repeats: u64,
+
+    /// The most evaluations of the rule at any one position
+    pub max_at_position: u32,
+
+    positions: BTreeMap<usize, u32>,
+}
+
+impl RuleStats {
+    /// Times the rule was run, rather than answered from a cache.
+    pub fn evaluations(&self) -> u64 {
+        self.matches + self.failures
+    }
+
+    /// Whether the rule has no `#[cache]` and was evaluated at least
+    /// [`CACHE_CANDIDATE_REPEATS`] times at the same position, the usual sign
+    /// that caching it would save work.
+    pub fn is_cache_candidate(&self) -> bool {
+        self.cache_hits == 0 && self.cache_misses == 0 && self.max_at_position >= CACHE_CANDIDATE_REPEATS
+    }
+}
+
+/// Per-rule statistics for a parse, returned by the `<rule>_with_stats`
+/// functions generated with the `stats` feature.
+///
+/// Its `Display` implementation prints a table of the rules, the ones
+/// evaluated most often first.
+#[derive(Clone, Debug, Default)]
+pub struct ParseStats {
+    rules: BTreeMap<&'static str, RuleStats>,
+}
+
+impl ParseStats {
+    /// The statistics for `rule`, if it was called.
+    pub fn get(&self, rule: &str) -> Option<&RuleStats> {
+        self.rules.get(rule)
+    }
+
+    /// The rules that were called, by name.
+    pub fn rules(&self) -> impl Iterator<Item = (&'static str, &RuleStats)> {
+        self.rules.iter().map(|(name, stats)| (*name, stats))
+    }
+
+    /// The rules that were called, the ones evaluated most often first.
+    pub fn by_cost(&self) -> Vec<(&'static str, &RuleStats)> {
+        let mut rules: Vec<_> = self.rules().collect();
+        rules.sort_by(|(a_name, a), (b_name, b)| b.evaluations().cmp(&a.evaluations()).then(a_name.cmp(b_name)));
+        rules
+    }
+
+    /// The rules for which [`RuleStats::is_cache_candidate`] holds, the ones
+    /// evaluated most often first.
+    pub fn cache_candidates(&self) -> Vec<&'static str> {
+        self.by_cost()
+            .into_iter()
+            .filter(|(_, stats)| stats.is_cache_candidate())
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[doc(hidden)]
+    pub fn rule_call(&mut self, rule: &'static str) {
+        self.rules.entry(rule).or_default().calls += 1;
+    }
+
+    #[doc(hidden)]
+    pub fn cache_hit(&mut self, rule: &'static str) {
+        self.rules.entry(rule).or_default().cache_hits += 1;
+    }
+
+    #[doc(hidden)]
+    pub fn cache_miss(&mut self, rule: &'static str) {
+        self.rules.entry(rule).or_default().cache_misses += 1;
+    }
+
+    /// Record an evaluation of `rule` at `pos`, that matched up to `end`.
+    #[doc(hidden)]
+    pub fn rule_result(&mut self, rule: &'static str, pos: usize, end: Option<usize>) {
+        let stats = self.rules.entry(rule).or_default();
+        match end {
+            Some(end) => {
+                stats.matches += 1;
+                stats.consumed += (end - pos) as u64;
+            }
+            None => stats.failures += 1,
+        }
+
+        let count = stats.positions.entry(pos).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            stats.repeats += 1;
+        }
+        stats.max_at_position = stats.max_at_position.max(*count);
+    }
+}
+
+impl Display for ParseStats {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            fmt,
+            "{:<24} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "rule", "calls", "matches", "failures", "consumed", "hits", "misses", "repeats"
+        )?;
+        for (name, stats) in self.by_cost() {
+            write!(
+                fmt,
+                "{:<24} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+                name,
+                stats.calls,
+                stats.matches,
+                stats.failures,
+                stats.consumed,
+                stats.cache_hits,
+                stats.cache_misses,
+                stats.repeats
+            )?;
+            if stats.is_cache_candidate() {
+                write!(fmt, "  <- up to {} times at one position, consider #[cache]", stats.max_at_position)?;
+            }
+            writeln!(fmt)?;
+        }
+        Ok(())
+    }
+}
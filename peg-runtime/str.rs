@@ -1,7 +1,12 @@
 //! Utilities for `str` input
 
-use super::{Parse, ParseElem, ParseLiteral, ParseSlice, RuleResult};
-use std::fmt::Display;
+use super::{Parse, ParseElem, ParseIndentation, ParseLiteral, ParseLiteralCaseInsensitive, ParseSlice, RuleResult};
+use crate::indent;
+use crate::error::ParseError;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Write};
 
 /// Line and column within a string
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -17,7 +22,7 @@ pub struct LineCol {
 }
 
 impl Display for LineCol {
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(fmt, "{}:{}", self.line, self.column)
     }
 }
@@ -32,16 +37,222 @@ impl Parse for str {
         pos >= self.len()
     }
 
+    fn input_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    // Linear in `pos`, which is fine for the single position of an error. Callers
+    // converting many positions use `position_reprs` or a `LineIndex`.
     fn position_repr(&self, pos: usize) -> LineCol {
         let before = &self[..pos];
-        let line = before.as_bytes().iter().filter(|&&c| c == b'\n').count() + 1;
-        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.as_bytes()[..line_start].iter().filter(|&&c| c == b'\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
         LineCol {
             line,
             column,
             offset: pos,
         }
     }
+
+    fn position_reprs(&self, positions: &[usize]) -> Vec<LineCol> {
+        if positions.len() > 1 {
+            let index = LineIndex::new(self);
+            positions.iter().map(|&p| index.line_col(p)).collect()
+        } else {
+            positions.iter().map(|&p| self.position_repr(p)).collect()
+        }
+    }
+}
+
+/// The unit in which a column is counted.
+///
+/// These correspond to the Language Server Protocol's position encodings
+/// `utf-8`, `utf-16` (the protocol's default), and `utf-32`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ColumnUnit {
+    /// Bytes of UTF-8
+    Utf8,
+
+    /// UTF-16 code units, where characters outside the Basic Multilingual Plane count as two
+    Utf16,
+
+    /// Unicode scalar values (`char`s), as in `LineCol::column`
+    Char,
+}
+
+impl ColumnUnit {
+    /// Length of `text` in this unit.
+    pub fn count(self, text: &str) -> usize {
+        match self {
+            ColumnUnit::Utf8 => text.len(),
+            ColumnUnit::Utf16 => text.chars().map(char::len_utf16).sum(),
+            ColumnUnit::Char => text.chars().count(),
+        }
+    }
+
+    fn len(self, c: char) -> usize {
+        match self {
+            ColumnUnit::Utf8 => c.len_utf8(),
+            ColumnUnit::Utf16 => c.len_utf16(),
+            ColumnUnit::Char => 1,
+        }
+    }
+
+    /// Byte index in `text` at `column` (zero-based) in this unit. If there is no exact
+    /// match, returns `Err` with the start of the character containing the column, or the
+    /// end of `text` if the column is past it.
+    fn find(self, text: &str, column: usize) -> Result<usize, usize> {
+        let mut units = 0;
+        for (i, c) in text.char_indices() {
+            if units == column {
+                return Ok(i);
+            }
+            units += self.len(c);
+            if units > column {
+                return Err(i);
+            }
+        }
+        if units == column {
+            Ok(text.len())
+        } else {
+            Err(text.len())
+        }
+    }
+}
+
+/// A zero-based line and column, as in the Language Server Protocol's `Position`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Position {
+    /// Line (0-indexed)
+    pub line: usize,
+
+    /// Column (0-indexed), in a `ColumnUnit` chosen by the caller
+    pub character: usize,
+}
+
+/// Index of the line starts in a string, for converting many byte offsets to
+/// `LineCol` without scanning the string each time.
+///
+/// `Parse::position_repr` for `str` counts the lines before the position,
+/// which is linear in the offset. Build a `LineIndex` once per input instead
+/// when converting positions for every node of a syntax tree.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'input> {
+    input: &'input str,
+
+    /// Byte offset of the start of each line
+    line_starts: Vec<usize>,
+}
+
+impl<'input> LineIndex<'input> {
+    /// Index the lines of `input`.
+    pub fn new(input: &'input str) -> LineIndex<'input> {
+        let mut line_starts = vec![0];
+        line_starts.extend(input.bytes().enumerate().filter(|&(_, b)| b == b'\n').map(|(i, _)| i + 1));
+        LineIndex { input, line_starts }
+    }
+
+    /// The indexed string.
+    pub fn input(&self) -> &'input str {
+        self.input
+    }
+
+    /// Number of lines. A trailing newline starts an empty last line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The line and column of byte `offset`, equal to `Parse::position_repr`.
+    ///
+    /// Finding the line is a binary search. The column is counted from the
+    /// start of the line, so this is `O(log lines + line length)`.
+    ///
+    /// Panics if `offset` is past the end of the input or not on a `char` boundary.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        self.line_col_in(offset, ColumnUnit::Char)
+    }
+
+    /// Like `line_col`, but with the column counted in `unit`.
+    pub fn line_col_in(&self, offset: usize, unit: ColumnUnit) -> LineCol {
+        let line = self.line_of(offset);
+        let column = unit.count(&self.input[self.line_starts[line]..offset]) + 1;
+        LineCol {
+            line: line + 1,
+            column,
+            offset,
+        }
+    }
+
+    /// The byte offset of a 1-indexed `line` and `column`, the inverse of `line_col`.
+    ///
+    /// The column may be one past the last character of the line, to refer to
+    /// its line break or the end of the input. Returns `None` for positions
+    /// outside the input.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        self.offset_in(line, column, ColumnUnit::Char)
+    }
+
+    /// Like `offset`, but with the column counted in `unit`. Also returns
+    /// `None` if the column falls inside a character.
+    pub fn offset_in(&self, line: usize, column: usize, unit: ColumnUnit) -> Option<usize> {
+        let text = self.line(line)?;
+        if column == 0 {
+            return None;
+        }
+        let start = self.line_starts[line - 1];
+        unit.find(text, column - 1).ok().map(|i| start + i)
+    }
+
+    /// The zero-based `Position` of byte `offset`, for the Language Server Protocol.
+    ///
+    /// Panics if `offset` is past the end of the input or not on a `char` boundary.
+    pub fn position(&self, offset: usize, unit: ColumnUnit) -> Position {
+        let lc = self.line_col_in(offset, unit);
+        Position {
+            line: lc.line - 1,
+            character: lc.column - 1,
+        }
+    }
+
+    /// The byte offset of an LSP `Position`, the inverse of `position`.
+    ///
+    /// As the protocol specifies, a `character` past the end of the line
+    /// refers to the end of the line. One inside a character refers to the
+    /// start of that character. Returns `None` if the line is not in the input.
+    pub fn position_offset(&self, position: Position, unit: ColumnUnit) -> Option<usize> {
+        let text = self.line(position.line + 1)?;
+        let start = self.line_starts[position.line];
+        match unit.find(text, position.character) {
+            Ok(i) | Err(i) => Some(start + i),
+        }
+    }
+
+    /// The text of a 1-indexed `line`, without its trailing `\n` or `\r\n`.
+    pub fn line(&self, line: usize) -> Option<&'input str> {
+        let range = self.line_range(line)?;
+        let text = &self.input[range];
+        Some(text.strip_suffix("\r\n").or_else(|| text.strip_suffix('\n')).unwrap_or(text))
+    }
+
+    /// Byte range of a 1-indexed `line`, including its line break.
+    pub fn line_range(&self, line: usize) -> Option<core::ops::Range<usize>> {
+        if line == 0 || line > self.line_starts.len() {
+            return None;
+        }
+        let start = self.line_starts[line - 1];
+        let end = self.line_starts.get(line).copied().unwrap_or(self.input.len());
+        Some(start..end)
+    }
+
+    /// Index into `line_starts` of the line containing `offset`.
+    fn line_of(&self, offset: usize) -> usize {
+        assert!(offset <= self.input.len(), "offset {} is past the end of the input", offset);
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next) => next - 1,
+        }
+    }
 }
 
 impl ParseElem for str {
@@ -66,9 +277,223 @@ impl ParseLiteral for str {
     }
 }
 
+impl ParseIndentation for str {
+    fn parse_indentation(&self, pos: usize) -> (usize, usize) {
+        let (len, width) = indent::measure(&self.as_bytes()[pos..]);
+        (pos + len, width)
+    }
+}
+
+/// Unicode simple case folding, approximated with the standard library's case
+/// mappings: characters that lowercase to the same single character after
+/// uppercasing are equal. Mappings that expand to several characters (like
+/// `'ß'` to `"SS"`) are not applied.
+fn fold_case(c: char) -> char {
+    if c.is_ascii() {
+        return c.to_ascii_lowercase();
+    }
+
+    fn single(mut iter: impl Iterator<Item = char>) -> Option<char> {
+        match (iter.next(), iter.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    }
+
+    let upper = single(c.to_uppercase()).unwrap_or(c);
+    single(upper.to_lowercase()).unwrap_or(upper)
+}
+
+impl ParseLiteralCaseInsensitive for str {
+    fn parse_string_literal_ci(&self, pos: usize, literal: &str) -> RuleResult<()> {
+        let mut input = self[pos..].char_indices();
+        for expected in literal.chars() {
+            match input.next() {
+                Some((_, c)) if c == expected || fold_case(c) == fold_case(expected) => {}
+                _ => return RuleResult::Failed,
+            }
+        }
+        let end = input.next().map_or(self.len(), |(i, _)| pos + i);
+        RuleResult::Matched(end, ())
+    }
+}
+
 impl<'input> ParseSlice<'input> for str {
     type Slice = &'input str;
     fn parse_slice(&'input self, p1: usize, p2: usize) -> &'input str {
         &self[p1..p2]
     }
 }
+
+/// Tab stop width used when rendering source lines
+const TAB_WIDTH: usize = 4;
+
+/// Number of terminal columns a character occupies: 0 for combining marks and other
+/// zero-width characters, 2 for East Asian wide and fullwidth characters and emoji.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F
+        | 0x0483..=0x0489
+        | 0x0591..=0x05BD
+        | 0x0610..=0x061A
+        | 0x064B..=0x065F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x2028..=0x202E
+        | 0x2060..=0x2064
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F
+        | 0xFEFF
+        | 0xE0100..=0xE01EF => 0,
+        0x1100..=0x115F
+        | 0x231A..=0x231B
+        | 0x2329..=0x232A
+        | 0x23E9..=0x23EC
+        | 0x23F0
+        | 0x23F3
+        | 0x25FD..=0x25FE
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x26A1
+        | 0x26AA..=0x26AB
+        | 0x26BD..=0x26BE
+        | 0x26C4..=0x26C5
+        | 0x26D4
+        | 0x26EA
+        | 0x26F2..=0x26F5
+        | 0x26FA
+        | 0x26FD
+        | 0x2705
+        | 0x270A..=0x270B
+        | 0x2728
+        | 0x274C
+        | 0x2753..=0x2755
+        | 0x2757
+        | 0x2795..=0x2797
+        | 0x27B0
+        | 0x27BF
+        | 0x2B1B..=0x2B1C
+        | 0x2B50
+        | 0x2B55
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xA960..=0xA97F
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE10..=0xFE19
+        | 0xFE30..=0xFE6F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x16FE0..=0x16FE4
+        | 0x17000..=0x18CFF
+        | 0x1B000..=0x1B2FF
+        | 0x1F004
+        | 0x1F0CF
+        | 0x1F18E
+        | 0x1F191..=0x1F19A
+        | 0x1F200..=0x1F251
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F7E0..=0x1F7EB
+        | 0x1F90C..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Expand tabs in `line` and return it along with the display column of each byte offset
+/// that starts a character, plus the end of the line.
+fn expand_line(line: &str) -> (String, Vec<(usize, usize)>) {
+    let mut out = String::with_capacity(line.len());
+    let mut columns = Vec::with_capacity(line.len() + 1);
+    let mut col = 0;
+    for (i, c) in line.char_indices() {
+        columns.push((i, col));
+        if c == '\t' {
+            let w = TAB_WIDTH - col % TAB_WIDTH;
+            for _ in 0..w {
+                out.push(' ');
+            }
+            col += w;
+        } else if c.is_control() {
+            // Replace other control characters so they can't disturb the terminal
+            out.push('\u{FFFD}');
+            col += 1;
+        } else {
+            out.push(c);
+            col += char_width(c);
+        }
+    }
+    columns.push((line.len(), col));
+    (out, columns)
+}
+
+impl ParseError<LineCol> {
+    /// Render the error with a snippet of `source` showing the line containing the
+    /// error and the lines around it, with a caret marking the failing position:
+    ///
+    /// ```text
+    /// error: expected one of "+", ";"
+    ///  --> input.txt:2:7
+    ///   |
+    /// 1 | a = 1;
+    /// 2 | b = 2 3;
+    ///   |       ^
+    /// 3 | c = 4;
+    ///   |
+    /// ```
+    ///
+    /// `source` must be the string that was parsed. Tabs are expanded to
+    /// 4-column tab stops, and the caret accounts for wide and zero-width characters.
+    pub fn render(&self, source: &str, filename: &str) -> String {
+        // An error built by hand, or from other input, may point inside a character
+        let mut offset = self.location.offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let lines: Vec<&str> = source.split('\n').collect();
+        let line_idx = source[..offset].matches('\n').count();
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+        let first = line_idx.saturating_sub(1);
+        let last = (line_idx + 1).min(lines.len() - 1);
+        let gutter = (last + 1).to_string().len();
+
+        let mut out = String::new();
+        let _ = writeln!(out, "error: expected {}", self.expected);
+        let _ = writeln!(out, "{:>w$}--> {}:{}", "", filename, self.location, w = gutter);
+        let _ = writeln!(out, "{:>w$} |", "", w = gutter);
+
+        for (i, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let (text, columns) = expand_line(line);
+            let _ = writeln!(out, "{:>w$} | {}", i + 1, text.trim_end(), w = gutter);
+
+            if i == line_idx {
+                let col_offset = (offset - line_start).min(line.len());
+                let idx = columns.iter().position(|&(o, _)| o >= col_offset).unwrap_or(columns.len() - 1);
+                let start = columns[idx].1;
+                let width = columns.get(idx + 1).map_or(1, |&(_, end)| (end - start).max(1));
+                let _ = writeln!(
+                    out,
+                    "{:>w$} | {:start$}{}",
+                    "",
+                    "",
+                    "^".repeat(width),
+                    w = gutter,
+                    start = start
+                );
+            }
+        }
+
+        let _ = writeln!(out, "{:>w$} |", "", w = gutter);
+        out
+    }
+}
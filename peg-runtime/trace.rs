@@ -0,0 +1,102 @@
+//! Receiving the steps of a parse, with the `trace` feature
+//!
+//! Parsers generated with the `trace` feature report each rule they try to a
+//! [`Tracer`]. Positions are offsets into the input, as used by [`Parse`](crate::Parse).
+
+#[cfg(feature = "std")]
+use crate::Parse;
+
+/// Receives the steps of a parse. Every method does nothing by default, so an
+/// implementation only needs the ones it's interested in.
+pub trait Tracer {
+    /// `rule` is about to be tried at `pos`.
+    fn rule_enter(&mut self, rule: &'static str, pos: usize) {
+        let _ = (rule, pos);
+    }
+
+    /// `rule` matched the input from `start` to `end`.
+    fn rule_match(&mut self, rule: &'static str, start: usize, end: usize) {
+        let _ = (rule, start, end);
+    }
+
+    /// `rule` failed to match at `pos`.
+    fn rule_fail(&mut self, rule: &'static str, pos: usize) {
+        let _ = (rule, pos);
+    }
+
+    /// The result of `rule` at `pos` was taken from its `#[cache]`, without
+    /// calling `rule_enter`. `end` is where the cached match ended, or `None`
+    /// for a cached failure.
+    fn cache_hit(&mut self, rule: &'static str, pos: usize, end: Option<usize>) {
+        let _ = (rule, pos, end);
+    }
+
+    /// A `precedence!{}` expression matched an operand ending at `pos`, and
+    /// is looking for operators of `level` or higher after it.
+    fn precedence_enter(&mut self, level: i32, pos: usize) {
+        let _ = (level, pos);
+    }
+
+    /// The operators of `level` or higher ended at `pos`.
+    fn precedence_leave(&mut self, level: i32, pos: usize) {
+        let _ = (level, pos);
+    }
+
+    /// The parse failed, and is about to run again from the start of the
+    /// input to find where it failed and what was expected there. The events
+    /// that follow are from that run.
+    fn error_reparse(&mut self) {}
+}
+
+/// The tracer used when none is passed in: prints each step to stdout.
+#[cfg(feature = "std")]
+pub struct PrintTracer<'input, I: ?Sized> {
+    input: &'input I,
+}
+
+#[cfg(feature = "std")]
+impl<'input, I: Parse + ?Sized> PrintTracer<'input, I> {
+    /// Print positions in `input` with its `PositionRepr`.
+    pub fn new(input: &'input I) -> Self {
+        PrintTracer { input }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'input, I: Parse + ?Sized> Tracer for PrintTracer<'input, I> {
+    fn rule_enter(&mut self, rule: &'static str, pos: usize) {
+        let loc = self.input.position_repr(pos);
+        println!("[PEG_TRACE] Attempting to match rule `{}` at {}", rule, loc);
+    }
+
+    fn rule_match(&mut self, rule: &'static str, start: usize, end: usize) {
+        let loc = self.input.position_repr(start);
+        let eloc = self.input.position_repr(end);
+        println!("[PEG_TRACE] Matched rule `{}` at {} to {}", rule, loc, eloc);
+    }
+
+    fn rule_fail(&mut self, rule: &'static str, pos: usize) {
+        let loc = self.input.position_repr(pos);
+        println!("[PEG_TRACE] Failed to match rule `{}` at {}", rule, loc);
+    }
+
+    fn cache_hit(&mut self, rule: &'static str, pos: usize, end: Option<usize>) {
+        let loc = self.input.position_repr(pos);
+        match end {
+            Some(_) => println!("[PEG_TRACE] Cached match of rule {} at {}", rule, loc),
+            None => println!("[PEG_TRACE] Cached fail of rule {} at {}", rule, loc),
+        }
+    }
+
+    fn precedence_enter(&mut self, level: i32, _pos: usize) {
+        println!("[PEG_TRACE] Entering level {}", level);
+    }
+
+    fn precedence_leave(&mut self, level: i32, _pos: usize) {
+        println!("[PEG_TRACE] Leaving level {}", level);
+    }
+
+    fn error_reparse(&mut self) {
+        println!("[PEG_TRACE] Parsing again to find the error");
+    }
+}
@@ -14,7 +14,11 @@
 //! * Precedence climbing for prefix/postfix/infix expressions
 //! * Helpful `rustc` error messages for errors in the grammar definition or the
 //!   Rust code embedded within it
-//! * Rule-level tracing to debug grammars
+//! * Rule-level tracing to debug grammars, statistics to tune them, and coverage
+//!   reports to test them
+//! * Random input generated from the grammar, for fuzzing
+//! * Usable in `#![no_std]` crates with `alloc`
+//! * Limits on nesting depth and parse time, for parsing untrusted input
 //!
 //! ## Overview
 //!
@@ -62,6 +66,13 @@
 //! ### Atoms
 //!
 //!   * `"keyword"` - _Literal:_ match a literal string.
+//!   * `"keyword"i` - _Case-insensitive literal:_ match a literal string, ignoring case. For `str`
+//!     input this uses Unicode simple case folding, and for `[u8]` ASCII case folding.
+//!   * `b"\x89PNG"` - _Byte string literal:_ match a sequence of bytes, which need not be UTF-8.
+//!     Supported for `[u8]` input.
+//!   * `literal!(expr)` - _Dynamic literal:_ match the string given by a Rust expression of type
+//!     `&str` (or anything that dereferences to `str`), which can use the labels bound earlier
+//!     in the sequence. [(details)](#dynamic-literals)
 //!   * `['0'..='9']`  - _Pattern:_ match a single element that matches a Rust `match`-style
 //!     pattern. [(details)](#pattern-expressions)
 //!   * `[^ '0'..='9']`  - _Inverted pattern:_ match a single element that does not match a Rust `match`-style
@@ -70,7 +81,8 @@
 //!     result. Arguments in the parentheses are Rust expressions.
 //!   * `_` or `__` or `___` - _Rule (underscore):_ As a special case, rule names
 //!     consisting of underscores can be defined and invoked without parentheses. These are
-//!     conventionally used to match whitespace between tokens.
+//!     conventionally used to match whitespace between tokens, and can be called between all
+//!     of them with [`#![skip(_)]`](#skipping-whitespace).
 //!   * `(e)` - _Parentheses:_ wrap an expression into a group to override
 //!     normal precedence. Returns the same value as the inner expression. (Use
 //!     an _Action_ block to set the return value for a sequence).
@@ -93,6 +105,8 @@
 //!     parse error with the `&str` `e`.
 //!   * `e1 / e2 / e3` - _Ordered choice:_ try to match `e1`. If the match succeeds, return its
 //!     result, otherwise try `e2`, and so on.
+//!   * `~` - _Cut:_ commit to the current alternative of the enclosing choice. If anything after
+//!     the cut fails, the whole choice fails without trying later alternatives. [(details)](#cut)
 //!
 //! ### Repetition
 //!   * `expression?` - _Optional:_ match zero or one repetitions of `expression`. Returns an
@@ -117,14 +131,24 @@
 //!     without consuming any characters.
 //!   * `!e` - _Negative lookahead:_ Match only if `e` does not match at this
 //!     position, without consuming any characters.
+//!   * `&{ rust }` - _Semantic predicate:_ Match only if the Rust block evaluates to `true`,
+//!     without consuming any characters. Like an action, the block can use the labels bound
+//!     earlier in the sequence, as in `n:number() &{ n < 256 }`. A failing predicate isn't
+//!     reported as expected in error messages; use a conditional action to name what was expected.
+//!   * `!{ rust }` - _Negative semantic predicate:_ Match only if the Rust block evaluates to
+//!     `false`, without consuming any characters.
 //!   * `position!()` - return a `usize` representing the current offset into
 //!     the input without consuming anything.
 //!   * `quiet!{ e }` - match the expression `e`, but don't report literals within it as "expected" in
 //!     error messages.
 //!   * `expected!("something")` - fail to match, and report the specified string as expected
 //!     at the current location.
+//!   * `recover!(e, sync)` - match `e`, or if it fails, record its error, skip input until `sync`
+//!     matches, and return the result of `sync`. [(details)](#error-recovery)
 //!   * `precedence!{ ... }` - Parse infix, prefix, or postfix expressions by precedence climbing.
 //!     [(details)](#precedence-climbing)
+//!   * `indent!()`, `same_indent!()`, `dedent!()` - Match the indentation at the start of a
+//!     line, for languages where it delimits blocks. [(details)](#indentation)
 //!
 //! ## Expression details
 //!
@@ -188,6 +212,146 @@
 //! rule beginning and ending with `@` is an infix expression. Prefix and postfix rules have one
 //! `@` at the beginning or end, and atoms do not include `@`.
 //!
+//! ### Cut
+//!
+//! A `~` in an alternative of a choice matches without consuming input, and commits the parser to
+//! that alternative: once past the cut, a failure fails the whole choice rather than falling back to
+//! the next alternative. This gives errors at the point where the input actually went wrong instead
+//! of at the start of the statement, and avoids retrying alternatives that can't match.
+//!
+//! ```rust,no_run
+//! # peg::parser!{grammar doc() for str {
+//! # rule ident() = ['a'..='z']+
+//! # rule expr() = ident()
+//! rule statement()
+//!   = "let" ~ " " ident() " = " expr() ";"
+//!   / "print" ~ " " expr() ";"
+//!   / expr() ";"
+//! # }}
+//! # fn main() {}
+//! ```
+//!
+//! A cut only affects the innermost choice containing it, and only its alternatives other than the
+//! last, so it has no effect in the last alternative, in a rule without a choice, or in a rule
+//! argument or `precedence!` operator. Note that `"let" ~` also commits on input like `letter`, so a
+//! keyword is usually followed by a check such as `!['a'..='z']` before the cut.
+//!
+//! Once a cut is reached outside any `?`, `*`, lookahead or choice alternative that could still
+//! backtrack, no earlier position will be parsed again, so the entries of `#[cache]` rules before
+//! that position are dropped. This keeps the memory used by the cache bounded when parsing a long
+//! list of statements. Caches of `#![incremental]` grammars are kept in full.
+//!
+//! ### Dynamic literals
+//!
+//! `literal!(expr)` matches a string computed at parse time, such as text captured earlier in the
+//! input. This handles closing delimiters that must repeat their opening one:
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! rule name() -> &'input str = $(['a'..='z']+)
+//!
+//! pub rule element() = "<" tag:name() ">" element()* "</" literal!(tag) ">"
+//!
+//! pub rule raw_string() -> &'input str
+//!   = "r" hashes:$("#"*) "\"" body:$((!("\"" literal!(hashes)) [_])*) "\"" literal!(hashes) { body }
+//! # }}
+//! # fn main() {
+//! assert!(doc::element("<a><b></b></a>").is_ok());
+//! assert_eq!(doc::raw_string(r###"r#"say "hi""#"###), Ok("say \"hi\""));
+//!
+//! let err = doc::element("<a></b>").unwrap_err();
+//! assert_eq!(err.expected.to_string(), "\"a\"");
+//! # }
+//! ```
+//!
+//! When it fails, the quoted string is reported as expected. As the string isn't known when the
+//! grammar is compiled, it isn't an [`error::Expected`] item: it is listed by
+//! `ExpectedSet::dynamic_literals()` rather than `tokens()`, isn't counted by `len()`, and is
+//! included in the error message. Since the string may be empty, `literal!()` is treated as
+//! possibly matching without consuming input when checking for infinite loops and left recursion.
+//!
+//! ### Indentation
+//!
+//! For layout-sensitive languages, the parser keeps a stack of indentation widths, starting with
+//! a single level at width 0. Called at the start of a line:
+//!
+//!   * `indent!()` matches the spaces and tabs there if they are wider than the current level, and
+//!     pushes their width as a new level.
+//!   * `same_indent!()` matches the spaces and tabs there if they are as wide as the current level.
+//!   * `dedent!()` pops the current level, without consuming any input.
+//!
+//! A space is one column wide, and a tab advances to the next multiple of
+//! [`indent::TAB_WIDTH`] (8) columns, as in Python. Line ends and blank lines are left to the
+//! grammar:
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! rule newline() = ([' ' | '\t']* "\n")+
+//!
+//! pub rule statements() = statement() ++ (newline() same_indent!())
+//!
+//! rule statement()
+//!   = "if " ['a'..='z']+ ":" block()
+//!   / ['a'..='z']+
+//!
+//! #[cache]
+//! rule block() = newline() indent!() statements() dedent!()
+//! # }}
+//! # fn main() {
+//! assert!(doc::statements("if a:\n  b\n  if c:\n    d\n\n  e\nf").is_ok());
+//! assert!(doc::statements("if a:\n  b\n   e").is_err());
+//! # }
+//! ```
+//!
+//! The stack is restored when the parser backtracks, and a lookahead leaves it unchanged. The
+//! results of `#[cache]` rules are kept separately for each indentation stack they were called
+//! with, along with the stack they left.
+//!
+//! ### Skipping whitespace
+//!
+//! Rather than writing `_` between every pair of tokens, `#![skip(_)]` at the start of the
+//! grammar (after `#![generator]` if present) calls the rule `_` between the elements of each
+//! sequence, between the items of a repetition and around its separator, and between the
+//! operands and tokens of `precedence!{}` operators. A rule marked `#[lexical]` matches its
+//! elements next to each other, as does the skip rule itself, so lexical rules are used for
+//! tokens like identifiers and numbers:
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! #![skip(_)]
+//!
+//! rule _ = [' ' | '\n']*
+//!
+//! #[lexical]
+//! rule number() -> i64 = n:$(['0'..='9']+) { n.parse().unwrap() }
+//!
+//! pub rule list() -> Vec<i64> = _ "[" l:(number() ** ",") "]" _ { l }
+//!
+//! pub rule expr() -> i64 = precedence!{
+//!   x:(@) "+" y:@ { x + y }
+//!   --
+//!   x:(@) "*" y:@ { x * y }
+//!   --
+//!   n:number() { n }
+//! }
+//! # }}
+//! # fn main() {
+//! assert_eq!(doc::list(" [1, 2 ,3] "), Ok(vec![1, 2, 3]));
+//! assert_eq!(doc::expr("1 + 2 * 3"), Ok(7));
+//! assert!(doc::list("[1 2]").is_err());
+//! # }
+//! ```
+//!
+//! As in other PEG tools that distinguish syntactic and lexical rules, the rules used by a
+//! lexical rule must not skip either, or a token could have whitespace in the middle: a rule
+//! reached from a lexical rule that would call the skip rule is reported, to be marked
+//! `#[lexical]` as well. Whitespace before the first element and after the last is left to the
+//! rule's callers, so a `pub rule` that may be surrounded by whitespace matches the skip rule
+//! itself. The skip rule is called within `quiet!{}`, so it isn't listed in errors, and not before
+//! a `position!()`, which is therefore the end of the element before it, or the start of its
+//! sequence: group the position with an element, as in `(p:position!() e:expr())`, to get where
+//! that element starts.
+//!
 //! ### End-of-file handling
 //!
 //! Normally, parsers report an error if the top-level rule matches without consuming all the input.
@@ -207,6 +371,9 @@
 //!   * [`ParseElem`] implements the `[_]` pattern operator, with a method returning the next item of
 //!     the input to match.
 //!   * [`ParseLiteral`] implements matching against a `"string"` literal.
+//!   * [`ParseLiteralCaseInsensitive`] implements matching against a case-insensitive
+//!     `"string"i` literal.
+//!   * [`ParseByteLiteral`] implements matching against a `b"bytes"` literal.
 //!   * [`ParseSlice`] implements the `$()` operator, returning a slice from a span of indexes.
 //!
 //! As a more complex example, the body of the `peg::parser!{}` macro itself is
@@ -240,6 +407,137 @@
 //! # fn main() {}
 //! ```
 //!
+//! Each item of the [`error::ExpectedSet`] is tagged with its kind, as an [`error::Expected`]:
+//! a `Literal`, a `Pattern`, a `Named` string from `expected!()`, or `Eof`. The set iterates in a
+//! deterministic order, and its `literals()`, `patterns()`, `names()` and `expects_eof()` methods
+//! allow tools to format the message their own way:
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! #   rule identifier() = quiet!{['a'..='z']+} / expected!("identifier")
+//! #   pub rule args() = "(" (identifier() ** ",") ")"
+//! # }}
+//! # fn main() {
+//! use peg::error::Expected;
+//!
+//! let err = doc::args("(").unwrap_err();
+//! let expected: Vec<Expected> = err.expected.iter().collect();
+//! assert_eq!(expected, [Expected::Literal("\")\""), Expected::Named("identifier")]);
+//! assert_eq!(err.expected.names().collect::<Vec<_>>(), ["identifier"]);
+//! # }
+//! ```
+//!
+//! `ParseError::context()` returns the names of the rules, outermost first, that were being
+//! parsed when all of the expected items were recorded, to explain where in a nested language the
+//! error occurred. A rule marked `#[context("...")]` is listed by that label instead of its
+//! name. The stack for each individual item is available from `ExpectedSet::context_of()`, and
+//! for all of them from `ExpectedSet::contexts()`. The stacks aren't compared when checking
+//! errors for equality. They are only recorded while reparsing to report an error, so they do
+//! not slow down a successful parse.
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! #   rule statement() = ['a'..='z'] ";"
+//! #[context("function body")]
+//! rule body() = "{" statement()* "}"
+//! #   pub rule function() = "fn" body()
+//! # }}
+//! # fn main() {
+//! let err = doc::function("fn{a}").unwrap_err();
+//! assert_eq!(err.context().join(" > "), "function > function body > statement");
+//! # }
+//! ```
+//!
+//! For `str` input, `ParseError::render(source, filename)` formats the error along with the
+//! lines of `source` around it and a caret under the failing position, accounting for tabs
+//! and wide characters:
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! #   pub rule list() = "[" ($(['0'..='9']+) ** ",") "]"
+//! # }}
+//! # fn main() {
+//! let source = "[1,2\n3]";
+//! let err = doc::list(source).unwrap_err();
+//! assert_eq!(err.render(source, "input.txt"), r#"error: expected one of ",", "]", ['0'..='9']
+//!  --> input.txt:1:5
+//!   |
+//! 1 | [1,2
+//!   |     ^
+//! 2 | 3]
+//!   |
+//! "#);
+//! # }
+//! ```
+//!
+//! The `LineCol` of a `str` position is found by counting the lines before it, so each call to
+//! `Parse::position_repr` takes time linear in the offset. The generated parsers only convert the
+//! positions of the errors they return. To convert many positions yourself, such as the
+//! `position!()` spans of every node in a syntax tree, don't call `position_repr` for each one:
+//! build a `peg::str::LineIndex` once for the input and use its `line_col(offset)`, which finds
+//! the line with a binary search. Its `offset(line, column)` converts back.
+//!
+//! `LineCol::column` counts `char`s. `LineIndex::line_col_in` and `offset_in` take a
+//! `peg::str::ColumnUnit` to count columns in UTF-8 bytes or UTF-16 code units instead. For the
+//! Language Server Protocol, `LineIndex::position` converts an offset to a zero-based
+//! `peg::str::Position`, and `position_offset` converts a `Position` from the client back to an
+//! offset:
+//!
+//! ```rust
+//! use peg::str::{ColumnUnit, LineIndex, Position};
+//! # peg::parser!{grammar doc() for str {
+//! #   pub rule words() = (['a'..='z' | '😀']+) ** " "
+//! # }}
+//! # fn main() {
+//! let source = "😀 ab !";
+//! let index = LineIndex::new(source);
+//! let err = doc::words(source).unwrap_err();
+//! let start = index.position(err.location.offset, ColumnUnit::Utf16);
+//! assert_eq!(start, Position { line: 0, character: 6 });
+//! assert_eq!(index.position_offset(start, ColumnUnit::Utf16), Some(err.location.offset));
+//! # }
+//! ```
+//!
+//! ### Error recovery
+//!
+//! Normally a parse stops at the first error. `recover!(e, sync)` allows it to continue past
+//! an error so that later errors are also reported. If `e` fails, the error it would have
+//! produced is recorded, and the input is skipped until `sync` matches. The value of `sync` is
+//! returned in place of the value of `e`, typically a node representing the error:
+//!
+//! ```rust
+//! # #[derive(Debug, PartialEq)] pub enum Stmt { Assign(u32), Error }
+//! peg::parser!{grammar doc() for str {
+//!     rule stmt() -> Stmt
+//!         = recover!("x=" n:$(['0'..='9']+) ";" { Stmt::Assign(n.parse().unwrap()) }, ";" { Stmt::Error })
+//!
+//!     #[recover]
+//!     pub rule stmts() -> Vec<Stmt> = stmt()*
+//! }}
+//! # fn main() {
+//! let (stmts, errors) = doc::stmts("x=1;x=?;x=3;");
+//! assert_eq!(stmts, Some(vec![Stmt::Assign(1), Stmt::Error, Stmt::Assign(3)]));
+//! assert_eq!(errors[0].location.offset, 6);
+//! # }
+//! ```
+//!
+//! A `pub rule` marked `#[recover]` returns `(Option<T>, Vec<ParseError>)`: the value, if the
+//! parse completed, and every error encountered, in input order. Other `pub rule`s return the
+//! first recovered error as their `Err` result. A parse stopped by one of its [limits](#limits)
+//! returns only the error for the limit, without the errors recovered before it stopped.
+//!
+//! If `sync` never matches before the end of input, `recover!()` fails and the error of `e` is
+//! reported as if `recover!()` was not used. Errors recovered in an alternative that is later
+//! backtracked over are discarded. Errors are not recorded within `quiet!{}` and lookahead.
+//! Because the recorded errors are not part of a rule's result, rules that use `recover!()`,
+//! directly or through the rules they call, cannot be marked `#[cache]`.
+//!
+//! Finding the error of `e` takes two more runs of it after it fails, with fresh caches so
+//! that memoized failures don't hide the furthest position, like the whole parse is re-run to
+//! report an error. A failing `recover!()` inside another one is run again in each of those
+//! runs, so the cost grows threefold with each level of nesting at which the expressions fail
+//! together. Keep nested `recover!()`s to a few levels, or make sure an inner one synchronizes.
+//!
 //! ## Imports
 //!
 //! ```rust,no_run
@@ -275,17 +573,16 @@
 //!
 //! ## Caching and left recursion
 //!
-//! A `rule` without parameters can be prefixed with `#[cache]` if it is likely
-//! to be checked repeatedly in the same position. This memoizes the rule result
-//! as a function of input position, in the style of a [packrat
-//! parser][wp-peg-packrat].
+//! A `rule` can be prefixed with `#[cache]` if it is likely to be checked
+//! repeatedly in the same position. This memoizes the rule result as a function
+//! of input position, in the style of a [packrat parser][wp-peg-packrat].
 //!
 //! [wp-peg-packrat]: https://en.wikipedia.org/wiki/Parsing_expression_grammar#Implementing_parsers_from_parsing_expression_grammars
 //!
 //! However, idiomatic code avoids structures that parse the same input
 //! repeatedly, so the use of `#[cache]` is often not a performance win. Simple
-//! rules may also be faster to re-match than the additional cost of the hash
-//! table lookup and insert.
+//! rules may also be faster to re-match than the additional cost of the table
+//! lookup and insert.
 //!
 //! For example, a complex rule called `expr` might benefit from caching if used
 //! like `expr() "x" / expr() "y" / expr() "z"`, but this could be rewritten to
@@ -294,14 +591,207 @@
 //! `#[cache_left_rec]` extends the `#[cache]` mechanism with the ability to resolve 
 //! left-recursive rules, which are otherwise an error. 
 //!
+//! Rules can also be left recursive through each other. Each such cycle needs one rule
+//! marked `#[cache_left_rec]`, and the first marked rule of the cycle to be called at a
+//! position grows the result there, re-evaluating the other rules of the cycle on each
+//! iteration rather than using their caches, as in "Packrat Parsers Can Support Left
+//! Recursion" (Warth et al.).
+//! Rules in these cycles can't take parameters.
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! #[cache_left_rec]
+//! pub rule primary() -> String
+//!     = p:primary() "." id:$(['a'..='z']+) { format!("{}.{}", p, id) }
+//!     / call()
+//!     / "this" { "this".to_owned() }
+//!
+//! rule call() -> String = p:primary() "()" { format!("{}()", p) }
+//! # }}
+//! # fn main() {
+//! #     assert_eq!(doc::primary("this.a().b"), Ok("this.a().b".to_owned()));
+//! # }
+//! ```
+//!
+//! Each cache hit returns a clone of the stored value, so the rule's return type must be
+//! `Clone`, and cloning a large syntax tree can cost more than parsing it again. With
+//! `#[cache(shared)]` (or `#[cache_left_rec(shared)]`), the value is moved into an `Rc` when
+//! it is first parsed, and the rule returns `Rc<T>` instead of `T`, so that a cache hit only
+//! increments a reference count. `T` does not need to implement `Clone`.
+//!
+//! ```rust
+//! # use std::rc::Rc;
+//! # pub enum Expr { Num(u32), Neg(Rc<Expr>) }
+//! # peg::parser!{grammar doc() for str {
+//! #[cache(shared)]
+//! rule expr() -> Expr
+//!     = "-" e:expr() { Expr::Neg(e) }
+//!     / n:$(['0'..='9']+) { Expr::Num(n.parse().unwrap()) }
+//!
+//! pub rule stmt() -> Rc<Expr> = e:expr() ";" { e } / e:expr() "!" { e }
+//! # }}
+//! # fn main() {}
+//! ```
+//!
 //! The `precedence!{}` syntax is another way to handle nested operators and avoid
 //! repeatedly matching an expression rule.
 //!
+//! ### Caching rules with parameters
+//!
+//! The cache of a rule with parameters is keyed by the argument values as well as the
+//! position, so the types of Rust parameters must implement `Eq` and `Clone`, and can't be
+//! references without a named lifetime such as `'input`, floating point numbers, or types
+//! mentioning the rule's own generic parameters. Rule closures can't be compared, so a
+//! rule taking `rule<T>` parameters is keyed by the place it was called from instead. A
+//! closure passed to a cached rule must then match the same way every time it's called
+//! from there, so it can't use the parameters or labels of the rule it's written in.
+//!
+//! ```rust
+//! # peg::parser!{grammar doc() for str {
+//! #[cache]
+//! rule keyword(kw: &'static str) = s:$(['a'..='z']+) {? if s == kw { Ok(()) } else { Err(kw) } }
+//!
+//! #[cache]
+//! rule list(item: rule<u32>) -> Vec<u32> = "[" v:(item() ** ",") "]" { v }
+//!
+//! rule num() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+//! rule nums() -> Vec<u32> = list(<num()>)
+//!
+//! pub rule stmt() -> Vec<u32>
+//!     = keyword("let") " " v:nums() ";" { v }
+//!     / keyword("let") " " v:nums() { v }
+//! # }}
+//! # fn main() {}
+//! ```
+//!
+//! Both alternatives reach `list` through the same call in `nums`, so the second one
+//! reuses its result, as it does for `keyword("let")`.
+//!
+//! ### Cache storage
+//!
+//! The storage for the results of a cached rule can be chosen with an argument to the
+//! attribute, as in `#[cache(dense)]`, `#[cache_left_rec(hash)]` or `#[cache(shared, chunked)]`:
+//!
+//!   * `dense` stores a slot for every input position up to the furthest one cached. It is
+//!     the fastest, but its memory use grows with the input length rather than with the
+//!     number of positions where the rule was tried.
+//!   * `chunked` allocates slots in small blocks, only for the parts of the input where
+//!     the rule was tried.
+//!   * `hash` uses a `HashMap`, which is slower but compact if the rule is only tried at a
+//!     few positions.
+//!
+//! `#![cache(dense)]` at the start of the grammar (after `#![incremental]`, if present) sets
+//! the storage for all cached rules without one of their own. By default, `dense` is used
+//! for inputs of up to 64K positions, and `chunked` for longer inputs and for incremental
+//! sessions. Custom input types report their length for this choice by implementing
+//! [`Parse::input_len`].
+//!
+//! ### Incremental reparsing
+//!
+//! A grammar beginning with `#![incremental]` also generates a `Session` type, which keeps the
+//! results of `#[cache]` and `#[cache_left_rec]` rules from one parse to the next. It has a
+//! method for each `pub rule`. After changing the input, call `edit()` with the range of the
+//! old input that was replaced and the length of its replacement, then parse the new input:
+//!
+//! ```rust
+//! peg::parser!{grammar list() for str {
+//!     #![incremental]
+//!
+//!     #[cache]
+//!     rule item() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+//!
+//!     pub rule items() -> Vec<u32> = item() ** ";"
+//! }}
+//!
+//! # fn main() {
+//! let mut session = list::Session::new();
+//! assert_eq!(session.items("1;22;333"), Ok(vec![1, 22, 333]));
+//!
+//! // Replace "22" with "4444"
+//! session.edit(2..4, 4);
+//! assert_eq!(session.items("1;4444;333"), Ok(vec![1, 4444, 333]));
+//! # }
+//! ```
+//!
+//! Each cached result records how far into the input the rule looked while matching. On an
+//! edit, results that only looked at input before the edit are kept, results starting after it
+//! are moved by the change in length, and the rest are discarded. Only the rules whose results
+//! were discarded, and the uncached rules that call them, are run again. Positions stored in
+//! the cached values themselves (for example from `position!()`) are not adjusted.
+//!
+//! Because the cached values outlive the input, cached rules in an incremental grammar can't
+//! return types borrowing from it (`'input`), or take parameters that do. A `##method()` expression is assumed to examine
+//! all of the remaining input. When the input doesn't parse, the error is found by parsing it
+//! again from scratch, without the session's caches.
+//!
+//! ## Limits
+//!
+//! Each rule called while another is still matching uses more of the stack, so deeply nested
+//! input can overflow it. `#![max_depth(n)]` at the start of the grammar (after any other
+//! grammar attributes) limits how many rules and `precedence!{}` levels can be nested. A parse
+//! that reaches the limit stops and fails with [`error::ErrorKind::NestingTooDeep`] at the
+//! position where it stopped, instead of the usual [`error::ErrorKind::Syntax`].
+//!
+//! Each `pub rule` also generates a `<rule>_with_limits` function taking a [`Limits`], which
+//! overrides the grammar's limit for one parse. `Session` has a `set_limits()` method for the
+//! same purpose.
+//!
+//! ```rust
+//! peg::parser!{grammar parens() for str {
+//!     #![max_depth(100)]
+//!     pub rule nested() = "(" nested() ")" / ""
+//! }}
+//!
+//! # fn main() {
+//! use peg::{error::ErrorKind, Limits};
+//!
+//! let input = "(".repeat(200) + &")".repeat(200);
+//! assert_eq!(parens::nested(&input).unwrap_err().kind, ErrorKind::NestingTooDeep);
+//! assert!(parens::nested_with_limits(&input, &Limits::default().with_max_depth(1000)).is_ok());
+//! # }
+//! ```
+//!
+//! A grammar that backtracks a lot can take a very long time on some inputs. [`Limits`] can also
+//! set `max_steps`, the number of rules the parse may run, and `cancel`, a flag that another
+//! thread can set to give up on the parse (on targets with atomics). Either one stops the parse
+//! with [`error::ErrorKind::Aborted`]. The flag is checked every [`CANCEL_CHECK_INTERVAL`] rules.
+//! Without either of them, the cost of checking is a counter that never runs out. As more limits
+//! may be added, `Limits` is built with its `with_*` methods rather than a struct literal.
+//!
+//! ```rust
+//! # peg::parser!{grammar parens() for str {
+//! #     pub rule nested() = "(" nested() ")" / ""
+//! # }}
+//! # fn main() {
+//! use peg::{error::ErrorKind, Limits};
+//! use std::sync::{atomic::AtomicBool, Arc};
+//!
+//! let cancel = Arc::new(AtomicBool::new(false));
+//! let limits = Limits::default().with_max_steps(1_000_000).with_cancel(cancel.clone());
+//! assert!(parens::nested_with_limits("(())", &limits).is_ok());
+//! # }
+//! ```
+//!
+//! ## `no_std`
+//!
+//! The `std` feature is enabled by default. With `default-features = false`,
+//! `peg` and the code generated by `peg::parser!{}` depend only on `core` and
+//! `alloc`, so grammars can be used in `#![no_std]` crates that provide a global
+//! allocator. Without `std`, [`error::ParseError`] does not implement
+//! `std::error::Error`, and `#[cache]` tables use a `BTreeMap` instead of a
+//! `HashMap`.
+//!
+//! ```toml
+//! [dependencies]
+//! peg = { version = "0.7", default-features = false }
+//! ```
+//!
 //! ## Tracing
 //!
 //! If you pass the `peg/trace` feature to Cargo when building your project, a
 //! trace of the rules attempted and matched will be printed to stdout when
-//! parsing. For example,
+//! parsing. Tracing uses `println!`, so it enables the `std` feature. For
+//! example,
 //! ```sh
 //! $ cargo run --features peg/trace
 //! ...
@@ -311,6 +801,104 @@
 //! [PEG_TRACE] Failed to match rule letter at 8:12
 //! ...
 //! ```
+//!
+//! The trace can also be sent elsewhere, by implementing [`trace::Tracer`] and passing it to the
+//! `<rule>_with_tracer` function generated for each `pub rule` when the feature is enabled, or to
+//! the `Session` method of the same name in an incremental grammar. Its methods are called as
+//! rules are tried, matched or failed, when results come from a `#[cache]`, and as
+//! `precedence!{}` expressions move between levels. When the input doesn't parse, it is parsed
+//! again to find the error, and `error_reparse()` is called before each such run, so its events
+//! can be told apart from the first one's. Positions are offsets into the input. A tracer that only cares about some rules can filter them by name:
+//!
+//! ```rust
+//! struct Calls(Vec<usize>);
+//!
+//! impl peg::trace::Tracer for Calls {
+//!     fn rule_enter(&mut self, rule: &'static str, pos: usize) {
+//!         if rule == "ident" {
+//!             self.0.push(pos);
+//!         }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ## Statistics
+//!
+//! To find out where a grammar spends its time, enable the `peg/stats` feature. Each `pub rule`
+//! then also generates a `<rule>_with_stats` function, which returns a [`stats::ParseStats`]
+//! along with the result. For each rule, it counts the calls, the matches and failures, the
+//! length of input matched, and the hits and misses of its `#[cache]`. Printing it shows a table
+//! of the rules, the ones evaluated most often first.
+//!
+//! A rule evaluated several times at the same position is redoing work that a `#[cache]` would
+//! save, and is marked in the table. [`stats::ParseStats::cache_candidates`] lists these rules.
+//! When the input doesn't parse, the statistics are those of the first attempt, not of the
+//! reparse that locates the error.
+//!
+//! ```rust,ignore
+//! let (result, stats) = my_grammar::file_with_stats(&input);
+//! println!("{}", stats);
+//! ```
+//!
+//! ## Coverage
+//!
+//! The `peg/coverage` feature counts how often each rule, and each alternative of each `/`
+//! choice, matched. It is meant for checking how much of a grammar a test suite exercises.
+//! The counts are shared by all parses with the grammar, so the tests don't need to change:
+//! afterwards, the `coverage()` function generated in the grammar's module returns a
+//! [`coverage::Coverage`] with the counts so far. It lists the points that never matched, each
+//! with an ID such as `value/0/2` (the third alternative of the first choice in `value`) and
+//! its location in the grammar source. Coverage from several grammars can be combined with
+//! [`coverage::Coverage::merge`]. This feature needs Rust 1.88 or later, to find the locations.
+//! The counters are atomic, so on targets without atomic pointer-sized operations nothing is
+//! counted, and neither `peg::coverage` nor the `coverage()` functions exist.
+//!
+//! ```rust,ignore
+//! run_tests();
+//! let mut coverage = my_grammar::coverage();
+//! coverage.merge(&other_grammar::coverage());
+//! println!("{}", coverage);
+//! ```
+//!
+//! ## Generating input
+//!
+//! With `#![generator]` at the start of a `for str` grammar, after `#![incremental]` and
+//! `#![cache]` if present, the grammar's module gets a `generator` module with a function for
+//! each rule that returns random input for it, for round-trip and crash testing of what
+//! consumes the parse. Each function takes a [`generate::Rng`], seeded so that any input can be
+//! reproduced, and a depth: how many levels of rules may be nested beyond the fewest needed to
+//! finish. Once the depth is used up, only the shortest alternatives are taken and repetitions
+//! are kept to their minimum, so the input stays finite.
+//!
+//! ```rust
+//! peg::parser!{grammar list() for str {
+//!     #![generator]
+//!     pub rule list() = "[" (number() ** ",") "]"
+//!     rule number() = ['1'..='9'] ['0'..='9']*
+//! }}
+//!
+//! # fn main() {
+//! let mut rng = peg::generate::Rng::new(42);
+//! for _ in 0..100 {
+//!     let input = list::generator::list(&mut rng, 5).unwrap();
+//!     assert!(list::list(&input).is_ok());
+//! }
+//! # }
+//! ```
+//!
+//! Literals, `[...]` patterns, choices, repetitions with literal bounds and `precedence!{}` are
+//! generated. Patterns are matched against the characters they spell out, and against printable
+//! ASCII when they can also accept others. Predicates, `{? }` blocks, `expected!()`, `literal!()`,
+//! indentation, rules with arguments and repetition counts computed in Rust can't be, so
+//! alternatives and repetitions containing them are avoided. A rule that can't be finished
+//! without them returns [`generate::Unsatisfiable`], and is listed in `generator::UNSATISFIABLE`.
+//! With `#![skip]`, input for the skip rule is generated wherever the parser calls it.
+//! The generated input follows the grammar, but because choices are ordered and repetitions are
+//! greedy, the parser may still read it differently than it was generated, or reject it: an
+//! identifier generated from `['a'..='z']+` can be a keyword matched by an earlier alternative.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate peg_macros;
 extern crate peg_runtime as runtime;
@@ -0,0 +1,11 @@
+extern crate peg;
+
+#[derive(Clone)]
+pub struct NoEq;
+
+peg::parser!(grammar foo() for str {
+    #[cache]
+    rule noeq(x: NoEq) = "foo" //~ ERROR
+});
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate peg;
+
+peg::parser!(grammar foo() for str {
+    #[cache(shared)]
+    rule foo() = "foo" //~ ERROR
+
+    #[cache(shared, hash)]
+    rule bar() -> u32 = "bar" { 0 }
+});
+
+fn main() {}
@@ -2,10 +2,27 @@ extern crate peg;
 
 peg::parser!(grammar foo() for str { 
     #[cache]
-    rule foo(x: u32) = "foo" //~ ERROR
+    rule float(x: f64) = "foo" //~ ERROR
+
+    #[cache]
+    rule elided(x: &str) = "foo" //~ ERROR
+
+    #[cache]
+    rule mutable(x: &mut u32) = "foo" //~ ERROR
 
     #[cache]
     rule ltarg<'a>() -> &'a str = { "" } //~ ERROR
+
+    #[cache]
+    rule generic<T>(x: Option<T>) = "foo" //~ ERROR
+
+    #[cache]
+    rule list(x: rule<()>) = x() ** ","
+
+    rule repeat(n: usize) = list(<"a"*<{n}> {}>) //~ ERROR
+    rule label() = c:$("x") list(<[ch if ch.to_string() == c]>) //~ ERROR
+    rule forward(x: rule<()>) = list(<x()>) //~ ERROR
+    rule inner() = list(<c:$("x") { let _ = c; }>)
 });
 
-fn main() {}
\ No newline at end of file
+fn main() {}
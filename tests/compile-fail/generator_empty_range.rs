@@ -0,0 +1,8 @@
+extern crate peg;
+
+peg::parser!(grammar g() for str {
+    #![generator]
+    pub rule nul() = ['\0'..'\0'] //~ ERROR
+});
+
+fn main() {}
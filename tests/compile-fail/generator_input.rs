@@ -0,0 +1,8 @@
+extern crate peg;
+
+peg::parser!(grammar foo() for [u8] { //~ ERROR
+    #![generator]
+    pub rule foo() = [b'a'..=b'z']+
+});
+
+fn main() {}
@@ -0,0 +1,15 @@
+extern crate peg;
+
+peg::parser!(grammar foo() for str {
+    #![incremental]
+
+    #[cache]
+    rule ident() -> &'input str = $(['a'..='z']+)
+
+    #[cache]
+    rule number() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    pub rule main() = ident() number()
+});
+
+fn main() {}
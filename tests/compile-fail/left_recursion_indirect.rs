@@ -0,0 +1,17 @@
+extern crate peg;
+
+peg::parser!(grammar foo() for str {
+    #[cache_left_rec]
+    rule a() = b() "x" / "y"
+
+    rule b() = a() "z" / c() //~ ERROR left recursive rules create an infinite loop: c -> b -> c
+
+    rule c() = b() "w" //~ ERROR left recursive rules create an infinite loop: b -> c -> b
+
+    #[cache_left_rec]
+    rule d() = e(1) "x" / "y"
+
+    rule e(n: u32) = d() "z" //~ ERROR rules that are left recursive through other rules cannot take parameters
+});
+
+fn main() {}
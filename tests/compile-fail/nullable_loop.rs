@@ -8,6 +8,8 @@ peg::parser!(grammar e() for str {
     rule call() = "foo" nullable()* //~ ERROR
 
     rule more_complex() = ("x" / "a"? "b"?)*<2,> //~ ERROR
+
+    rule predicate() = (&{ true })+ //~ ERROR
 });
 
 fn main() {}
@@ -0,0 +1,13 @@
+extern crate peg;
+
+peg::parser!(grammar foo() for str {
+    rule item() = recover!("a", ";")
+
+    #[cache]
+    rule items() = item()* //~ ERROR
+
+    #[recover]
+    rule not_pub() = items() //~ ERROR
+});
+
+fn main() {}
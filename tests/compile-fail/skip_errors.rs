@@ -0,0 +1,27 @@
+extern crate peg;
+
+peg::parser!(grammar undefined() for str {
+    #![skip(whitespace)] //~ ERROR
+    rule pair() = "a" "b"
+});
+
+peg::parser!(grammar with_params() for str {
+    #![skip(ws)] //~ ERROR
+    rule ws(c: char) = [c]*
+    rule pair() = "a" "b"
+});
+
+peg::parser!(grammar lexical_call() for str {
+    #![skip(_)]
+    rule _ = [' ']*
+    #[lexical]
+    rule number() = digits() ("." digits())?
+    rule digits() = ['0'..='9']+ //~ ERROR
+});
+
+peg::parser!(grammar without_skip() for str {
+    #[lexical]
+    rule token() = "a" "b" //~ ERROR
+});
+
+fn main() {}
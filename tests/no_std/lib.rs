@@ -0,0 +1,67 @@
+//! Grammars compiled with `#![no_std]` and the `std` feature of `peg` disabled.
+//! The tests in `tests/` run them on the host.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+    Num(u32),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+peg::parser!{ pub grammar arith() for str {
+    rule _ = [' ']*
+
+    #[cache]
+    rule num() -> u32
+        = n:$(['0'..='9']+) {? n.parse().or(Err("u32")) }
+
+    pub rule expr() -> Expr = precedence!{
+        x:(@) _ "+" _ y:@ { Expr::Add(Box::new(x), Box::new(y)) }
+        --
+        x:(@) _ "*" _ y:@ { Expr::Mul(Box::new(x), Box::new(y)) }
+        --
+        "-" x:@ { Expr::Neg(Box::new(x)) }
+        --
+        n:num() { Expr::Num(n) }
+        "(" _ e:expr() _ ")" { e }
+    }
+
+    pub rule list() -> Vec<u32> = "[" _ l:(num() ** (_ "," _)) _ "]" { l }
+
+    #[cache_left_rec]
+    pub rule sum() -> u32
+        = a:sum() "+" b:num() { a + b }
+        / num()
+
+    pub rule keyword() = "select"i
+}}
+
+peg::parser!{ pub grammar bytes() for [u8] {
+    pub rule header() -> u8 = b"\x89PNG" [v] { v }
+}}
+
+peg::parser!{ pub grammar incremental() for str {
+    #![incremental]
+
+    #[cache]
+    rule item() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    pub rule items() -> Vec<u32> = item() ** ","
+}}
+
+peg::parser!{ pub grammar recovering() for str {
+    rule number() -> u32 = n:$(['0'..='9']+) ";" { n.parse().unwrap() }
+
+    rule stmt() -> Option<u32>
+        = recover!(n:number() { Some(n) }, [^';']* ";" { None })
+
+    #[recover]
+    pub rule stmts() -> Vec<Option<u32>> = stmt()*
+}}
@@ -0,0 +1,40 @@
+use peg_no_std_test::*;
+
+#[test]
+fn arith() {
+    assert_eq!(
+        arith::expr("-1 + 2 * (3)"),
+        Ok(Expr::Add(
+            Box::new(Expr::Neg(Box::new(Expr::Num(1)))),
+            Box::new(Expr::Mul(Box::new(Expr::Num(2)), Box::new(Expr::Num(3)))),
+        ))
+    );
+    assert_eq!(arith::list("[1, 2, 3]"), Ok(vec![1, 2, 3]));
+    assert_eq!(arith::sum("1+2+3"), Ok(6));
+    assert!(arith::keyword("SeLeCt").is_ok());
+
+    let err = arith::list("[1, x]").unwrap_err();
+    assert_eq!(err.location.column, 5);
+    assert_eq!(err.to_string(), "error at 1:5: expected one of [' '], ['0'..='9']");
+}
+
+#[test]
+fn bytes() {
+    assert_eq!(bytes::header(b"\x89PNG\x01"), Ok(1));
+    assert!(bytes::header(b"GIF89a").is_err());
+}
+
+#[test]
+fn incremental() {
+    let mut session = incremental::Session::new();
+    assert_eq!(session.items("1,2,3"), Ok(vec![1, 2, 3]));
+    session.edit(2..3, 2);
+    assert_eq!(session.items("1,22,3"), Ok(vec![1, 22, 3]));
+}
+
+#[test]
+fn recovery() {
+    let (value, errors) = recovering::stmts("1;x;3;");
+    assert_eq!(value, Some(vec![Some(1), None, Some(3)]));
+    assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,28 @@
+extern crate peg;
+
+peg::parser!{ grammar png() for [u8] {
+    rule u32_be() -> u32 = b:$([_]*<4>) { u32::from_be_bytes([b[0], b[1], b[2], b[3]]) }
+
+    rule chunk() -> (&'input [u8], u32)
+        = len:u32_be() ty:$([b'a'..=b'z' | b'A'..=b'Z']*<4>) [_]*<{len as usize}> u32_be() { (ty, len) }
+
+    pub rule file() -> Vec<(&'input [u8], u32)>
+        = b"\x89PNG\r\n\x1a\n" c:chunk()* { c }
+
+    pub rule escapes() = b"\"q\"\t\\\0'" br#"r"\x"#
+}}
+
+fn main() {
+    let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+    data.extend_from_slice(b"\x00\x00\x00\x02IHDR\xff\xfe\x00\x00\x00\x00");
+    data.extend_from_slice(b"\x00\x00\x00\x00IEND\x00\x00\x00\x00");
+    assert_eq!(png::file(&data), Ok(vec![(&b"IHDR"[..], 2), (&b"IEND"[..], 0)]));
+
+    let err = png::file(b"\x89PNx").unwrap_err();
+    assert_eq!(err.location, 0);
+    assert_eq!(format!("{}", err.expected), r#"b"\x89PNG\r\n\x1a\n""#);
+
+    assert_eq!(png::escapes(b"\"q\"\t\\\0'r\"\\x"), Ok(()));
+    let err = png::escapes(b"").unwrap_err();
+    assert_eq!(format!("{}", err.expected), r#"b"\"q\"\t\\\x00'""#);
+}
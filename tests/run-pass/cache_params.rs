@@ -0,0 +1,82 @@
+extern crate peg;
+
+use std::cell::Cell;
+
+peg::parser!{ grammar parser(calls: &Cell<u32>) for str {
+    #[cache]
+    rule kw(k: &'input str) -> &'input str
+        = s:$(['a'..='z']+) {? calls.set(calls.get() + 1); if s == k { Ok(s) } else { Err("keyword") } }
+
+    pub rule stmt() -> &'input str
+        = kw("let") " " v:$(['a'..='z']+) "!" { v }
+        / kw("let") " " v:$(['a'..='z']+) "?" { v }
+        / kw("var") " " v:$(['a'..='z']+) { v }
+
+    rule num() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+    rule word() -> u32 = w:$(['a'..='z']+) { w.len() as u32 }
+
+    // Keyed by call site, since the argument is a rule
+    #[cache]
+    rule list(x: rule<u32>) -> Vec<u32>
+        = "[" v:(x() ** ",") "]" { calls.set(calls.get() + 1); v }
+
+    rule nums() -> Vec<u32> = list(<num()>)
+    rule words() -> Vec<u32> = list(<word()>)
+
+    pub rule lists() -> Vec<u32>
+        = v:nums() "!" { v }
+        / v:nums() "?" { v }
+        / v:words() { v }
+
+    #[cache_left_rec]
+    rule chain(op: char) -> u32
+        = a:chain(op) [c if c == op] b:num() { a + b }
+        / num()
+
+    pub rule chains() -> u32
+        = a:chain('+') ";" { a }
+        / a:chain('-') { a }
+
+    // Generic rules can be cached if their type parameters aren't part of the key or value
+    #[cache]
+    rule count<T>(x: rule<T>) -> usize = v:x()* { v.len() }
+
+    pub rule counts() -> (usize, usize) = a:count(<"a">) b:count(<num()>) { (a, b) }
+}}
+
+peg::parser!{ grammar incremental() for str {
+    #![incremental]
+
+    #[cache]
+    rule padded(width: usize) -> u32
+        = n:$(['0'..='9']*<{width}>) { n.parse().unwrap() }
+
+    pub rule pairs() -> Vec<(u32, u32)> = (a:padded(2) b:padded(3) { (a, b) }) ** " "
+}}
+
+fn main() {
+    let calls = Cell::new(0);
+    assert_eq!(parser::stmt("let x?", &calls), Ok("x"));
+    assert_eq!(calls.get(), 1);
+
+    calls.set(0);
+    assert_eq!(parser::stmt("var y", &calls), Ok("y"));
+    assert_eq!(calls.get(), 2);
+
+    calls.set(0);
+    assert_eq!(parser::lists("[1,2]?", &calls), Ok(vec![1, 2]));
+    assert_eq!(calls.get(), 1);
+
+    calls.set(0);
+    assert_eq!(parser::lists("[ab,c]", &calls), Ok(vec![2, 1]));
+    assert_eq!(calls.get(), 1);
+
+    assert_eq!(parser::chains("1+2+3;", &calls), Ok(6));
+    assert_eq!(parser::chains("1-2", &calls), Ok(3));
+    assert_eq!(parser::counts("aaa12", &calls), Ok((3, 1)));
+
+    let mut session = incremental::Session::new();
+    assert_eq!(session.pairs("12345 67890"), Ok(vec![(12, 345), (67, 890)]));
+    session.edit(0..1, 1);
+    assert_eq!(session.pairs("92345 67890"), Ok(vec![(92, 345), (67, 890)]));
+}
@@ -0,0 +1,48 @@
+extern crate peg;
+
+use std::rc::Rc;
+
+// Not `Clone`: cached results are shared instead
+#[derive(Debug)]
+pub enum Expr {
+    Num(u32),
+    Add(Rc<Expr>, Rc<Expr>),
+    List(Vec<Rc<Expr>>),
+}
+
+peg::parser!{ grammar parser() for str {
+    #[cache(shared)]
+    rule atom() -> Expr
+        = n:$(['0'..='9']+) { Expr::Num(n.parse().unwrap()) }
+        / "[" l:(expr() ** ",") "]" { Expr::List(l) }
+
+    #[cache_left_rec(shared, dense)]
+    pub rule expr() -> Expr
+        = a:expr() "+" b:atom() { Expr::Add(a, b) }
+        / a:atom() { Expr::List(vec![a]) }
+
+    // Both calls return the same value
+    pub rule twice() -> (Rc<Expr>, Rc<Expr>) = a:&atom() b:atom() { (a, b) }
+}}
+
+peg::parser!{ grammar incremental() for str {
+    #![incremental]
+
+    #[cache(shared)]
+    rule num() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    pub rule nums() -> Vec<Rc<u32>> = num() ** ","
+}}
+
+fn main() {
+    let e = parser::expr("1+[2,3+4]").unwrap();
+    assert_eq!(format!("{:?}", e), "Add(List([Num(1)]), List([List([Num(2)]), Add(List([Num(3)]), Num(4))]))");
+
+    let (a, b) = parser::twice("[1,2]").unwrap();
+    assert!(Rc::ptr_eq(&a, &b));
+
+    let mut session = incremental::Session::new();
+    let first = session.nums("1,2,3").unwrap();
+    let second = session.nums("1,2,3").unwrap();
+    assert!(first.iter().zip(&second).all(|(a, b)| Rc::ptr_eq(a, b)));
+}
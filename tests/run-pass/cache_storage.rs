@@ -0,0 +1,177 @@
+extern crate peg;
+
+use peg::memo::{MemoTable, Storage, AUTO_DENSE_MAX_LEN};
+use std::cell::Cell;
+
+thread_local! {
+    static WORDS_PARSED: Cell<usize> = Cell::new(0);
+}
+
+fn words_parsed() -> usize {
+    WORDS_PARSED.with(|c| c.replace(0))
+}
+
+fn count() {
+    WORDS_PARSED.with(|c| c.set(c.get() + 1))
+}
+
+peg::parser!{ grammar per_rule() for str {
+    #[cache(dense)]
+    rule word_dense() -> () = ['a'..='z']+ { count() }
+
+    #[cache(chunked)]
+    rule word_chunked() -> () = ['A'..='Z']+ { count() }
+
+    #[cache(hash)]
+    rule word_hash() -> () = ['0'..='9']+ { count() }
+
+    // Each word is tried twice at the same position
+    pub rule words() -> usize
+        = w:((word_dense() "." / word_dense() "," / word_chunked() "!" / word_chunked() "?" / word_hash() ";" / word_hash() ":") ** " ") { w.len() }
+
+    #[cache_left_rec(dense)]
+    pub rule sum() -> u32 = a:sum() "+" b:num() { a + b } / num()
+
+    rule num() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+}}
+
+peg::parser!{ grammar per_grammar() for str {
+    #![cache(chunked)]
+
+    #[cache]
+    rule word_dense() -> () = ['a'..='z']+ { count() }
+
+    #[cache]
+    rule word_chunked() -> () = ['A'..='Z']+ { count() }
+
+    #[cache(hash)]
+    rule word_hash() -> () = ['0'..='9']+ { count() }
+
+    pub rule words() -> usize
+        = w:((word_dense() "." / word_dense() "," / word_chunked() "!" / word_chunked() "?" / word_hash() ";" / word_hash() ":") ** " ") { w.len() }
+
+    #[cache_left_rec]
+    pub rule sum() -> u32 = a:sum() "+" b:num() { a + b } / num()
+
+    rule num() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+}}
+
+peg::parser!{ grammar auto() for str {
+    // No storage selected, so it is chosen by the length of the input
+    #[cache]
+    rule word() -> () = ['a'..='z']+ { count() }
+
+    pub rule words() -> usize = w:((word() "." / word() ",") ** " ") { w.len() }
+}}
+
+peg::parser!{ grammar pruned() for str {
+    #[cache(dense)]
+    rule word_dense() -> () = ['a'..='z']+ { count() }
+
+    #[cache(chunked)]
+    rule word_chunked() -> () = ['A'..='Z']+ { count() }
+
+    #[cache(hash)]
+    rule word_hash() -> () = ['0'..='9']+ { count() }
+
+    rule word() = word_dense() / word_chunked() / word_hash()
+
+    // The cut drops the entries before the statement, which can still backtrack to its
+    // start, so the words are found in the caches when `tagged()` fails
+    rule tagged() = word() ~ "."
+
+    rule statement() -> () = tagged() {} / word() "," {}
+
+    pub rule program() -> usize = s:(statement() ** " ") { s.len() }
+}}
+
+peg::parser!{ grammar incremental() for str {
+    #![incremental]
+    #![cache(dense)]
+
+    #[cache]
+    rule word() -> () = ['a'..='z']+ { count() }
+
+    pub rule words() -> usize = w:((word() "." / word() ",") ** " ") { w.len() }
+}}
+
+fn main() {
+    // Words past the first chunk, and long words leaving chunks with no entries
+    let mut input = String::new();
+    for i in 0..300 {
+        if i > 0 {
+            input.push(' ');
+        }
+        input.push_str(["abc,", "XYZ?", "123:"][i % 3]);
+        if i % 50 == 0 {
+            input.push(' ');
+            input.push_str(&"q".repeat(200));
+            input.push('.');
+        }
+    }
+
+    assert_eq!(per_rule::words(&input), Ok(306));
+    assert_eq!(words_parsed(), 306);
+    assert_eq!(per_grammar::words(&input), Ok(306));
+    assert_eq!(words_parsed(), 306);
+
+    let long = vec!["abc,"; 20_000].join(" ");
+    assert_eq!(per_rule::words(&long), Ok(20_000));
+    assert_eq!(words_parsed(), 20_000);
+
+    // Without a storage, short inputs get dense tables and long ones chunked tables
+    let short = "ab, cd.";
+    assert_eq!(auto::words(short), Ok(2));
+    assert_eq!(words_parsed(), 2);
+    assert_eq!(auto::words(&long), Ok(20_000));
+    assert_eq!(words_parsed(), 20_000);
+
+    let auto_storage = |len| MemoTable::<()>::new(Storage::Auto, len).storage();
+    assert_eq!(auto_storage(Some(short.len())), Storage::Dense);
+    assert_eq!(auto_storage(Some(AUTO_DENSE_MAX_LEN)), Storage::Dense);
+    assert!(long.len() > AUTO_DENSE_MAX_LEN);
+    assert_eq!(auto_storage(Some(long.len())), Storage::Chunked);
+    assert_eq!(auto_storage(None), Storage::Chunked);
+    assert_eq!(MemoTable::<()>::new(Storage::Hash, Some(short.len())).storage(), Storage::Hash);
+
+    assert_eq!(per_rule::sum("1+2+3"), Ok(6));
+    assert_eq!(per_grammar::sum("1+2+3"), Ok(6));
+
+    // Statements of several lengths, so that cuts fall at every offset in a chunk
+    let mut program = String::new();
+    for i in 0..300 {
+        if i > 0 {
+            program.push(' ');
+        }
+        program.push_str(["abc,", "XYZ.", "123,"][i % 3]);
+        if i % 50 == 0 {
+            program.push(' ');
+            program.push_str(&"q".repeat(60 + i / 50));
+            program.push('.');
+        }
+    }
+    assert_eq!(pruned::program(&program), Ok(306));
+    assert_eq!(words_parsed(), 306);
+
+    // Cuts drop the entries before a position, across the chunks of a chunked table
+    for &storage in &[Storage::Dense, Storage::Chunked, Storage::Hash] {
+        let mut table = MemoTable::new(storage, Some(300));
+        for pos in 0..300 {
+            table.insert(pos, pos);
+        }
+        for &cut in &[10, 130, 192, 50] {
+            table.drop_before(cut);
+        }
+        assert!((0..192).all(|pos| table.get(pos).is_none()), "{:?}", storage);
+        assert!((192..300).all(|pos| table.get(pos) == Some(&pos)), "{:?}", storage);
+        table.insert(5, 5);
+        assert_eq!(table.get(5), Some(&5), "{:?}", storage);
+    }
+
+    let mut session = incremental::Session::new();
+    assert_eq!(session.words("ab, cd, ef."), Ok(3));
+    assert_eq!(words_parsed(), 3);
+    session.edit(4..6, 3);
+    assert_eq!(session.words("ab, xyz, ef."), Ok(3));
+    assert_eq!(words_parsed(), 1);
+}
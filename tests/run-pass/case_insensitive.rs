@@ -0,0 +1,44 @@
+extern crate peg;
+
+peg::parser!{ grammar sql() for str {
+    rule _() = [' ']*
+    rule ident() -> &'input str = $(['a'..='z' | 'A'..='Z']+)
+
+    pub rule select() -> Vec<&'input str>
+        = "select"i _ cols:(ident() ++ (_ "," _)) _ "from"i _ ident() { cols }
+
+    pub rule greek() = "σοφία"i
+    pub rule sigma() = "ΑΣ"i
+    pub rule kelvin() -> usize = "k"i p:position!() { p }
+    pub rule raw() = r#"a"b"#i
+}}
+
+peg::parser!{ grammar bytes() for [u8] {
+    pub rule cmd() -> usize = "get"i " " n:$([b'0'..=b'9']+) { n.len() }
+}}
+
+fn main() {
+    assert_eq!(sql::select("SELECT a, b FROM t"), Ok(vec!["a", "b"]));
+    assert_eq!(sql::select("select a from t"), Ok(vec!["a"]));
+    assert_eq!(sql::select("SeLeCt a fRoM t"), Ok(vec!["a"]));
+
+    let err = sql::select("selec a from t").unwrap_err();
+    assert_eq!(err.location.offset, 0);
+    assert_eq!(format!("{}", err.expected), r#""select"i"#);
+
+    // Unicode case folding, including final sigma
+    assert_eq!(sql::greek("ΣΟΦΊΑ"), Ok(()));
+    assert_eq!(sql::greek("σοφία"), Ok(()));
+    assert!(sql::greek("σοφια").is_err());
+    assert_eq!(sql::sigma("ας"), Ok(()));
+    assert_eq!(sql::sigma("ασ"), Ok(()));
+
+    // a match can have a different length than the literal
+    assert_eq!(sql::kelvin("\u{212A}"), Ok(3));
+    assert_eq!(sql::kelvin("K"), Ok(1));
+
+    assert_eq!(sql::raw("A\"B"), Ok(()));
+
+    assert_eq!(bytes::cmd(b"GeT 123"), Ok(3));
+    assert!(bytes::cmd(b"GET").is_err());
+}
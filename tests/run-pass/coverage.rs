@@ -0,0 +1,59 @@
+extern crate peg;
+
+peg::parser!( grammar parser() for str {
+    pub rule value() -> u32
+        = n:number() { n }
+        / "(" v:value() ")" { v }
+        / "-" ("-" / "+")? v:value() { v }
+
+    rule number() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    rule unused() = "x"
+});
+
+peg::parser!( grammar other() for str {
+    pub rule word() = ['a'..='z']+ / ['A'..='Z']+
+});
+
+#[cfg(feature = "coverage")]
+fn main() {
+    assert_eq!(parser::value("1"), Ok(1));
+    assert_eq!(parser::value("(2)"), Ok(2));
+    assert!(parser::value("x").is_err());
+
+    let coverage = parser::coverage();
+    assert_eq!(coverage.hits("parser", "value"), Some(3));
+    assert_eq!(coverage.hits("parser", "number"), Some(2));
+    assert_eq!(coverage.hits("parser", "value/0/0"), Some(2));
+    assert_eq!(coverage.hits("parser", "value/0/1"), Some(1));
+    assert_eq!(coverage.hits("parser", "value/0/2"), Some(0));
+    assert_eq!(coverage.hits("parser", "value/1/0"), Some(0));
+
+    let uncovered: Vec<_> = coverage.uncovered().map(|p| p.id).collect();
+    assert_eq!(uncovered, ["value/0/2", "value/1/0", "value/1/1", "unused"]);
+
+    let point = coverage.uncovered().next().unwrap();
+    assert_eq!(point.rule, "value");
+    assert!(point.location.ends_with("coverage.rs:7:11"), "{}", point.location);
+
+    // Counts accumulate over all parses
+    assert_eq!(parser::value("--3"), Ok(3));
+    let coverage = parser::coverage();
+    assert_eq!(coverage.hits("parser", "value"), Some(5));
+    assert_eq!(coverage.uncovered().map(|p| p.id).collect::<Vec<_>>(), ["value/1/1", "unused"]);
+
+    // Coverage of several grammars can be combined
+    assert!(other::word("abc").is_ok());
+    let mut combined = parser::coverage();
+    combined.merge(&other::coverage());
+    combined.merge(&other::coverage());
+    assert_eq!(combined.hits("other", "word/0/0"), Some(2));
+    assert_eq!(combined.uncovered().count(), 3);
+    assert!(combined.to_string().starts_with("8 of 11 rules and alternatives matched\n"));
+}
+
+#[cfg(not(feature = "coverage"))]
+fn main() {
+    // Without the feature, there are no counts, nor a `peg::coverage` module
+    assert_eq!(parser::value("1"), Ok(1));
+}
@@ -0,0 +1,54 @@
+extern crate peg;
+
+peg::parser!( grammar parser() for str {
+    pub rule with_cut() -> u32 = "a" ~ "b" { 1 } / "a" "c" { 2 }
+    pub rule without_cut() -> u32 = "a" "b" { 1 } / "a" "c" { 2 }
+
+    // A cut commits to the alternative of the innermost choice only
+    pub rule nested() -> u32 = ("x" ~ "y" / "x") "z" { 1 } / "xz" { 2 }
+
+    // Cuts in optional and repeated expressions still commit to the choice around them
+    pub rule optional() -> u32 = ("-" ~ "-")? "a" { 1 } / "-b" { 2 }
+
+    rule _ = [' ']*
+
+    #[cache]
+    rule ident() -> &'input str = $(['a'..='z']+)
+
+    rule number() -> i64 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    rule statement() -> String
+        = "let" ~ _ name:ident() _ "=" _ value:number() ";" { format!("{}={}", name, value) }
+        / "print" ~ _ name:ident() ";" { format!("print {}", name) }
+        / name:ident() ";" { name.to_owned() }
+
+    pub rule program() -> Vec<String> = (s:statement() _ { s })*
+
+    #[cache_left_rec]
+    pub rule sum() -> i64 = a:sum() "+" ~ b:number() { a + b } / number()
+});
+
+fn main() {
+    assert_eq!(parser::with_cut("ab"), Ok(1));
+    assert!(parser::with_cut("ac").is_err());
+    assert_eq!(parser::without_cut("ac"), Ok(2));
+
+    assert_eq!(parser::nested("xyz"), Ok(1));
+    assert_eq!(parser::nested("xz"), Ok(2));
+
+    assert_eq!(parser::optional("--a"), Ok(1));
+    assert_eq!(parser::optional("a"), Ok(1));
+    assert!(parser::optional("-b").is_err());
+
+    assert_eq!(
+        parser::program("let x = 1; print x; other; y;"),
+        Ok(vec!["x=1".to_owned(), "print x".to_owned(), "other".to_owned(), "y".to_owned()])
+    );
+
+    // Without the cut, `let;` would be a statement naming `let`
+    let err = parser::program("x; let;").unwrap_err();
+    assert_eq!(err.location.offset, 6);
+
+    assert_eq!(parser::sum("1+2+3"), Ok(6));
+    assert!(parser::sum("1+2+").is_err());
+}
@@ -0,0 +1,45 @@
+extern crate peg;
+
+peg::parser!( grammar parser() for str {
+    // Rust raw strings: the closing quote must be followed by as many `#` as the opening one
+    pub rule raw_string() -> &'input str
+        = "r" hashes:$("#"*) "\"" body:$((!("\"" literal!(hashes)) [_])*) "\"" literal!(hashes) { body }
+
+    rule name() -> &'input str = $(['a'..='z']+)
+
+    pub rule element() -> usize
+        = "<" tag:name() ">" children:element()* "</" literal!(tag) ">" { 1 + children.iter().sum::<usize>() }
+
+    // Any expression dereferencing to `str` can be matched, such as a `String`
+    pub rule shout() = word:name() " " literal!(word.to_uppercase())
+
+    pub rule keyword(word: &str) = literal!(word) / "x" / ['0'..='9']
+});
+
+fn main() {
+    assert_eq!(parser::raw_string(r####"r"abc""####), Ok("abc"));
+    assert_eq!(parser::raw_string(r####"r##"a "# b"##"####), Ok("a \"# b"));
+    assert!(parser::raw_string(r####"r##"abc"#"####).is_err());
+
+    assert_eq!(parser::element("<a><b></b><c></c></a>"), Ok(3));
+
+    let err = parser::element("<a><b></a>").unwrap_err();
+    assert_eq!(err.location.offset, 8);
+    assert_eq!(err.expected.dynamic_literals().collect::<Vec<_>>(), ["\"b\""]);
+    assert!(err.expected.is_empty());
+    assert_eq!(err.expected.tokens().count(), 0);
+    assert_eq!(err.to_string(), "error at 1:9: expected \"b\"");
+
+    assert!(parser::shout("hey HEY").is_ok());
+    assert!(parser::shout("hey hey").is_err());
+
+    // Dynamic literals aren't among the tokens, but are part of the message
+    assert!(parser::keyword("if", "if").is_ok());
+    let err = parser::keyword("?", "if").unwrap_err();
+    assert_eq!(err.expected.tokens().collect::<Vec<_>>(), ["\"x\"", "['0'..='9']"]);
+    assert_eq!(err.expected.dynamic_literals().collect::<Vec<_>>(), ["\"if\""]);
+    assert_eq!(err.expected.len(), 2);
+    assert_eq!(err.expected.len(), err.expected.tokens().count());
+    assert!(!err.expected.is_empty());
+    assert_eq!(err.expected.to_string(), "one of \"if\", \"x\", ['0'..='9']");
+}
@@ -0,0 +1,58 @@
+extern crate peg;
+
+use peg::error::Expected;
+
+peg::parser!{ grammar lang() for str {
+    rule _ = quiet!{[' ' | '\n']*}
+
+    rule ident() = quiet!{['a'..='z']+} / expected!("identifier")
+
+    rule expression() = ident() / "(" _ expression() _ ")"
+
+    rule statement() = "let" _ ident() _ "=" _ expression() _ ";"
+
+    #[context("function body")]
+    rule body() = "{" _ (statement() _)* "}"
+
+    pub rule function() = "fn" _ ident() _ "(" _ ")" _ body()
+
+    #[recover]
+    pub rule functions() = (_ recover!(function(), (!"fn" [_])+))* _
+}}
+
+fn main() {
+    let err = lang::function("fn f() { let x = (; }").unwrap_err();
+    assert_eq!(err.location.offset, 18);
+    assert_eq!(err.context(), ["function", "function body", "statement", "expression", "expression"]);
+    assert_eq!(err.expected.context_of(&Expected::Named("identifier")), Some(&["function", "function body", "statement", "expression", "expression", "ident"][..]));
+    assert_eq!(err.expected.context_of(&Expected::Literal("\"(\"")), Some(&["function", "function body", "statement", "expression", "expression"][..]));
+
+    // The context is what the expected items have in common
+    let err = lang::function("fn f() { let x = y }").unwrap_err();
+    assert_eq!(err.expected.to_string(), r#"";""#);
+    assert_eq!(err.context(), ["function", "function body", "statement"]);
+
+    let err = lang::function("fn f() { x }").unwrap_err();
+    assert_eq!(err.expected.to_string(), r#"one of "let", "}""#);
+    assert_eq!(err.context(), ["function", "function body"]);
+    assert_eq!(err.context().join(" > "), "function > function body");
+
+    // Errors expecting the same items are equal, whatever their rule stacks
+    let a = lang::function("fn (").unwrap_err();
+    let b = lang::function("fn f() { let = x; }").unwrap_err();
+    assert_ne!(a.context(), b.context());
+    assert_eq!(a.expected, b.expected);
+    assert_eq!(
+        a.expected.contexts().collect::<Vec<_>>(),
+        [(Expected::Named("identifier"), &["function", "ident"][..])]
+    );
+
+    // Errors at the end of the input have no context
+    let err = lang::function("fn f() {} x").unwrap_err();
+    assert_eq!(err.context(), [] as [&str; 0]);
+
+    // Errors skipped by `recover!()`
+    let (_, errors) = lang::functions("fn f() { let = x; } fn g() {}");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].context(), ["function", "function body", "statement", "ident"]);
+}
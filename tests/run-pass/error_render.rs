@@ -0,0 +1,65 @@
+extern crate peg;
+
+peg::parser!{ grammar parser() for str {
+    rule ws() = quiet!{[' ' | '\t']*}
+    rule stmt() = ws() ['a'..='z' | '日' | '本']+ ws() "=" ws() number() ws() ";" ws()
+    rule number() = quiet!{['0'..='9']+} / expected!("number")
+    pub rule stmts() = (stmt() "\n")* stmt()?
+}}
+
+fn main() {
+    let src = "a = 1;\nb = 2 3;\nc = 4;\n";
+    let err = parser::stmts(src).unwrap_err();
+    assert_eq!(err.render(src, "input.txt"), r#"error: expected ";"
+ --> input.txt:2:7
+  |
+1 | a = 1;
+2 | b = 2 3;
+  |       ^
+3 | c = 4;
+  |
+"#);
+
+    // tabs are expanded to 4-column stops
+    let src = "a\t=\t1;\n\tb = x;";
+    let err = parser::stmts(src).unwrap_err();
+    assert_eq!(err.location.column, 6);
+    assert_eq!(err.render(src, "tabs.txt"), r#"error: expected number
+ --> tabs.txt:2:6
+  |
+1 | a   =   1;
+2 |     b = x;
+  |         ^
+  |
+"#);
+
+    // wide characters take two columns, and a wide character at the error is underlined fully
+    let src = "日本 = 1;日";
+    let err = parser::stmts(src).unwrap_err();
+    assert_eq!(err.render(src, "wide.txt"), r#"error: expected one of "\n", EOF
+ --> wide.txt:1:8
+  |
+1 | 日本 = 1;日
+  |          ^^
+  |
+"#);
+
+    // an offset inside a character, as from other input, is moved back to its start
+    let mut inside = err.clone();
+    inside.location.offset += 1;
+    assert_eq!(inside.render(src, "wide.txt"), err.render(src, "wide.txt"));
+    inside.location.offset = 1;
+    assert!(inside.render(src, "wide.txt").contains("1 | 日本 = 1;日\n  | ^^\n"));
+
+    // error at end of input, with a wide gutter
+    let src = "a=1;\n".repeat(9) + "b=";
+    let err = parser::stmts(&src).unwrap_err();
+    assert_eq!(err.render(&src, "eof.txt"), r#"error: expected number
+  --> eof.txt:10:3
+   |
+ 9 | a=1;
+10 | b=
+   |   ^
+   |
+"#);
+}
@@ -0,0 +1,46 @@
+extern crate peg;
+
+use peg::error::Expected;
+
+peg::parser!{ grammar parser() for str {
+    rule number() = quiet!{['0'..='9']+} / expected!("number")
+
+    rule value() = number() / "(" value() ")" / ['a']
+
+    pub rule list() = value() ** ","
+
+    pub rule checked() -> u32
+        = n:$(['0'..='9']+) {? n.parse().or(Err("u32")) }
+}}
+
+fn main() {
+    let err = parser::list("1,x").unwrap_err();
+    assert_eq!(err.location.offset, 2);
+    assert_eq!(
+        err.expected.iter().collect::<Vec<_>>(),
+        [Expected::Literal("\"(\""), Expected::Pattern("['a']"), Expected::Named("number")]
+    );
+    assert_eq!(err.expected.literals().collect::<Vec<_>>(), ["\"(\""]);
+    assert_eq!(err.expected.patterns().collect::<Vec<_>>(), ["['a']"]);
+    assert_eq!(err.expected.names().collect::<Vec<_>>(), ["number"]);
+    assert!(!err.expected.expects_eof());
+    assert_eq!(err.expected.len(), 3);
+    assert_eq!(err.expected.to_string(), r#"one of "(", ['a'], number"#);
+
+    // The end of input is reported separately from a literal "EOF"
+    let err = parser::list("1)").unwrap_err();
+    assert!(err.expected.expects_eof());
+    assert!(err.expected.contains(&Expected::Literal("\",\"")));
+    assert!(!err.expected.contains(&Expected::Named("EOF")));
+    assert_eq!(err.expected.tokens().collect::<Vec<_>>(), ["\",\"", "EOF"]);
+
+    // Errors from `{? }` blocks are named
+    let err = parser::checked("99999999999").unwrap_err();
+    assert_eq!(err.expected.names().collect::<Vec<_>>(), ["u32"]);
+
+    // The order does not depend on the order of failures
+    for _ in 0..10 {
+        let err = parser::list("(((").unwrap_err();
+        assert_eq!(err.expected.to_string(), r#"one of "(", ['a'], number"#);
+    }
+}
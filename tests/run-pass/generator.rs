@@ -0,0 +1,72 @@
+extern crate peg;
+
+use peg::generate::{Rng, Unsatisfiable};
+
+peg::parser!( grammar list() for str {
+    #![generator]
+
+    pub rule list() -> Vec<u32> = "[" items:(number() ** ",") "]" { items }
+
+    pub rule number() -> u32 = n:$(['1'..='9'] ['0'..='9']*<,3>) { n.parse().unwrap() }
+
+    pub rule string() -> String = "\"" s:$([^ '"' | '\\']*) "\"" { s.to_owned() }
+
+    pub rule word() = ['a'..='z' | '_']+ / "-"*<2> "x"i
+
+    pub rule nested() = "(" nested() ")" / "<>"
+
+    pub rule ident() = !keyword() ['a'..='z']+ / "$" ['a'..='z']+
+
+    rule keyword() = "if"
+
+    pub rule even() -> u32 = n:number() {? if n % 2 == 0 { Ok(n) } else { Err("even") } }
+
+    rule repeated(r: rule<()>) = r()*
+
+    pub rule expr() -> i64 = precedence! {
+        x:(@) "+" y:@ { x.wrapping_add(y) }
+        x:(@) "-" y:@ { x.wrapping_sub(y) }
+        --
+        x:(@) "*" y:@ { x.wrapping_mul(y) }
+        --
+        x:@ "^" y:(@) { x.wrapping_pow(y as u32) }
+        --
+        "-" x:@ { x.wrapping_neg() }
+        --
+        n:number() { n as i64 }
+        "(" e:expr() ")" { e }
+    }
+});
+
+fn main() {
+    for seed in 0..200 {
+        let mut rng = Rng::new(seed);
+        let input = list::generator::list(&mut rng, 3).unwrap();
+        assert!(list::list(&input).is_ok(), "{:?}", input);
+        let input = list::generator::string(&mut rng, 3).unwrap();
+        assert!(list::string(&input).is_ok(), "{:?}", input);
+        let input = list::generator::word(&mut rng, 3).unwrap();
+        assert!(list::word(&input).is_ok(), "{:?}", input);
+        let input = list::generator::nested(&mut rng, 3).unwrap();
+        assert!(list::nested(&input).is_ok(), "{:?}", input);
+        let input = list::generator::ident(&mut rng, 3).unwrap();
+        assert!(list::ident(&input).is_ok(), "{:?}", input);
+        let input = list::generator::expr(&mut rng, 4).unwrap();
+        assert!(list::expr(&input).is_ok(), "{:?}", input);
+    }
+
+    // The same seed gives the same input
+    let a = list::generator::expr(&mut Rng::new(7), 5).unwrap();
+    let b = list::generator::expr(&mut Rng::new(7), 5).unwrap();
+    assert_eq!(a, b);
+
+    // Depth 0 takes the shortest way through each rule
+    assert_eq!(list::generator::nested(&mut Rng::new(1), 0), Ok("<>".to_owned()));
+
+    // Rules that can't be generated are listed, and report why
+    assert_eq!(list::generator::UNSATISFIABLE, ["even", "repeated"]);
+    assert_eq!(
+        list::generator::even(&mut Rng::new(1), 3),
+        Err(Unsatisfiable { rule: "even", reason: "`{? }` action" })
+    );
+}
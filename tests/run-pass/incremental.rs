@@ -0,0 +1,81 @@
+extern crate peg;
+
+use std::cell::Cell;
+
+thread_local! {
+    static ITEMS_PARSED: Cell<usize> = Cell::new(0);
+}
+
+fn items_parsed() -> usize {
+    ITEMS_PARSED.with(|c| c.replace(0))
+}
+
+peg::parser!{ grammar list() for str {
+    #![incremental]
+
+    #[cache]
+    rule item() -> u32
+        = n:$(['0'..='9']+) {
+            ITEMS_PARSED.with(|c| c.set(c.get() + 1));
+            n.parse().unwrap()
+        }
+
+    #[cache_left_rec]
+    rule sum() -> u32
+        = a:sum() "+" b:item() { a + b }
+        / item()
+
+    pub rule items() -> Vec<u32> = item() ** ";"
+
+    pub rule sums() -> Vec<u32> = sum() ** ";"
+}}
+
+fn main() {
+    let mut session = list::Session::new();
+
+    assert_eq!(session.items("1;22;333"), Ok(vec![1, 22, 333]));
+    assert_eq!(items_parsed(), 3);
+
+    // nothing changed, so every item comes from the cache
+    assert_eq!(session.items("1;22;333"), Ok(vec![1, 22, 333]));
+    assert_eq!(items_parsed(), 0);
+
+    // replace "22" with "4444": only the new item is parsed, and "333" is found at its new position
+    session.edit(2..4, 4);
+    assert_eq!(session.items("1;4444;333"), Ok(vec![1, 4444, 333]));
+    assert_eq!(items_parsed(), 1);
+
+    // appending to the last item invalidates it, because it looked for more digits at the end
+    session.edit(10..10, 1);
+    assert_eq!(session.items("1;4444;3335"), Ok(vec![1, 4444, 3335]));
+    assert_eq!(items_parsed(), 1);
+
+    // inserting before the start shifts everything
+    session.edit(0..0, 2);
+    assert_eq!(session.items("7;1;4444;3335"), Ok(vec![7, 1, 4444, 3335]));
+    assert_eq!(items_parsed(), 1);
+
+    // deleting "1;"
+    session.edit(2..4, 0);
+    assert_eq!(session.items("7;4444;3335"), Ok(vec![7, 4444, 3335]));
+    assert_eq!(items_parsed(), 0);
+
+    // errors are the same as without a session
+    session.edit(2..6, 1);
+    assert_eq!(session.items("7;x;3335"), list::items("7;x;3335"));
+    assert_eq!(session.items("7;x;3335").unwrap_err().location.offset, 2);
+    items_parsed();
+
+    // left recursive rules
+    let mut session = list::Session::new();
+    assert_eq!(session.sums("1+2+3;4"), Ok(vec![6, 4]));
+    assert_eq!(items_parsed(), 4);
+
+    session.edit(2..3, 2);
+    assert_eq!(session.sums("1+20+3;4"), Ok(vec![24, 4]));
+    assert_eq!(items_parsed(), 1);
+
+    session.edit(5..5, 2);
+    assert_eq!(session.sums("1+20+5+3;4"), Ok(vec![29, 4]));
+    assert_eq!(items_parsed(), 1);
+}
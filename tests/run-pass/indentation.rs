@@ -0,0 +1,125 @@
+extern crate peg;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Stmt {
+    If(String, Vec<Stmt>),
+    Expr(u32),
+}
+
+peg::parser!( grammar layout() for str {
+    // Line ends, with any blank lines after them
+    rule newline() = ([' ' | '\t']* "\n")+
+
+    pub rule program() -> Vec<Stmt> = s:statement() ++ (newline() same_indent!()) newline()? { s }
+
+    rule statement() -> Stmt
+        = "if " c:$(['a'..='z']+) ":" b:block() { Stmt::If(c.to_owned(), b) }
+        / e:sum() { Stmt::Expr(e) }
+
+    #[cache]
+    rule block() -> Vec<Stmt> = newline() indent!() s:statement() ++ (newline() same_indent!()) dedent!() { s }
+
+    #[cache_left_rec]
+    rule sum() -> u32 = a:sum() "+" b:number() { a + b } / number()
+
+    rule number() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    // The same position is parsed with two different indentation stacks, and the
+    // cache keeps a result for each
+    #[cache]
+    rule aligned() = same_indent!() ['a'..='z']
+
+    pub rule cached() -> u32
+        = indent!() "a\n" aligned() "!" { 1 }
+        / [' ']* "a\n" aligned() { 2 }
+
+    // A lookahead doesn't change the indentation
+    pub rule lookahead() = &indent!() [' ']* "x"
+});
+
+peg::parser!( grammar session() for str {
+    #![incremental]
+
+    #[cache]
+    rule item() -> &'static str = "x" { "x" } / "if:" block() { "if" }
+
+    rule block() = "\n" indent!() item() ++ ("\n" same_indent!()) dedent!()
+
+    pub rule items() -> Vec<&'static str> = item() ++ ("\n" same_indent!())
+});
+
+peg::parser!( grammar bytes() for [u8] {
+    pub rule block() = "a:\n" indent!() "b\n" same_indent!() "c" dedent!()
+});
+
+peg::parser!( grammar recovering() for str {
+    rule skip() = [^ '\n']*
+
+    // The re-runs that find the error start from the block's indentation
+    rule line() = recover!(same_indent!() "x", skip())
+
+    #[recover]
+    pub rule block() = "b:\n" indent!() "x" ("\n" line())* dedent!()
+});
+
+peg::parser!( grammar outline() for str {
+    // A result recalled while growing the cycle keeps the indentation it left
+    #[cache_left_rec]
+    rule entries() -> usize = n:previous() "\n" same_indent!() "y" { n + 1 } / "a:\n" indent!() "x" { 0 }
+
+    rule previous() -> usize = entries()
+
+    pub rule doc() -> usize = n:entries() dedent!() { n }
+});
+
+fn main() {
+    use Stmt::*;
+
+    let source = "if a:\n  1\n  if b:\n    2+3\n\n  4\n5\n";
+    assert_eq!(
+        layout::program(source),
+        Ok(vec![
+            If("a".to_owned(), vec![Expr(1), If("b".to_owned(), vec![Expr(5)]), Expr(4)]),
+            Expr(5)
+        ])
+    );
+
+    // A tab advances to the next multiple of 8 columns
+    assert_eq!(layout::program("if a:\n\t1\n        2"), Ok(vec![If("a".to_owned(), vec![Expr(1), Expr(2)])]));
+    assert!(layout::program("if a:\n\t1\n    2").is_err());
+    assert_eq!(layout::program("if a:\n    \t1\n\t2"), Ok(vec![If("a".to_owned(), vec![Expr(1), Expr(2)])]));
+
+    let err = layout::program("if a:\n1").unwrap_err();
+    assert_eq!(err.location.offset, 6);
+    assert!(err.expected.names().any(|n| n == "indentation"), "{}", err);
+
+    let err = layout::program("if a:\n  1\n 2").unwrap_err();
+    assert_eq!(err.location.offset, 11);
+
+    assert!(layout::cached("  a\n  b").is_err());
+    assert_eq!(layout::cached("  a\nb"), Ok(2));
+
+    assert!(layout::lookahead("  x").is_ok());
+    assert!(layout::lookahead("x").is_err());
+
+    assert!(bytes::block(b"a:\n\tb\n        c").is_ok());
+    assert!(bytes::block(b"a:\nb\nc").is_err());
+
+    let mut session = session::Session::new();
+    assert_eq!(session.items("if:\n x\n x\nx"), Ok(vec!["if", "x"]));
+    assert_eq!(session.items("if:\n x\n x\nx"), Ok(vec!["if", "x"]));
+    session.edit(7..8, 2);
+    assert!(session.items("if:\n x\n  x\nx").is_err());
+    session.edit(7..9, 1);
+    assert_eq!(session.items("if:\n x\n x\nx"), Ok(vec!["if", "x"]));
+
+    assert_eq!(recovering::block("b:\n  x\n  x"), (Some(()), vec![]));
+    let (res, errors) = recovering::block("b:\n  x\n  y");
+    assert_eq!(res, Some(()));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "error at 3:3: expected \"x\"");
+
+    assert_eq!(outline::doc("a:\n  x"), Ok(0));
+    assert_eq!(outline::doc("a:\n  x\n  y\n  y"), Ok(2));
+    assert!(outline::doc("a:\n  x\n y").is_err());
+}
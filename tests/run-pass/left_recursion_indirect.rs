@@ -0,0 +1,84 @@
+extern crate peg;
+
+peg::parser!( grammar mutual() for str {
+    // Only one rule of the cycle needs the annotation
+    #[cache_left_rec]
+    pub rule a() -> String
+        = b:b() "x" { format!("({}x)", b) }
+        / "y" { "y".to_owned() }
+
+    // Cached results of other rules in the cycle aren't used while it grows
+    #[cache]
+    pub rule b() -> String
+        = a:a() "z" { format!("({}z)", a) }
+});
+
+peg::parser!( grammar java() for str {
+    // The example from "Packrat Parsers Can Support Left Recursion" (Warth et al.)
+    #[cache_left_rec]
+    pub rule primary() -> String = primary_no_new_array()
+
+    rule primary_no_new_array() -> String
+        = method_invocation()
+        / field_access()
+        / array_access()
+        / "this" { "this".to_owned() }
+
+    rule method_invocation() -> String = p:primary() "." i:id() "()" { format!("call({},{})", p, i) }
+    rule field_access() -> String = p:primary() "." i:id() { format!("field({},{})", p, i) }
+    rule array_access() -> String = p:primary() "[" i:id() "]" { format!("index({},{})", p, i) }
+
+    rule id() -> &'input str = $(['a'..='z']+)
+});
+
+peg::parser!( grammar nested() for str {
+    // `b` is also left recursive on its own, so both rules are marked, and either can be
+    // the head that grows the result
+    #[cache_left_rec]
+    pub rule a() -> String
+        = b()
+        / "a" { "a".to_owned() }
+
+    #[cache_left_rec]
+    pub rule b() -> String
+        = b:b() "b" { format!("({}b)", b) }
+        / a()
+
+    pub rule both() -> (String, String) = x:&a() y:b() { (x, y) }
+});
+
+peg::parser!( grammar incremental() for str {
+    #![incremental]
+
+    #[cache_left_rec]
+    pub rule list() -> Vec<u32> = items()
+
+    rule items() -> Vec<u32>
+        = l:list() "," n:num() { let mut l = l; l.push(n); l }
+        / n:num() { vec![n] }
+
+    rule num() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+});
+
+fn main() {
+    assert_eq!(mutual::a("y"), Ok("y".to_owned()));
+    assert_eq!(mutual::a("yzx"), Ok("((yz)x)".to_owned()));
+    assert_eq!(mutual::a("yzxzx"), Ok("((((yz)x)z)x)".to_owned()));
+    assert_eq!(mutual::b("yz"), Ok("(yz)".to_owned()));
+    assert_eq!(mutual::b("yzxz"), Ok("(((yz)x)z)".to_owned()));
+    assert!(mutual::a("yz").is_err());
+
+    assert_eq!(java::primary("this"), Ok("this".to_owned()));
+    assert_eq!(java::primary("this.x"), Ok("field(this,x)".to_owned()));
+    assert_eq!(java::primary("this.x.y()"), Ok("call(field(this,x),y)".to_owned()));
+    assert_eq!(java::primary("this.x[i].y()[j]"), Ok("index(call(index(field(this,x),i),y),j)".to_owned()));
+
+    assert_eq!(nested::a("abb"), Ok("((ab)b)".to_owned()));
+    assert_eq!(nested::b("abb"), Ok("((ab)b)".to_owned()));
+    assert_eq!(nested::both("abb"), Ok(("((ab)b)".to_owned(), "((ab)b)".to_owned())));
+
+    let mut session = incremental::Session::new();
+    assert_eq!(session.list("1,2,3"), Ok(vec![1, 2, 3]));
+    session.edit(4..5, 2);
+    assert_eq!(session.list("1,2,34"), Ok(vec![1, 2, 34]));
+}
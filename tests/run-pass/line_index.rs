@@ -0,0 +1,44 @@
+extern crate peg;
+
+use peg::str::LineIndex;
+use peg::Parse;
+
+peg::parser!{ grammar words() for str {
+    rule word() -> (usize, usize) = s:position!() ['a'..='z' | 'é' | '日']+ e:position!() { (s, e) }
+    rule sep() = (" " / "\n")+
+    pub rule words() -> Vec<(usize, usize)> = word() ** sep()
+}}
+
+fn main() {
+    let input = "abc dé\n\n日日 x\nlast";
+    let index = LineIndex::new(input);
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.line(2), Some(""));
+    assert_eq!(index.line(3), Some("日日 x"));
+    assert_eq!(index.line(5), None);
+    assert_eq!(index.line_range(1), Some(0..8));
+
+    // agrees with position_repr at every char boundary, and converts back
+    for offset in (0..=input.len()).filter(|&i| input.is_char_boundary(i)) {
+        let lc = index.line_col(offset);
+        assert_eq!(lc, input.position_repr(offset));
+        assert_eq!(index.offset(lc.line, lc.column), Some(offset));
+    }
+
+    assert_eq!(index.offset(1, 0), None);
+    assert_eq!(index.offset(1, 7), Some(7));
+    assert_eq!(index.offset(1, 8), None);
+    assert_eq!(index.offset(4, 5), Some(input.len()));
+    assert_eq!(index.offset(0, 1), None);
+
+    let spans: Vec<_> = words::words(input).unwrap().into_iter()
+        .map(|(s, e)| (index.line_col(s), index.line_col(e)))
+        .map(|(s, e)| (s.line, s.column, e.line, e.column))
+        .collect();
+    assert_eq!(spans, vec![(1, 1, 1, 4), (1, 5, 1, 7), (3, 1, 3, 3), (3, 4, 3, 5), (4, 1, 4, 5)]);
+
+    let trailing = LineIndex::new("a\n");
+    assert_eq!(trailing.line_count(), 2);
+    assert_eq!(trailing.line_col(2).line, 2);
+    assert_eq!(LineIndex::new("").line_col(0).column, 1);
+}
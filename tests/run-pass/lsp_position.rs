@@ -0,0 +1,65 @@
+extern crate peg;
+
+use peg::str::{ColumnUnit, LineIndex, Position};
+
+peg::parser!{ grammar list() for str {
+    rule item() = ['a'..='z' | 'é' | '😀']+
+    pub rule list() = item() ** ("," "\n"?)
+}}
+
+fn pos(line: usize, character: usize) -> Position {
+    Position { line, character }
+}
+
+fn main() {
+    // 'é' is 2 bytes and one UTF-16 unit, '😀' is 4 bytes and two UTF-16 units
+    let input = "é😀a,\nb😀,\n1";
+    let index = LineIndex::new(input);
+
+    let err = list::list(input).unwrap_err();
+    assert_eq!(err.location.offset, 16);
+    assert_eq!(index.position(err.location.offset, ColumnUnit::Utf16), pos(2, 0));
+
+    let a = input.find('a').unwrap();
+    assert_eq!(index.position(a, ColumnUnit::Utf8), pos(0, 6));
+    assert_eq!(index.position(a, ColumnUnit::Utf16), pos(0, 3));
+    assert_eq!(index.position(a, ColumnUnit::Char), pos(0, 2));
+    assert_eq!(index.line_col_in(a, ColumnUnit::Utf16).column, 4);
+    assert_eq!(index.line_col_in(a, ColumnUnit::Char), index.line_col(a));
+
+    // round trip at every char boundary, in every unit
+    for &unit in &[ColumnUnit::Utf8, ColumnUnit::Utf16, ColumnUnit::Char] {
+        for offset in (0..=input.len()).filter(|&i| input.is_char_boundary(i)) {
+            assert_eq!(index.position_offset(index.position(offset, unit), unit), Some(offset));
+            let lc = index.line_col_in(offset, unit);
+            assert_eq!(index.offset_in(lc.line, lc.column, unit), Some(offset));
+        }
+    }
+
+    // inside a surrogate pair: the start of the character, or no exact offset
+    assert_eq!(index.position_offset(pos(0, 2), ColumnUnit::Utf16), Some(2));
+    assert_eq!(index.offset_in(1, 3, ColumnUnit::Utf16), None);
+
+    // past the end of a line: the end of the line
+    assert_eq!(index.position_offset(pos(1, 100), ColumnUnit::Utf16), Some(15));
+    assert_eq!(index.offset_in(2, 100, ColumnUnit::Utf16), None);
+
+    // past the last line
+    assert_eq!(index.position_offset(pos(3, 0), ColumnUnit::Utf16), None);
+
+    assert_eq!(ColumnUnit::Utf16.count("é😀a"), 4);
+
+    // `\r\n` is a line break, so the end of the line is before the `\r`
+    let input = "ab\r\ncd\r\n";
+    let index = LineIndex::new(input);
+    assert_eq!(index.line(1), Some("ab"));
+    assert_eq!(index.position_offset(pos(0, 100), ColumnUnit::Utf16), Some(2));
+    assert_eq!(index.position_offset(pos(1, 2), ColumnUnit::Utf16), Some(6));
+    assert_eq!(index.position_offset(pos(1, 3), ColumnUnit::Utf16), Some(6));
+    assert_eq!(index.position_offset(pos(2, 0), ColumnUnit::Utf16), Some(8));
+    assert_eq!(index.offset(1, 3), Some(2));
+    assert_eq!(index.offset(1, 4), None);
+    for offset in [0, 2, 4, 6, 8] {
+        assert_eq!(index.position_offset(index.position(offset, ColumnUnit::Utf16), ColumnUnit::Utf16), Some(offset));
+    }
+}
@@ -0,0 +1,97 @@
+extern crate peg;
+
+use peg::error::ErrorKind;
+use peg::Limits;
+
+peg::parser!( grammar parens() for str {
+    pub rule nested() -> usize
+        = "(" n:nested() ")" { n + 1 }
+        / "" { 0 }
+});
+
+peg::parser!( grammar limited() for str {
+    #![max_depth(10)]
+
+    pub rule nested() -> usize
+        = "(" n:nested() ")" { n + 1 }
+        / "" { 0 }
+
+    // A stopped parse fails even where the failing rule was optional
+    pub rule optional() -> usize
+        = n:nested()? "!" { n.unwrap_or(0) }
+});
+
+peg::parser!( grammar arithmetic() for str {
+    pub rule expr() -> i64 = precedence! {
+        x:(@) "+" y:@ { x + y }
+        --
+        "-" x:@ { -x }
+        --
+        n:$(['0'..='9']+) { n.parse().unwrap() }
+    }
+});
+
+peg::parser!( grammar recovering() for str {
+    rule nested() = "(" nested() ")" / "x"
+
+    rule item() = recover!(nested() ";", [^';']* ";")
+
+    #[recover]
+    pub rule items() = item()*
+});
+
+peg::parser!( grammar incremental() for str {
+    #![incremental]
+
+    pub rule nested() -> usize
+        = "(" n:nested() ")" { n + 1 }
+        / "" { 0 }
+});
+
+fn main() {
+    let deep = "(".repeat(20) + &")".repeat(20);
+    assert_eq!(parens::nested(&deep), Ok(20));
+
+    let err = parens::nested_with_limits(&deep, &Limits::default().with_max_depth(5)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NestingTooDeep);
+    assert_eq!(err.location.offset, 5);
+    assert_eq!(err.to_string(), "nesting too deep at 1:6");
+
+    let err = limited::nested(&deep).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NestingTooDeep);
+    assert_eq!(limited::nested("((()))"), Ok(3));
+    assert!(limited::optional(&(deep.clone() + "!")).is_err());
+    assert_eq!(limited::optional("!"), Ok(0));
+
+    // Limits passed in override the grammar's
+    assert_eq!(limited::nested_with_limits(&deep, &Limits::default().with_max_depth(100)), Ok(20));
+
+    // Syntax errors are unchanged
+    let err = parens::nested_with_limits("(()", &Limits::default().with_max_depth(5)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Syntax);
+
+    let negated = "-".repeat(50) + "1";
+    assert_eq!(arithmetic::expr(&negated), Ok(1));
+    let err = arithmetic::expr_with_limits(&negated, &Limits::default().with_max_depth(20)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NestingTooDeep);
+
+    // Errors recovered before the parse stopped aren't returned with the limit's error
+    let items = format!("(x);y;{}x{};", "(".repeat(20), ")".repeat(20));
+    let (value, errors) = recovering::items(&items);
+    assert_eq!(value, Some(()));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].location.offset, 4);
+
+    let (value, errors) = recovering::items_with_limits(&items, &Limits::default().with_max_depth(10));
+    assert_eq!(value, None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::NestingTooDeep);
+
+    let mut session = incremental::Session::new();
+    session.set_limits(Limits::default().with_max_depth(5));
+    assert_eq!(session.nested("(())"), Ok(2));
+    let err = session.nested(&deep).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NestingTooDeep);
+    session.set_limits(Limits::default());
+    assert_eq!(session.nested(&deep), Ok(20));
+}
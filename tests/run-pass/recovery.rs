@@ -0,0 +1,83 @@
+extern crate peg;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, PartialEq)]
+pub enum Stmt {
+    Assign(String, u32),
+    Error,
+}
+
+peg::parser!{ grammar config() for str {
+    rule assign() -> Stmt
+        = k:$(['a'..='z']) "=" v:number() ";" { Stmt::Assign(k.to_owned(), v.parse().unwrap()) }
+
+    rule number() -> &'input str
+        = quiet!{$(['0'..='9']+)} / expected!("number")
+
+    rule statement() -> Stmt
+        = recover!(assign(), ";" { Stmt::Error })
+
+    #[recover]
+    pub rule file() -> Vec<Stmt> = statement()*
+
+    pub rule file_strict() -> Vec<Stmt> = statement()*
+
+    #[recover]
+    pub rule choice() -> Stmt
+        = "[" s:statement() "]" { s }
+        / "(" s:statement() ")" { s }
+}}
+
+static ITEM_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+peg::parser!{ grammar nested() for str {
+    rule item() = &{ ITEM_RUNS.fetch_add(1, Ordering::Relaxed); true } "x"
+
+    // The inner recover!() never synchronizes, so its failure makes the outer one fail too
+    #[recover]
+    pub rule group() = recover!("(" recover!(item(), "never") ")", "!")
+}}
+
+fn assign(k: &str, v: u32) -> Stmt {
+    Stmt::Assign(k.to_owned(), v)
+}
+
+fn main() {
+    use Stmt::*;
+
+    assert_eq!(config::file("a=1;b=2;"), (Some(vec![assign("a", 1), assign("b", 2)]), vec![]));
+
+    let (stmts, errors) = config::file("a=1;b=x;c=3;d;");
+    assert_eq!(stmts, Some(vec![assign("a", 1), Error, assign("c", 3), Error]));
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].location.offset, 6);
+    assert_eq!(errors[0].expected.to_string(), "number");
+    assert_eq!(errors[1].location.offset, 13);
+    assert_eq!(errors[1].expected.to_string(), r#""=""#);
+
+    // Plain entry points report the first recovered error
+    let err = config::file_strict("a=1;b=x;c=3;").unwrap_err();
+    assert_eq!(err.location.offset, 6);
+    assert_eq!(config::file_strict("a=1;"), Ok(vec![assign("a", 1)]));
+
+    // Nothing to synchronize on: the error is reported like a normal failure
+    let (stmts, errors) = config::file("a=1;b=2");
+    assert_eq!(stmts, None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].location.offset, 7);
+    assert_eq!(errors[0].expected.to_string(), r#"";""#);
+
+    // Errors recovered in an alternative that is backtracked over are discarded
+    let (stmt, errors) = config::choice("(x;)");
+    assert_eq!(stmt, Some(Error));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].location.offset, 2);
+
+    // A failing recover!() runs its expression three times, so each level of nesting
+    // multiplies the runs of what's inside by three
+    let (value, errors) = nested::group("(y!");
+    assert_eq!(value, Some(()));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(ITEM_RUNS.load(Ordering::Relaxed), 9);
+}
@@ -0,0 +1,37 @@
+extern crate peg;
+
+peg::parser!( grammar parser() for str {
+    rule number() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    // Predicates see the labels before them in the sequence
+    pub rule byte() -> u32 = n:number() &{ n < 256 } { n }
+
+    pub rule not_zero() -> u32 = n:number() !{ n == 0 } { n }
+
+    // A failing predicate falls through to the next alternative
+    pub rule size() -> &'static str
+        = n:number() &{ n < 10 } { "small" }
+        / n:number() { "large" }
+
+    // Matching without consuming input, a predicate can guard a repetition
+    pub rule digits(max: usize) -> &'input str = d:$(['0'..='9']*) &{ d.len() <= max } { d }
+
+    pub rule closing() -> bool = open:$("("*) close:$(")"*) !{ open.len() != close.len() } { true }
+});
+
+fn main() {
+    assert_eq!(parser::byte("200"), Ok(200));
+    assert!(parser::byte("300").is_err());
+
+    assert_eq!(parser::not_zero("7"), Ok(7));
+    assert!(parser::not_zero("0").is_err());
+
+    assert_eq!(parser::size("3"), Ok("small"));
+    assert_eq!(parser::size("30"), Ok("large"));
+
+    assert_eq!(parser::digits("123", 3), Ok("123"));
+    assert!(parser::digits("1234", 3).is_err());
+
+    assert_eq!(parser::closing("(())"), Ok(true));
+    assert!(parser::closing("(()").is_err());
+}
@@ -0,0 +1,86 @@
+extern crate peg;
+
+use peg::generate::Rng;
+
+peg::parser!( grammar calc() for str {
+    #![skip(_)]
+
+    rule _ = [' ' | '\n']*
+
+    #[lexical]
+    rule number() -> i64 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    #[lexical]
+    pub rule ident() -> &'input str = $(['a'..='z'] ['a'..='z' | '0'..='9']*)
+
+    // The whitespace around the whole input is still up to the grammar
+    pub rule call() -> (&'input str, Vec<i64>) = _ f:ident() "(" args:(number() ** ",") ")" _ { (f, args) }
+
+    pub rule words() -> Vec<&'input str> = ident()+
+
+    pub rule pair() -> (i64, i64) = "(" a:number() "," b:number() ")" { (a, b) }
+
+    // A position!() is where the element before it ended, or where its sequence started
+    pub rule span() -> (usize, usize) = "(" s:(start:position!() ident() end:position!() { (start, end) }) ")" { s }
+
+    pub rule expr() -> i64 = precedence! {
+        x:(@) "+" y:@ { x + y }
+        x:(@) "-" y:@ { x - y }
+        --
+        x:(@) "*" y:@ { x * y }
+        --
+        "-" x:@ { -x }
+        --
+        x:@ "!" { (1..=x).product() }
+        --
+        n:number() { n }
+        "(" e:expr() ")" { e }
+    }
+
+    pub rule sum() -> Vec<i64> = e:expr() ** ";" { e }
+});
+
+peg::parser!( grammar list() for str {
+    #![generator]
+    #![skip(_)]
+
+    rule _ = " "*
+
+    #[lexical]
+    rule word() = ['a'..='z']+
+
+    pub rule list() = "[" (word() ** ",") "]" word()*
+});
+
+fn main() {
+    assert_eq!(calc::call("f(1,2)"), Ok(("f", vec![1, 2])));
+    assert_eq!(calc::call("  f ( 1 , 2 )\n"), Ok(("f", vec![1, 2])));
+    assert_eq!(calc::call("f()"), Ok(("f", vec![])));
+
+    // Lexical rules match their elements next to each other
+    assert_eq!(calc::call("f(1 2)").unwrap_err().location.offset, 4);
+    assert!(calc::ident("a b").is_err());
+
+    assert_eq!(calc::words("ab cd  e1"), Ok(vec!["ab", "cd", "e1"]));
+    assert_eq!(calc::pair("( 3,4 )"), Ok((3, 4)));
+    assert_eq!(calc::span("( ab )"), Ok((2, 4)));
+
+    assert_eq!(calc::expr("1 + 2 * 3"), Ok(7));
+    assert_eq!(calc::expr("1+2*3"), Ok(7));
+    assert_eq!(calc::expr("- ( 1 + 2 ) * 3 !"), Ok(-18));
+    assert_eq!(calc::expr("10 - 2 - 3"), Ok(5));
+    assert_eq!(calc::sum("1 + 1 ; 2 * 2"), Ok(vec![2, 4]));
+
+    // The skip rule isn't listed in errors
+    let err = calc::expr("1 +").unwrap_err();
+    assert_eq!(err.location.offset, 3);
+    let tokens: Vec<&str> = err.expected.tokens().collect();
+    assert_eq!(tokens.len(), 3, "{:?}", tokens);
+    assert_eq!(tokens[..2], ["\"(\"", "\"-\""]);
+
+    // The generator puts the skip rule's input between the elements
+    for seed in 0..100 {
+        let input = list::generator::list(&mut Rng::new(seed), 3).unwrap();
+        assert!(list::list(&input).is_ok(), "{:?}", input);
+    }
+}
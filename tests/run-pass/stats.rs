@@ -0,0 +1,59 @@
+extern crate peg;
+
+peg::parser!( grammar parser() for str {
+    rule number() -> u32 = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+    // Every alternative starts by parsing the same `number`
+    pub rule uncached() -> u32
+        = n:number() "+" { n } / n:number() "-" { n } / n:number() "*" { n } / n:number()
+
+    #[cache]
+    rule cached_number() -> u32 = number()
+
+    pub rule cached() -> u32
+        = n:cached_number() "+" { n } / n:cached_number() "-" { n } / n:cached_number() "*" { n } / n:cached_number()
+});
+
+#[cfg(feature = "stats")]
+fn main() {
+    let (result, stats) = parser::uncached_with_stats("123");
+    assert_eq!(result, Ok(123));
+
+    let number = stats.get("number").unwrap();
+    assert_eq!(number.calls, 4);
+    assert_eq!(number.matches, 4);
+    assert_eq!(number.failures, 0);
+    assert_eq!(number.consumed, 12);
+    assert_eq!(number.repeats, 3);
+    assert_eq!(number.max_at_position, 4);
+    assert!(number.is_cache_candidate());
+    assert_eq!(stats.cache_candidates(), ["number"]);
+    assert_eq!(stats.by_cost()[0].0, "number");
+
+    let (result, stats) = parser::cached_with_stats("123");
+    assert_eq!(result, Ok(123));
+
+    let cached = stats.get("cached_number").unwrap();
+    assert_eq!(cached.calls, 4);
+    assert_eq!(cached.cache_misses, 1);
+    assert_eq!(cached.cache_hits, 3);
+    assert_eq!(cached.evaluations(), 1);
+    assert_eq!(stats.get("number").unwrap().calls, 1);
+    assert!(stats.cache_candidates().is_empty());
+
+    // Failed parses report the first pass only, not the reparse for the error
+    let (result, stats) = parser::uncached_with_stats("x");
+    assert!(result.is_err());
+    assert_eq!(stats.get("number").unwrap().failures, 4);
+
+    let table = parser::uncached_with_stats("1").1.to_string();
+    assert!(table.starts_with("rule "));
+    assert!(table.contains("consider #[cache]"));
+}
+
+#[cfg(not(feature = "stats"))]
+fn main() {
+    // Without the `stats` feature no statistics are collected
+    assert_eq!(parser::uncached("123"), Ok(123));
+    assert!(peg::stats::ParseStats::default().get("number").is_none());
+}
@@ -0,0 +1,52 @@
+extern crate peg;
+
+use peg::error::ErrorKind;
+use peg::Limits;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+peg::parser!( grammar backtracking() for str {
+    // Each `a` followed by "?" is parsed twice, so the work doubles with every
+    // nested parenthesis
+    pub rule a() = "(" a() ")" "!" / "(" a() ")" "?" / "x"
+
+    pub rule count(n: &std::cell::Cell<u32>) -> u32 = "x"+ { n.set(n.get() + 1); n.get() }
+});
+
+fn main() {
+    assert!(backtracking::a("((x)!)!").is_ok());
+
+    let slow = "(".repeat(30) + "x" + &")?".repeat(30);
+    let err = backtracking::a_with_limits(&slow, &Limits::default().with_max_steps(10_000)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Aborted);
+    assert_eq!(err.to_string(), format!("parse aborted at {}", err.location));
+
+    let fast = "(".repeat(5) + "x" + &")!".repeat(5);
+    assert!(backtracking::a_with_limits(&fast, &Limits::default().with_max_steps(10_000)).is_ok());
+
+    // Errors found within the budget are reported as usual
+    let err = backtracking::a_with_limits("((x)!)", &Limits::default().with_max_steps(10_000)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Syntax);
+
+    let err = backtracking::a_with_limits("x", &Limits::default().with_max_steps(0)).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Aborted);
+    assert_eq!(err.location.offset, 0);
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let limits = Limits::default().with_cancel(cancel.clone());
+    assert!(backtracking::a_with_limits(&fast, &limits).is_ok());
+    cancel.store(true, Ordering::Relaxed);
+    let err = backtracking::a_with_limits(&slow, &limits).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Aborted);
+
+    // Limits with the same flag are equal
+    assert_eq!(limits, Limits::default().with_cancel(cancel.clone()));
+    assert_ne!(limits, Limits::default().with_cancel(Arc::new(AtomicBool::new(true))));
+    assert_eq!(Limits::default().with_max_steps(5), Limits::default().with_max_steps(5));
+
+    // Actions don't run once the parse is aborted
+    let n = std::cell::Cell::new(0);
+    assert!(backtracking::count_with_limits("xx", &Limits::default().with_max_steps(0), &n).is_err());
+    assert_eq!(n.get(), 0);
+    assert_eq!(backtracking::count("xx", &n), Ok(1));
+}
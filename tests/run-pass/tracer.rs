@@ -0,0 +1,112 @@
+extern crate peg;
+
+use peg::trace::Tracer;
+
+peg::parser!( grammar parser() for str {
+    #[cache]
+    rule digit() -> u32 = d:$(['0'..='9']) { d.parse().unwrap() }
+
+    rule letter() = ['a'..='z']
+
+    pub rule items() -> Vec<u32> = (digit() / letter() { 0 }) ** ","
+
+    pub rule sum() -> u32 = digit() "+" digit() "?" { 0 } / a:digit() "+" b:digit() { a + b }
+
+    pub rule expr() -> u32 = precedence! {
+        x:(@) "+" y:@ { x + y }
+        --
+        n:digit() { n }
+    }
+});
+
+peg::parser!( grammar session() for str {
+    #![incremental]
+
+    #[cache]
+    rule digit() -> u32 = d:$(['0'..='9']) { d.parse().unwrap() }
+
+    pub rule items() -> Vec<u32> = digit() ** ","
+});
+
+#[derive(Default)]
+struct Collect {
+    events: Vec<String>,
+}
+
+// Only follows `digit`, showing how a tracer can filter by rule
+impl Tracer for Collect {
+    fn rule_enter(&mut self, rule: &'static str, pos: usize) {
+        if rule == "digit" {
+            self.events.push(format!("enter {}", pos));
+        }
+    }
+
+    fn rule_match(&mut self, rule: &'static str, start: usize, end: usize) {
+        if rule == "digit" {
+            self.events.push(format!("match {}..{}", start, end));
+        }
+    }
+
+    fn rule_fail(&mut self, rule: &'static str, pos: usize) {
+        if rule == "digit" {
+            self.events.push(format!("fail {}", pos));
+        }
+    }
+
+    fn cache_hit(&mut self, rule: &'static str, pos: usize, end: Option<usize>) {
+        self.events.push(format!("cached {} {} {:?}", rule, pos, end));
+    }
+
+    fn precedence_enter(&mut self, level: i32, pos: usize) {
+        self.events.push(format!("level {} at {}", level, pos));
+    }
+
+    fn error_reparse(&mut self) {
+        self.events.push("reparse".to_owned());
+    }
+}
+
+#[cfg(feature = "trace")]
+fn main() {
+    let mut tracer = Collect::default();
+    assert_eq!(parser::items_with_tracer("1,a", &mut tracer), Ok(vec![1, 0]));
+    assert_eq!(tracer.events, ["enter 0", "match 0..1", "enter 2", "fail 2"]);
+
+    let mut tracer = Collect::default();
+    assert_eq!(parser::sum_with_tracer("1+2", &mut tracer), Ok(3));
+    assert_eq!(tracer.events, ["enter 0", "match 0..1", "enter 2", "match 2..3", "cached digit 0 Some(1)", "cached digit 2 Some(3)"]);
+
+    let mut tracer = Collect::default();
+    assert_eq!(parser::expr_with_tracer("1+2", &mut tracer), Ok(3));
+    assert!(tracer.events.contains(&"level 0 at 1".to_owned()));
+
+    // The run that finds the error is marked
+    let mut tracer = Collect::default();
+    assert!(parser::sum_with_tracer("1-", &mut tracer).is_err());
+    assert_eq!(tracer.events, ["enter 0", "match 0..1", "cached digit 0 Some(1)", "reparse", "enter 0", "match 0..1", "cached digit 0 Some(1)"]);
+
+    // A session's parses can be traced too, and find the error without its caches
+    let mut session = session::Session::new();
+    let mut tracer = Collect::default();
+    assert_eq!(session.items_with_tracer("1,2", &mut tracer), Ok(vec![1, 2]));
+    assert_eq!(tracer.events, ["enter 0", "match 0..1", "enter 2", "match 2..3"]);
+
+    session.edit(2..3, 1);
+    let mut tracer = Collect::default();
+    assert!(session.items_with_tracer("1,x", &mut tracer).is_err());
+    assert_eq!(tracer.events, [
+        "cached digit 0 Some(1)", "enter 2", "fail 2",
+        "reparse", "enter 0", "match 0..1", "enter 2", "fail 2",
+        "reparse", "enter 0", "match 0..1", "enter 2", "fail 2",
+    ]);
+}
+
+#[cfg(not(feature = "trace"))]
+fn main() {
+    // Without the `trace` feature there are no `_with_tracer` functions, but
+    // tracers can still be written.
+    let mut tracer = Collect::default();
+    tracer.rule_enter("digit", 0);
+    assert_eq!(parser::items("1,a"), Ok(vec![1, 0]));
+    assert_eq!(tracer.events, ["enter 0"]);
+}